@@ -28,7 +28,12 @@ pub enum McpError {
     
     /// JSON-RPC 2.0 invalid params (-32602)
     #[error("Invalid params: {message}")]
-    InvalidParams { message: String },
+    InvalidParams {
+        message: String,
+        /// Name of the offending argument, when known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        argument: Option<String>,
+    },
     
     /// JSON-RPC 2.0 internal error (-32603)
     #[error("Internal error: {message}")]
@@ -40,7 +45,13 @@ pub enum McpError {
     
     /// MCP-specific tool execution error
     #[error("Tool execution failed: {tool_name} - {message}")]
-    ToolExecutionError { tool_name: String, message: String },
+    ToolExecutionError {
+        tool_name: String,
+        message: String,
+        /// Name of the offending argument, when the failure was caused by one
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        argument: Option<String>,
+    },
     
     /// Transport-level error
     #[error("Transport error: {message}")]
@@ -67,6 +78,27 @@ impl McpError {
         }
     }
     
+    /// Structured context for the JSON-RPC error `data` field.
+    ///
+    /// Only variants that carry more than a message produce data; the rest
+    /// return `None` so the error object stays as before.
+    pub fn to_json_rpc_data(&self) -> Option<serde_json::Value> {
+        match self {
+            McpError::InvalidParams { argument: Some(argument), .. } => {
+                Some(serde_json::json!({ "argument": argument }))
+            }
+            McpError::ToolNotFound { tool_name } => Some(serde_json::json!({ "tool": tool_name })),
+            McpError::ToolExecutionError { tool_name, argument, .. } => {
+                let mut data = serde_json::json!({ "tool": tool_name });
+                if let Some(argument) = argument {
+                    data["argument"] = serde_json::json!(argument);
+                }
+                Some(data)
+            }
+            _ => None,
+        }
+    }
+    
     /// Create a new parse error.
     pub fn parse_error(message: impl Into<String>) -> Self {
         Self::ParseError { message: message.into() }
@@ -84,7 +116,12 @@ impl McpError {
     
     /// Create a new invalid params error.
     pub fn invalid_params(message: impl Into<String>) -> Self {
-        Self::InvalidParams { message: message.into() }
+        Self::InvalidParams { message: message.into(), argument: None }
+    }
+    
+    /// Create a new invalid params error naming the offending argument.
+    pub fn invalid_argument(argument: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::InvalidParams { message: message.into(), argument: Some(argument.into()) }
     }
     
     /// Create a new internal error.
@@ -101,7 +138,8 @@ impl McpError {
     pub fn tool_execution_error(tool_name: impl Into<String>, message: impl Into<String>) -> Self {
         Self::ToolExecutionError { 
             tool_name: tool_name.into(), 
-            message: message.into() 
+            message: message.into(),
+            argument: None,
         }
     }
     
@@ -3,10 +3,11 @@
 //! A Rust library implementing the Anthropic Model Context Protocol (MCP).
 //! 
 //! This library provides a complete implementation of the MCP protocol over JSON-RPC 2.0,
-//! supporting both stdio and named pipe transports. It includes:
+//! supporting stdio, named pipe and WebSocket transports. It includes:
 //! 
 //! - Complete MCP protocol message types and serialization
 //! - Tool registry and invocation system
+//! - Prompt templates served over `prompts/list` and `prompts/get`
 //! - Error handling following MCP specification
 //! - Logging and tracing integration
 //! - Async runtime support with Tokio
@@ -28,18 +29,23 @@
 //!         }
 //!     ));
 //!     
-//!     server.run_stdio().await
+//!     server.run_stdio().await?;
+//!     Ok(())
 //! }
 //! ```
 
 pub mod error;
+pub mod prompts;
 pub mod protocol;
 pub mod registry;
 pub mod server;
 pub mod transport;
+#[cfg(feature = "output-validation")]
+pub mod validation;
 
 pub use error::{McpError, McpResult};
+pub use prompts::{PromptInput, PromptRegistry, PromptTemplate};
 pub use protocol::*;
-pub use registry::{Tool, ToolInput, ToolRegistry, ToolResult};
+pub use registry::{ProgressReporter, Tool, ToolCatalog, ToolInput, ToolRegistry, ToolResult};
 pub use server::Server;
-pub use transport::{StdioTransport, Transport};
+pub use transport::{StdioTransport, Transport, WebSocketTransport};
@@ -0,0 +1,158 @@
+//! Prompt registry for MCP servers.
+//!
+//! Prompts are named message templates a client can list with
+//! `prompts/list` and fill in with `prompts/get`. Each template declares
+//! its arguments and renders them into messages; rendering is synchronous
+//! since a prompt only arranges text.
+
+use crate::error::{McpError, McpResult};
+use crate::protocol::{GetPromptResult, Prompt, PromptArgument, PromptMessage};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Type alias for prompt render functions.
+///
+/// Renderers receive the filled-in arguments and return the prompt messages.
+/// Required arguments are checked before the renderer runs.
+pub type PromptRenderer = Arc<dyn Fn(&PromptInput) -> McpResult<Vec<PromptMessage>> + Send + Sync>;
+
+/// Arguments passed to prompt renderers.
+#[derive(Debug, Clone)]
+pub struct PromptInput {
+    /// The name of the prompt being rendered.
+    pub name: String,
+    /// The arguments sent with `prompts/get`.
+    pub arguments: HashMap<String, String>,
+}
+
+/// A registered prompt template with its metadata and renderer.
+#[derive(Clone)]
+pub struct PromptTemplate {
+    /// The prompt definition (name, description, arguments).
+    pub definition: Prompt,
+    /// The render function for this prompt.
+    pub renderer: PromptRenderer,
+}
+
+/// Registry for managing MCP prompts.
+///
+/// Prompts are listed in name order.
+#[derive(Default)]
+pub struct PromptRegistry {
+    prompts: BTreeMap<String, PromptTemplate>,
+}
+
+impl PromptInput {
+    /// Get an argument, failing if it is missing or blank.
+    pub fn get_argument(&self, key: &str) -> McpResult<&str> {
+        self.get_optional_argument(key)
+            .ok_or_else(|| McpError::invalid_argument(key, format!("Missing required argument: {}", key)))
+    }
+
+    /// Get an argument, or `None` if it is missing or blank.
+    pub fn get_optional_argument(&self, key: &str) -> Option<&str> {
+        self.arguments
+            .get(key)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+impl PromptTemplate {
+    /// Create a new prompt template without arguments.
+    ///
+    /// Declare arguments with [`PromptTemplate::with_argument`].
+    pub fn new<F>(name: impl Into<String>, description: impl Into<String>, renderer: F) -> Self
+    where
+        F: Fn(&PromptInput) -> McpResult<Vec<PromptMessage>> + Send + Sync + 'static,
+    {
+        Self {
+            definition: Prompt {
+                name: name.into(),
+                description: Some(description.into()),
+                arguments: Vec::new(),
+            },
+            renderer: Arc::new(renderer),
+        }
+    }
+
+    /// Declare an argument the prompt accepts.
+    pub fn with_argument(mut self, name: impl Into<String>, description: impl Into<String>, required: bool) -> Self {
+        self.definition.arguments.push(PromptArgument {
+            name: name.into(),
+            description: Some(description.into()),
+            required: Some(required),
+        });
+        self
+    }
+
+    /// Render the prompt after checking its required arguments are present.
+    pub fn render(&self, arguments: HashMap<String, String>) -> McpResult<GetPromptResult> {
+        let input = PromptInput {
+            name: self.definition.name.clone(),
+            arguments,
+        };
+        for argument in &self.definition.arguments {
+            if argument.required == Some(true) {
+                input.get_argument(&argument.name)?;
+            }
+        }
+
+        let messages = (self.renderer)(&input)?;
+        Ok(GetPromptResult {
+            description: self.definition.description.clone(),
+            messages,
+        })
+    }
+}
+
+impl PromptRegistry {
+    /// Create a new empty prompt registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a prompt in the registry.
+    ///
+    /// If a prompt with the same name already exists, it will be replaced
+    /// and a warning will be logged.
+    pub fn register(&mut self, prompt: PromptTemplate) {
+        let name = prompt.definition.name.clone();
+
+        if self.prompts.contains_key(&name) {
+            warn!("Replacing existing prompt: {}", name);
+        }
+
+        info!("Registered prompt: {}", name);
+        self.prompts.insert(name, prompt);
+    }
+
+    /// Get a list of all registered prompt definitions, sorted by name.
+    pub fn list_prompts(&self) -> Vec<Prompt> {
+        self.prompts.values().map(|prompt| prompt.definition.clone()).collect()
+    }
+
+    /// Get a prompt by name.
+    pub fn get_prompt(&self, name: &str) -> Option<&PromptTemplate> {
+        self.prompts.get(name)
+    }
+
+    /// Render a prompt by name with the given arguments.
+    pub fn render_prompt(&self, name: &str, arguments: HashMap<String, String>) -> McpResult<GetPromptResult> {
+        let prompt = self.get_prompt(name)
+            .ok_or_else(|| McpError::invalid_argument("name", format!("Unknown prompt: {}", name)))?;
+        debug!("Rendering prompt {} with arguments {:?}", name, arguments);
+        prompt.render(arguments)
+    }
+
+    /// Get the number of registered prompts.
+    pub fn len(&self) -> usize {
+        self.prompts.len()
+    }
+
+    /// Check if the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.prompts.is_empty()
+    }
+}
@@ -136,6 +136,9 @@ pub struct ToolDefinition {
     pub description: String,
     #[serde(rename = "inputSchema")]
     pub input_schema: ToolInputSchema,
+    /// JSON Schema of the tool's (JSON text) result, when declared.
+    #[serde(rename = "outputSchema", default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<Value>,
 }
 
 /// JSON Schema for tool input parameters.
@@ -167,6 +170,16 @@ pub struct CallToolParams {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arguments: Option<HashMap<String, Value>>,
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RequestMeta>,
+}
+
+/// Request metadata (`_meta`) sent alongside request parameters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestMeta {
+    /// Token the client wants `notifications/progress` tagged with.
+    #[serde(rename = "progressToken", default, skip_serializing_if = "Option::is_none")]
+    pub progress_token: Option<Value>,
 }
 
 /// Tool invocation response.
@@ -261,6 +274,81 @@ pub enum ResourceContents {
     },
 }
 
+/// Prompt template definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prompt {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// Argument accepted by a prompt template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+}
+
+/// List prompts response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPromptsResult {
+    pub prompts: Vec<Prompt>,
+    #[serde(rename = "nextCursor", default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Get prompt request parameters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptParams {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Get prompt response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub messages: Vec<PromptMessage>,
+}
+
+/// Speaker of a prompt message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptRole {
+    User,
+    Assistant,
+}
+
+/// Message in a rendered prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptMessage {
+    pub role: PromptRole,
+    pub content: ToolContent,
+}
+
+/// Logging level change request parameters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetLevelParams {
+    pub level: String,
+}
+
+/// Progress notification parameters (`notifications/progress`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressNotificationParams {
+    #[serde(rename = "progressToken")]
+    pub progress_token: Value,
+    pub progress: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+}
+
 impl JsonRpcRequest {
     /// Create a new JSON-RPC request.
     pub fn new(id: impl Into<Value>, method: impl Into<String>) -> Self {
@@ -283,6 +371,17 @@ impl JsonRpcRequest {
     }
 }
 
+impl JsonRpcNotification {
+    /// Create a new JSON-RPC notification with parameters.
+    pub fn with_params(method: impl Into<String>, params: impl Serialize) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.into(),
+            params: Some(serde_json::to_value(params)?),
+        })
+    }
+}
+
 impl JsonRpcResponse {
     /// Create a successful JSON-RPC response.
     pub fn success(id: impl Into<Value>, result: impl Serialize) -> Result<Self, serde_json::Error> {
@@ -349,3 +448,21 @@ impl ToolContent {
         }
     }
 }
+
+impl PromptMessage {
+    /// Create a text message from the user.
+    pub fn user(text: impl Into<String>) -> Self {
+        Self {
+            role: PromptRole::User,
+            content: ToolContent::text(text),
+        }
+    }
+    
+    /// Create a text message from the assistant.
+    pub fn assistant(text: impl Into<String>) -> Self {
+        Self {
+            role: PromptRole::Assistant,
+            content: ToolContent::text(text),
+        }
+    }
+}
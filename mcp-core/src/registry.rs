@@ -7,15 +7,30 @@
 //! - Result formatting and error handling
 
 use crate::error::{McpError, McpResult};
-use crate::protocol::{CallToolResult, ToolContent, ToolDefinition, ToolInputSchema};
+use crate::protocol::{
+    CallToolResult, JsonRpcNotification, ProgressNotificationParams, ToolContent, ToolDefinition,
+    ToolInputSchema,
+};
+use futures_util::StreamExt;
 use serde::Deserialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use tokio::sync::{mpsc, Semaphore};
 use tracing::{debug, error, info, warn};
 
+/// Argument that turns a `tools/call` into a batch: an array of argument
+/// objects, each run through the tool as a separate call.
+pub const BATCH_ARGUMENT: &str = "_batch";
+
+/// Most items one [`BATCH_ARGUMENT`] array may hold.
+pub const MAX_BATCH_ITEMS: usize = 50;
+
+/// Batch items run at once; the rest wait their turn.
+const BATCH_CONCURRENCY: usize = 4;
+
 /// Type alias for tool handler functions.
 /// 
 /// Tool handlers are async functions that take a `ToolInput` and return a `ToolResult`.
@@ -31,6 +46,18 @@ pub struct ToolInput {
     pub name: String,
     /// The raw arguments passed to the tool as a JSON object.
     pub arguments: HashMap<String, Value>,
+    /// Progress channel, present when the client sent a progress token.
+    pub progress: Option<ProgressReporter>,
+}
+
+/// Emits `notifications/progress` for a single tool call.
+/// 
+/// The server forwards reported progress to the client while the tool is
+/// still running, tagged with the `progressToken` from the call's `_meta`.
+#[derive(Debug, Clone)]
+pub struct ProgressReporter {
+    token: Value,
+    sender: mpsc::UnboundedSender<JsonRpcNotification>,
 }
 
 /// Result returned by tool handlers.
@@ -49,6 +76,8 @@ pub struct Tool {
     pub definition: ToolDefinition,
     /// The handler function for this tool.
     pub handler: ToolHandler,
+    /// Optional cap on concurrent executions of this tool.
+    concurrency_limit: Option<Arc<Semaphore>>,
 }
 
 /// Registry for managing MCP tools.
@@ -59,6 +88,33 @@ pub struct Tool {
 #[derive(Default)]
 pub struct ToolRegistry {
     tools: HashMap<String, Tool>,
+    catalog: ToolCatalog,
+}
+
+/// Shared, read-only view of a registry's tool definitions.
+/// 
+/// The view stays current as tools are registered, so a tool handler can
+/// hold one to describe the server it runs in (for hosts that cannot call
+/// `tools/list` themselves).
+#[derive(Clone, Default)]
+pub struct ToolCatalog {
+    definitions: Arc<RwLock<BTreeMap<String, ToolDefinition>>>,
+}
+
+impl ToolCatalog {
+    /// All tool definitions, sorted by name.
+    pub fn list(&self) -> Vec<ToolDefinition> {
+        self.definitions
+            .read()
+            .map(|definitions| definitions.values().cloned().collect())
+            .unwrap_or_default()
+    }
+    
+    fn insert(&self, definition: ToolDefinition) {
+        if let Ok(mut definitions) = self.definitions.write() {
+            definitions.insert(definition.name.clone(), definition);
+        }
+    }
 }
 
 impl ToolInput {
@@ -67,6 +123,14 @@ impl ToolInput {
         Self {
             name: name.into(),
             arguments,
+            progress: None,
+        }
+    }
+    
+    /// Report progress if the client asked for it; a no-op otherwise.
+    pub fn report_progress(&self, progress: f64, total: Option<f64>) {
+        if let Some(reporter) = &self.progress {
+            reporter.report(progress, total);
         }
     }
     
@@ -81,10 +145,10 @@ impl ToolInput {
     {
         let value = self.arguments
             .get(key)
-            .ok_or_else(|| McpError::invalid_params(format!("Missing required argument: {}", key)))?;
+            .ok_or_else(|| McpError::invalid_argument(key, format!("Missing required argument: {}", key)))?;
         
         serde_json::from_value(value.clone())
-            .map_err(|e| McpError::invalid_params(format!("Invalid argument '{}': {}", key, e)))
+            .map_err(|e| McpError::invalid_argument(key, format!("Invalid argument '{}': {}", key, e)))
     }
     
     /// Get an optional typed argument from the input parameters.
@@ -98,13 +162,55 @@ impl ToolInput {
         match self.arguments.get(key) {
             Some(value) => {
                 let result = serde_json::from_value(value.clone())
-                    .map_err(|e| McpError::invalid_params(format!("Invalid argument '{}': {}", key, e)))?;
+                    .map_err(|e| McpError::invalid_argument(key, format!("Invalid argument '{}': {}", key, e)))?;
                 Ok(Some(result))
             }
             None => Ok(None),
         }
     }
     
+    /// Get an id-like argument as a string.
+    /// 
+    /// Clients often send MISP ids and timestamps as JSON numbers. Integers are
+    /// formatted exactly (`10000000000` becomes `"10000000000"`), never via a
+    /// float or scientific notation; a float with no fractional part is only
+    /// accepted while it is still exactly representable.
+    /// 
+    /// ```
+    /// use std::collections::HashMap;
+    /// use mcp_core::ToolInput;
+    /// 
+    /// let mut arguments = HashMap::new();
+    /// arguments.insert("event_id".to_string(), serde_json::json!(10000000000u64));
+    /// let input = ToolInput::new("get_event_by_id", arguments);
+    /// assert_eq!(input.get_id("event_id").unwrap(), "10000000000");
+    /// ```
+    pub fn get_id(&self, key: &str) -> McpResult<String> {
+        let value = self.arguments
+            .get(key)
+            .ok_or_else(|| McpError::invalid_argument(key, format!("Missing required argument: {}", key)))?;
+        id_from_value(key, value)
+    }
+    
+    /// Get an optional id-like argument as a string, see [`ToolInput::get_id`].
+    pub fn get_optional_id(&self, key: &str) -> McpResult<Option<String>> {
+        match self.arguments.get(key) {
+            Some(Value::Null) | None => Ok(None),
+            Some(value) => id_from_value(key, value).map(Some),
+        }
+    }
+    
+    /// Get an array of id-like arguments as strings, see [`ToolInput::get_id`].
+    pub fn get_ids(&self, key: &str) -> McpResult<Vec<String>> {
+        let value = self.arguments
+            .get(key)
+            .ok_or_else(|| McpError::invalid_argument(key, format!("Missing required argument: {}", key)))?;
+        let items = value.as_array().ok_or_else(|| {
+            McpError::invalid_argument(key, format!("Invalid argument '{}': expected an array of ids", key))
+        })?;
+        items.iter().map(|item| id_from_value(key, item)).collect()
+    }
+
     /// Get all arguments as a typed struct.
     /// 
     /// This method attempts to deserialize all arguments into a single struct.
@@ -116,6 +222,103 @@ impl ToolInput {
         serde_json::from_value(serde_json::to_value(&self.arguments)?)
             .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {}", e)))
     }
+
+    /// Get a request struct sent either flat or wrapped under `key`.
+    /// 
+    /// Clients send request structs both ways: as the arguments themselves
+    /// (`{"limit": 5}`), or under one key, as an object or as stringified
+    /// JSON (`{"request_json": "{\"limit\": 5}"}`). When `key` is present
+    /// the request is read from it and the other arguments are ignored;
+    /// otherwise all arguments are deserialized as with
+    /// [`ToolInput::deserialize_arguments`].
+    /// 
+    /// ```
+    /// use std::collections::HashMap;
+    /// use mcp_core::ToolInput;
+    /// 
+    /// #[derive(serde::Deserialize)]
+    /// struct Search { limit: u32 }
+    /// 
+    /// let flat = ToolInput::new("search", HashMap::from([("limit".to_string(), serde_json::json!(5))]));
+    /// let wrapped = ToolInput::new(
+    ///     "search",
+    ///     HashMap::from([("request_json".to_string(), serde_json::json!("{\"limit\": 5}"))]),
+    /// );
+    /// assert_eq!(flat.get_request_either::<Search>("request_json").unwrap().limit, 5);
+    /// assert_eq!(wrapped.get_request_either::<Search>("request_json").unwrap().limit, 5);
+    /// ```
+    pub fn get_request_either<T>(&self, key: &str) -> McpResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let request = match self.arguments.get(key) {
+            None => return self.deserialize_arguments(),
+            Some(Value::String(json)) => serde_json::from_str(json)
+                .map_err(|e| McpError::invalid_argument(key, format!("Invalid argument '{}': not valid JSON: {}", key, e)))?,
+            Some(value @ Value::Object(_)) => value.clone(),
+            Some(_) => {
+                return Err(McpError::invalid_argument(
+                    key,
+                    format!("Invalid argument '{}': expected an object or stringified JSON", key),
+                ))
+            }
+        };
+        serde_json::from_value(request)
+            .map_err(|e| McpError::invalid_argument(key, format!("Invalid argument '{}': {}", key, e)))
+    }
+}
+
+/// Largest integer an f64 holds exactly (2^53).
+const MAX_EXACT_FLOAT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+fn id_from_value(key: &str, value: &Value) -> McpResult<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => {
+            if let Some(i) = n.as_u64() {
+                Ok(i.to_string())
+            } else if let Some(i) = n.as_i64() {
+                Ok(i.to_string())
+            } else {
+                match n.as_f64() {
+                    Some(f) if f.fract() == 0.0 && f.abs() <= MAX_EXACT_FLOAT_INTEGER => Ok(format!("{:.0}", f)),
+                    _ => Err(McpError::invalid_argument(
+                        key,
+                        format!("Invalid argument '{}': {} is not an exact integer id; send it as a string", key, n),
+                    )),
+                }
+            }
+        }
+        other => Err(McpError::invalid_argument(
+            key,
+            format!("Invalid argument '{}': expected a string or integer id, got {}", key, other),
+        )),
+    }
+}
+
+impl ProgressReporter {
+    /// Create a reporter that tags notifications with `token`.
+    pub fn new(token: Value, sender: mpsc::UnboundedSender<JsonRpcNotification>) -> Self {
+        Self { token, sender }
+    }
+    
+    /// Send a progress update. `total` is omitted when unknown.
+    pub fn report(&self, progress: f64, total: Option<f64>) {
+        let params = ProgressNotificationParams {
+            progress_token: self.token.clone(),
+            progress,
+            total,
+        };
+        match JsonRpcNotification::with_params("notifications/progress", params) {
+            // The call may already have finished; a late update is harmless to drop
+            Ok(notification) => {
+                if self.sender.send(notification).is_err() {
+                    debug!("Progress receiver gone, dropping update");
+                }
+            }
+            Err(e) => warn!("Failed to build progress notification: {}", e),
+        }
+    }
 }
 
 impl ToolResult {
@@ -142,6 +345,19 @@ impl ToolResult {
         Self::new(vec![ToolContent::resource(uri)])
     }
     
+    /// Append further content blocks, e.g. a readable summary after a JSON block.
+    ///
+    /// ```
+    /// use mcp_core::{ToolContent, ToolResult};
+    ///
+    /// let result = ToolResult::text("{\"matched\": 1}").with_blocks(vec![ToolContent::text("1 value matched")]);
+    /// assert_eq!(result.into_call_result().content.len(), 2);
+    /// ```
+    pub fn with_blocks(mut self, blocks: Vec<ToolContent>) -> Self {
+        self.content.extend(blocks);
+        self
+    }
+    
     /// Create an error result with a text message.
     pub fn error(message: impl Into<String>) -> Self {
         Self {
@@ -184,13 +400,14 @@ impl Tool {
                 required: vec![],
                 additional_properties: Some(true),
             },
+            output_schema: None,
         };
         
         let handler = Arc::new(move |input: ToolInput| {
             Box::pin(handler(input)) as Pin<Box<dyn Future<Output = McpResult<ToolResult>> + Send>>
         });
         
-        Self { definition, handler }
+        Self { definition, handler, concurrency_limit: None }
     }
     
     /// Create a new tool with a detailed schema.
@@ -211,26 +428,58 @@ impl Tool {
             name: name.into(),
             description: description.into(),
             input_schema,
+            output_schema: None,
         };
         
         let handler = Arc::new(move |input: ToolInput| {
             Box::pin(handler(input)) as Pin<Box<dyn Future<Output = McpResult<ToolResult>> + Send>>
         });
         
-        Self { definition, handler }
+        Self { definition, handler, concurrency_limit: None }
+    }
+    
+    /// Declare the JSON Schema of this tool's result (advertised as `outputSchema`).
+    /// 
+    /// With the `output-validation` feature, JSON text results are checked
+    /// against it and mismatches are logged as warnings.
+    pub fn with_output_schema(mut self, schema: Value) -> Self {
+        self.definition.output_schema = Some(schema);
+        self
+    }
+    
+    /// Limit how many calls of this tool may run at the same time.
+    /// 
+    /// Further calls wait for a running one to finish. Useful for expensive
+    /// backend queries; other tools are unaffected. The server handles one
+    /// request at a time, so calls only overlap, and the limit only bites,
+    /// for the items of a `_batch` call or in-process callers sharing the
+    /// registry.
+    pub fn with_concurrency_limit(mut self, max_concurrency: usize) -> Self {
+        self.concurrency_limit = Some(Arc::new(Semaphore::new(max_concurrency.max(1))));
+        self
     }
     
     /// Execute the tool with the given input.
     pub async fn execute(&self, input: ToolInput) -> McpResult<ToolResult> {
         debug!("Executing tool '{}' with arguments: {:?}", input.name, input.arguments);
         
+        // Held until the handler finishes; the semaphore is never closed
+        let _permit = match &self.concurrency_limit {
+            Some(semaphore) => Some(semaphore.acquire().await.map_err(|e| McpError::internal_error(e.to_string()))?),
+            None => None,
+        };
+        
         let start_time = std::time::Instant::now();
         let result = (self.handler)(input.clone()).await;
         let duration = start_time.elapsed();
         
         match &result {
-            Ok(_) => {
+            Ok(_output) => {
                 info!("Tool '{}' completed successfully in {:?}", input.name, duration);
+                #[cfg(feature = "output-validation")]
+                if let Some(schema) = &self.definition.output_schema {
+                    crate::validation::check_output(&self.definition.name, schema, _output);
+                }
             }
             Err(e) => {
                 error!("Tool '{}' failed after {:?}: {}", input.name, duration, e);
@@ -259,9 +508,15 @@ impl ToolRegistry {
         }
         
         info!("Registered tool: {} - {}", name, tool.definition.description);
+        self.catalog.insert(tool.definition.clone());
         self.tools.insert(name, tool);
     }
     
+    /// Get a live, shareable view of the registered tool definitions.
+    pub fn catalog(&self) -> ToolCatalog {
+        self.catalog.clone()
+    }
+    
     /// Get a list of all registered tool definitions.
     pub fn list_tools(&self) -> Vec<ToolDefinition> {
         self.tools.values().map(|tool| tool.definition.clone()).collect()
@@ -278,18 +533,113 @@ impl ToolRegistry {
     /// the tool's handler. It returns appropriate errors if the tool is
     /// not found or execution fails.
     pub async fn execute_tool(&self, name: &str, arguments: HashMap<String, Value>) -> McpResult<ToolResult> {
+        self.execute_tool_with_progress(name, arguments, None).await
+    }
+    
+    /// Execute a tool by name, handing it a progress reporter if one is given.
+    /// 
+    /// Arguments with a [`BATCH_ARGUMENT`] array run the tool once per element
+    /// instead; see [`ToolRegistry::execute_batch`].
+    pub async fn execute_tool_with_progress(
+        &self,
+        name: &str,
+        mut arguments: HashMap<String, Value>,
+        progress: Option<ProgressReporter>,
+    ) -> McpResult<ToolResult> {
         let tool = self.get_tool(name)
             .ok_or_else(|| McpError::tool_not_found(name))?;
         
-        let input = ToolInput::new(name, arguments);
+        let result = match arguments.remove(BATCH_ARGUMENT) {
+            Some(batch) => Self::execute_batch(tool, name, arguments, batch).await,
+            None => {
+                let mut input = ToolInput::new(name, arguments);
+                input.progress = progress;
+                tool.execute(input).await
+            }
+        };
+        result.map_err(|e| {
+            error!("Tool execution failed for '{}': {}", name, e);
+            Self::tool_error(name, e)
+        })
+    }
+    
+    /// Run a tool for each argument object of a batch, at most
+    /// [`BATCH_CONCURRENCY`] items at a time.
+    /// 
+    /// Batches over [`MAX_BATCH_ITEMS`] are rejected. Arguments given next
+    /// to the batch apply to every item, which can override them. The result
+    /// holds the content of every item in batch order, each item's blocks
+    /// after a "Batch item N" header; an item that fails contributes an
+    /// error text instead, and the result is only an error when all items
+    /// failed. Items get no progress reporter.
+    async fn execute_batch(
+        tool: &Tool,
+        name: &str,
+        shared: HashMap<String, Value>,
+        batch: Value,
+    ) -> McpResult<ToolResult> {
+        let Value::Array(items) = batch else {
+            return Err(McpError::invalid_argument(BATCH_ARGUMENT, "must be an array of argument objects"));
+        };
+        if items.len() > MAX_BATCH_ITEMS {
+            return Err(McpError::invalid_argument(
+                BATCH_ARGUMENT,
+                format!("at most {} items per batch, got {}", MAX_BATCH_ITEMS, items.len()),
+            ));
+        }
+        let mut inputs = Vec::with_capacity(items.len());
+        for (index, item) in items.into_iter().enumerate() {
+            let Value::Object(item) = item else {
+                return Err(McpError::invalid_argument(BATCH_ARGUMENT, format!("item {} is not an object", index)));
+            };
+            let mut arguments = shared.clone();
+            arguments.extend(item);
+            inputs.push(ToolInput::new(name, arguments));
+        }
         
-        match tool.execute(input).await {
-            Ok(result) => Ok(result),
-            Err(e) => {
-                error!("Tool execution failed for '{}': {}", name, e);
-                Err(McpError::tool_execution_error(name, e.to_string()))
+        info!("Running tool '{}' for a batch of {} inputs", name, inputs.len());
+        let results: Vec<McpResult<ToolResult>> = futures_util::stream::iter(inputs.into_iter().map(|input| tool.execute(input)))
+            .buffered(BATCH_CONCURRENCY)
+            .collect()
+            .await;
+        let items = results.len();
+        let mut failures = 0;
+        let mut content = Vec::with_capacity(items);
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(result) => {
+                    if result.is_error {
+                        failures += 1;
+                    }
+                    content.push(ToolContent::text(format!("Batch item {}:", index)));
+                    content.extend(result.content);
+                }
+                Err(e) => {
+                    failures += 1;
+                    content.push(ToolContent::text(format!("Batch item {} failed: {}", index, Self::tool_error(name, e))));
+                }
             }
         }
+        Ok(ToolResult {
+            content,
+            is_error: items > 0 && failures == items,
+        })
+    }
+    
+    /// Attribute a handler error to the tool, keeping whatever structured
+    /// context the handler attached.
+    fn tool_error(name: &str, e: McpError) -> McpError {
+        match e {
+            McpError::ToolExecutionError { message, argument, .. } => {
+                McpError::ToolExecutionError { tool_name: name.to_string(), message, argument }
+            }
+            McpError::InvalidParams { ref argument, .. } => McpError::ToolExecutionError {
+                tool_name: name.to_string(),
+                message: e.to_string(),
+                argument: argument.clone(),
+            },
+            other => McpError::tool_execution_error(name, other.to_string()),
+        }
     }
     
     /// Get the number of registered tools.
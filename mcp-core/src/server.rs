@@ -7,15 +7,24 @@
 
 use crate::error::{McpError, McpResult};
 use crate::protocol::{
-    CallToolParams, Implementation, InitializeParams, InitializeResult,
-    JsonRpcError, JsonRpcRequest, JsonRpcResponse, ListToolsParams, ListToolsResult,
-    ServerCapabilities, ToolsCapability,
+    CallToolParams, CallToolResult, GetPromptParams, Implementation, InitializeParams, InitializeResult,
+    JsonRpcError, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, ListPromptsResult, ListResourcesParams,
+    ListResourcesResult, ListToolsParams, ListToolsResult, LoggingCapability, PromptsCapability,
+    ReadResourceParams, ReadResourceResult, Resource, ResourceContents, ResourcesCapability,
+    ServerCapabilities, SetLevelParams, ToolContent, ToolsCapability,
 };
-use crate::registry::{Tool, ToolRegistry};
-use crate::transport::{StdioTransport, Transport};
+use crate::prompts::{PromptRegistry, PromptTemplate};
+use crate::registry::{ProgressReporter, Tool, ToolCatalog, ToolRegistry};
+use crate::transport::{StdioTransport, Transport, WebSocketTransport};
 use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+/// Number of oversized tool results kept readable before the oldest is dropped.
+const MAX_STORED_RESULTS: usize = 32;
+
 /// MCP server state tracking.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ServerState {
@@ -53,7 +62,8 @@ pub enum ServerState {
 ///         }
 ///     ));
 ///     
-///     server.run_stdio().await
+///     server.run_stdio().await?;
+///     Ok(())
 /// }
 /// ```
 pub struct Server {
@@ -63,8 +73,92 @@ pub struct Server {
     state: ServerState,
     /// Tool registry for managing available tools.
     tool_registry: ToolRegistry,
+    /// Prompt templates served when the prompts capability is enabled.
+    prompt_registry: PromptRegistry,
     /// Server capabilities advertised to clients.
     capabilities: ServerCapabilities,
+    /// Static resources served when the resources capability is enabled, by URI.
+    resources: BTreeMap<String, (Resource, ResourceContents)>,
+    /// Last level requested via `logging/setLevel`.
+    log_level: Option<String>,
+    /// Where oversized tool results go, when a result budget is configured.
+    result_store: Option<ResultStore>,
+}
+
+/// Tool results too large to return inline, served back as resources.
+struct ResultStore {
+    /// Largest text result, in bytes, returned inline.
+    max_bytes: usize,
+    /// URI prefix of stored results; a sequence number is appended.
+    uri_prefix: String,
+    next_id: u64,
+    /// Stored results, oldest first.
+    results: VecDeque<(Resource, ResourceContents)>,
+}
+
+impl ResultStore {
+    /// Replace an oversized result with a reference to a stored copy.
+    /// 
+    /// Errors and results with non-text content are returned unchanged.
+    fn offload(&mut self, tool_name: &str, result: CallToolResult) -> CallToolResult {
+        if result.is_error == Some(true) {
+            return result;
+        }
+        let mut texts = Vec::with_capacity(result.content.len());
+        for content in &result.content {
+            match content {
+                ToolContent::Text { text } => texts.push(text.as_str()),
+                _ => return result,
+            }
+        }
+        let size: usize = texts.iter().map(|text| text.len()).sum();
+        if size <= self.max_bytes {
+            return result;
+        }
+        
+        let text = texts.join("\n");
+        let mime_type = if serde_json::from_str::<serde::de::IgnoredAny>(&text).is_ok() {
+            "application/json"
+        } else {
+            "text/plain"
+        };
+        self.next_id += 1;
+        let uri = format!("{}{}", self.uri_prefix, self.next_id);
+        info!("Result of {} is {} bytes, serving it as {}", tool_name, size, uri);
+        
+        let resource = Resource {
+            uri: uri.clone(),
+            name: format!("{} result {}", tool_name, self.next_id),
+            description: Some(format!("Full output of a {} call ({} bytes)", tool_name, size)),
+            mime_type: Some(mime_type.to_string()),
+        };
+        let contents = ResourceContents::Text {
+            uri: uri.clone(),
+            mime_type: mime_type.to_string(),
+            text,
+        };
+        self.results.push_back((resource, contents));
+        if self.results.len() > MAX_STORED_RESULTS {
+            if let Some((dropped, _)) = self.results.pop_front() {
+                debug!("Dropped stored result {}", dropped.uri);
+            }
+        }
+        
+        CallToolResult {
+            content: vec![
+                ToolContent::text(format!(
+                    "The result is {} bytes, over the {}-byte inline limit. Read {} with resources/read for the full output.",
+                    size, self.max_bytes, uri
+                )),
+                ToolContent::resource(uri),
+            ],
+            is_error: None,
+        }
+    }
+    
+    fn get(&self, uri: &str) -> Option<&(Resource, ResourceContents)> {
+        self.results.iter().find(|(resource, _)| resource.uri == uri)
+    }
 }
 
 impl Server {
@@ -84,13 +178,78 @@ impl Server {
             server_info,
             state: ServerState::Created,
             tool_registry: ToolRegistry::new(),
+            prompt_registry: PromptRegistry::new(),
             capabilities: ServerCapabilities {
                 tools: Some(ToolsCapability::default()),
                 ..Default::default()
             },
+            resources: BTreeMap::new(),
+            log_level: None,
+            result_store: None,
         }
     }
     
+    /// Advertise the resources capability and serve `resources/list` and
+    /// `resources/read` from resources added with [`Server::add_resource`].
+    pub fn with_resources(mut self) -> Self {
+        self.capabilities.resources = Some(ResourcesCapability::default());
+        self
+    }
+    
+    /// Return tool results larger than `max_bytes` as a resource reference.
+    /// 
+    /// The full text is kept in memory under `{uri_prefix}{n}` and read back
+    /// with `resources/read`; only the most recent results are kept. Enables
+    /// the resources capability.
+    pub fn with_result_budget(mut self, max_bytes: usize, uri_prefix: impl Into<String>) -> Self {
+        self.result_store = Some(ResultStore {
+            max_bytes,
+            uri_prefix: uri_prefix.into(),
+            next_id: 0,
+            results: VecDeque::new(),
+        });
+        self.with_resources()
+    }
+    
+    /// Advertise the prompts capability and serve `prompts/list` and
+    /// `prompts/get` from prompts added with [`Server::add_prompt`].
+    pub fn with_prompts(mut self) -> Self {
+        self.capabilities.prompts = Some(PromptsCapability::default());
+        self
+    }
+    
+    /// Advertise the logging capability and accept `logging/setLevel`.
+    pub fn with_logging(mut self) -> Self {
+        self.capabilities.logging = Some(LoggingCapability::default());
+        self
+    }
+    
+    /// Get the capabilities advertised during initialization.
+    pub fn capabilities(&self) -> &ServerCapabilities {
+        &self.capabilities
+    }
+    
+    /// Get the log level last requested by the client via `logging/setLevel`.
+    pub fn log_level(&self) -> Option<&str> {
+        self.log_level.as_deref()
+    }
+    
+    /// Add a resource to the server, replacing any resource with the same URI.
+    /// 
+    /// Resources are only reachable by clients when the server was built
+    /// with [`Server::with_resources`].
+    pub fn add_resource(&mut self, resource: Resource, contents: ResourceContents) {
+        self.resources.insert(resource.uri.clone(), (resource, contents));
+    }
+    
+    /// Add a prompt template to the server, replacing any prompt with the same name.
+    /// 
+    /// Prompts are only reachable by clients when the server was built
+    /// with [`Server::with_prompts`].
+    pub fn add_prompt(&mut self, prompt: PromptTemplate) {
+        self.prompt_registry.register(prompt);
+    }
+    
     /// Add a tool to the server.
     /// 
     /// Tools can be added before or after initialization. If added after
@@ -105,6 +264,14 @@ impl Server {
         self.state.clone()
     }
     
+    /// Get a live view of the registered tool definitions.
+    /// 
+    /// Tools registered later are included, so the catalog can be handed
+    /// to a tool that describes the server itself.
+    pub fn tool_catalog(&self) -> ToolCatalog {
+        self.tool_registry.catalog()
+    }
+    
     /// Get the number of registered tools.
     pub fn tool_count(&self) -> usize {
         self.tool_registry.len()
@@ -119,6 +286,36 @@ impl Server {
         self.run_with_transport(&mut transport).await
     }
     
+    /// Run the server as a WebSocket endpoint listening on `addr`.
+    /// 
+    /// Only one connection is accepted and served at a time: a second client
+    /// waits until the current one disconnects. Each connection is a separate
+    /// MCP session that must perform its own initialization handshake.
+    /// Returns only if the listener fails.
+    pub async fn run_websocket(&mut self, addr: impl ToSocketAddrs) -> McpResult<()> {
+        let listener = TcpListener::bind(addr).await?;
+        info!("Listening for WebSocket connections on {}", listener.local_addr()?);
+        
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            info!("Accepted WebSocket connection from {}", peer);
+            
+            let mut transport = match WebSocketTransport::accept(stream).await {
+                Ok(transport) => transport,
+                Err(e) => {
+                    warn!("Rejected connection from {}: {}", peer, e);
+                    continue;
+                }
+            };
+            
+            // Every connection starts a fresh session
+            self.state = ServerState::Created;
+            if let Err(e) = self.run_with_transport(&mut transport).await {
+                warn!("WebSocket session with {} ended with error: {}", peer, e);
+            }
+        }
+    }
+    
     /// Run the server with a custom transport.
     /// 
     /// This allows for using alternative transport mechanisms such as
@@ -164,9 +361,32 @@ impl Server {
     /// or `Ok(false)` if the server should shut down.
     async fn handle_next_request(&mut self, transport: &mut dyn Transport) -> McpResult<bool> {
         let request = transport.read_message().await?;
+        
+        // Messages without an id are notifications and must never be answered
+        if request.id.is_none() {
+            self.handle_notification(&request);
+            return Ok(true);
+        }
         debug!("Processing request: method={}, id={:?}", request.method, request.id);
         
-        let response = match self.process_request(request.clone()).await {
+        // Tools may report progress while they run; forward it as it arrives
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+        let result = {
+            let processing = self.process_request(request.clone(), progress_tx);
+            tokio::pin!(processing);
+            loop {
+                tokio::select! {
+                    result = &mut processing => break result,
+                    Some(notification) = progress_rx.recv() => transport.write_notification(notification).await?,
+                }
+            }
+        };
+        // Anything reported right before completion still precedes the response
+        while let Ok(notification) = progress_rx.try_recv() {
+            transport.write_notification(notification).await?;
+        }
+        
+        let response = match result {
             Ok(response) => response,
             Err(e) => {
                 warn!("Request processing failed: {}", e);
@@ -178,12 +398,32 @@ impl Server {
         Ok(true)
     }
     
+    /// Handle a JSON-RPC notification (a message without an id).
+    fn handle_notification(&self, notification: &JsonRpcRequest) {
+        match notification.method.as_str() {
+            "notifications/initialized" => debug!("Client finished initialization"),
+            "notifications/cancelled" => debug!("Client cancelled a request: {:?}", notification.params),
+            method if method.starts_with("notifications/") => debug!("Ignoring notification: {}", method),
+            // A request sent without an id cannot be answered, so it is not run
+            method => warn!("Ignoring '{}' sent without an id", method),
+        }
+    }
+    
     /// Process a JSON-RPC request and generate a response.
-    async fn process_request(&mut self, request: JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+    async fn process_request(
+        &mut self,
+        request: JsonRpcRequest,
+        progress_tx: mpsc::UnboundedSender<JsonRpcNotification>,
+    ) -> McpResult<JsonRpcResponse> {
         match request.method.as_str() {
             "initialize" => self.handle_initialize(request).await,
             "tools/list" => self.handle_list_tools(request).await,
-            "tools/call" => self.handle_call_tool(request).await,
+            "tools/call" => self.handle_call_tool(request, progress_tx).await,
+            "resources/list" if self.capabilities.resources.is_some() => self.handle_list_resources(request).await,
+            "resources/read" if self.capabilities.resources.is_some() => self.handle_read_resource(request).await,
+            "prompts/list" if self.capabilities.prompts.is_some() => self.handle_list_prompts(request).await,
+            "prompts/get" if self.capabilities.prompts.is_some() => self.handle_get_prompt(request).await,
+            "logging/setLevel" if self.capabilities.logging.is_some() => self.handle_set_level(request).await,
             _ => {
                 Err(McpError::method_not_found(&request.method))
             }
@@ -242,7 +482,14 @@ impl Server {
     }
     
     /// Handle the tools/call request.
-    async fn handle_call_tool(&self, request: JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+    /// 
+    /// When the call carries `_meta.progressToken`, the tool gets a
+    /// `ProgressReporter` feeding `progress_tx`.
+    async fn handle_call_tool(
+        &mut self,
+        request: JsonRpcRequest,
+        progress_tx: mpsc::UnboundedSender<JsonRpcNotification>,
+    ) -> McpResult<JsonRpcResponse> {
         if self.state != ServerState::Initialized {
             return Err(McpError::invalid_request("Server not initialized"));
         }
@@ -255,16 +502,115 @@ impl Server {
         info!("Calling tool: {}", params.name);
         debug!("Tool arguments: {:?}", params.arguments);
         
+        let progress = params.meta
+            .and_then(|meta| meta.progress_token)
+            .map(|token| ProgressReporter::new(token, progress_tx));
+        
         let arguments = params.arguments.unwrap_or_default();
-        let tool_result = self.tool_registry.execute_tool(&params.name, arguments).await?;
-        let call_result = tool_result.into_call_result();
+        let tool_result = self.tool_registry
+            .execute_tool_with_progress(&params.name, arguments, progress)
+            .await?;
+        let mut call_result = tool_result.into_call_result();
+        if let Some(store) = self.result_store.as_mut() {
+            call_result = store.offload(&params.name, call_result);
+        }
         
         JsonRpcResponse::success(request.id, call_result).map_err(McpError::from)
     }
     
+    /// Handle the resources/list request.
+    async fn handle_list_resources(&self, request: JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+        if self.state != ServerState::Initialized {
+            return Err(McpError::invalid_request("Server not initialized"));
+        }
+        
+        let _params: ListResourcesParams = match request.params {
+            Some(params) => serde_json::from_value(params)?,
+            None => ListResourcesParams::default(),
+        };
+        
+        let stored = self.result_store.iter().flat_map(|store| store.results.iter());
+        let resources = self.resources.values().chain(stored).map(|(resource, _)| resource.clone()).collect();
+        let result = ListResourcesResult { resources, next_cursor: None };
+        JsonRpcResponse::success(request.id, result).map_err(McpError::from)
+    }
+    
+    /// Handle the resources/read request.
+    async fn handle_read_resource(&self, request: JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+        if self.state != ServerState::Initialized {
+            return Err(McpError::invalid_request("Server not initialized"));
+        }
+        
+        let params: ReadResourceParams = match request.params {
+            Some(params) => serde_json::from_value(params)?,
+            None => return Err(McpError::invalid_params("Missing resource read parameters")),
+        };
+        
+        let found = self.resources.get(&params.uri)
+            .or_else(|| self.result_store.as_ref().and_then(|store| store.get(&params.uri)));
+        let (_, contents) = found.ok_or_else(|| {
+            McpError::invalid_argument("uri", format!("Unknown resource: {}", params.uri))
+        })?;
+        
+        let result = ReadResourceResult { contents: vec![contents.clone()] };
+        JsonRpcResponse::success(request.id, result).map_err(McpError::from)
+    }
+    
+    /// Handle the prompts/list request.
+    async fn handle_list_prompts(&self, request: JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+        if self.state != ServerState::Initialized {
+            return Err(McpError::invalid_request("Server not initialized"));
+        }
+        
+        let prompts = self.prompt_registry.list_prompts();
+        debug!("Listing {} prompts", prompts.len());
+        let result = ListPromptsResult { prompts, next_cursor: None };
+        JsonRpcResponse::success(request.id, result).map_err(McpError::from)
+    }
+    
+    /// Handle the prompts/get request.
+    async fn handle_get_prompt(&self, request: JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+        if self.state != ServerState::Initialized {
+            return Err(McpError::invalid_request("Server not initialized"));
+        }
+        
+        let params: GetPromptParams = match request.params {
+            Some(params) => serde_json::from_value(params)?,
+            None => return Err(McpError::invalid_params("Missing prompt parameters")),
+        };
+        
+        info!("Rendering prompt: {}", params.name);
+        let result = self.prompt_registry.render_prompt(&params.name, params.arguments)?;
+        JsonRpcResponse::success(request.id, result).map_err(McpError::from)
+    }
+    
+    /// Handle the logging/setLevel request.
+    /// 
+    /// The requested level is recorded; log filtering itself stays with the
+    /// host application's tracing subscriber.
+    async fn handle_set_level(&mut self, request: JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+        if self.state != ServerState::Initialized {
+            return Err(McpError::invalid_request("Server not initialized"));
+        }
+        
+        let params: SetLevelParams = match request.params {
+            Some(params) => serde_json::from_value(params)?,
+            None => return Err(McpError::invalid_params("Missing logging level")),
+        };
+        
+        info!("Client requested log level: {}", params.level);
+        self.log_level = Some(params.level);
+        JsonRpcResponse::success(request.id, serde_json::json!({})).map_err(McpError::from)
+    }
+    
     /// Create an error response for a failed request.
     fn create_error_response(&self, request_id: Option<Value>, error: McpError) -> JsonRpcResponse {
-        let json_rpc_error = JsonRpcError::new(error.to_json_rpc_code(), error.to_string());
+        let code = error.to_json_rpc_code();
+        let json_rpc_error = match error.to_json_rpc_data() {
+            Some(data) => JsonRpcError::with_data(code, error.to_string(), data)
+                .unwrap_or_else(|_| JsonRpcError::new(code, error.to_string())),
+            None => JsonRpcError::new(code, error.to_string()),
+        };
         JsonRpcResponse::error(request_id, json_rpc_error)
     }
 }
@@ -1,14 +1,17 @@
 //! Transport layer implementations for MCP communication.
 //!
 //! This module provides different transport mechanisms for MCP servers,
-//! including stdio (standard input/output) and named pipes. All transports
-//! implement the `Transport` trait for consistent message handling.
+//! including stdio (standard input/output), named pipes and WebSockets. All
+//! transports implement the `Transport` trait for consistent message handling.
 
 use crate::error::{McpError, McpResult};
-use crate::protocol::{JsonRpcRequest, JsonRpcResponse};
-use serde_json;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader as TokioBufReader};
+use crate::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader as TokioBufReader};
+use tokio::net::TcpStream;
 use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
 use tracing::{debug, error, info, trace, warn};
 
 /// Trait for MCP transport implementations.
@@ -24,14 +27,28 @@ pub trait Transport: Send + Sync {
     /// Write a JSON-RPC response to the transport.
     async fn write_response(&mut self, response: JsonRpcResponse) -> McpResult<()>;
     
+    /// Write a JSON-RPC notification (a message without an id) to the transport.
+    async fn write_notification(&mut self, notification: JsonRpcNotification) -> McpResult<()>;
+    
     /// Close the transport and clean up resources.
     async fn close(&mut self) -> McpResult<()>;
 }
 
+/// Writer for log output when serving over stdio.
+///
+/// Always stderr: stdout belongs to [`StdioTransport`], and anything else
+/// written there would corrupt the JSON-RPC stream. Pass it to the log
+/// subscriber, e.g. `tracing_subscriber::fmt().with_writer(log_writer)`.
+pub fn log_writer() -> std::io::Stderr {
+    std::io::stderr()
+}
+
 /// Stdio transport implementation using standard input and output.
 /// 
 /// This transport reads JSON-RPC messages from stdin and writes responses
 /// to stdout. Each message is expected to be on a single line (JSON Lines format).
+/// It is the only writer of stdout; `print!`/`println!` are denied by the
+/// workspace lints and logs go to [`log_writer`].
 pub struct StdioTransport {
     stdin_reader: TokioBufReader<tokio::io::Stdin>,
     stdout: tokio::io::Stdout,
@@ -54,31 +71,61 @@ impl Default for StdioTransport {
     }
 }
 
+/// How many consecutive `Interrupted` errors a read tolerates before giving up.
+pub const MAX_INTERRUPTED_RETRIES: u32 = 5;
+
+/// Read one line, retrying reads interrupted by a signal (EINTR).
+///
+/// Interrupted reads are retried with a short linear backoff; data already
+/// read stays in `line`. EOF is reported as `Ok(0)`, anything else is a real
+/// failure. Used by the stdio transport, and available to custom line-based
+/// transports.
+pub async fn read_line_retrying<R>(reader: &mut R, line: &mut String) -> std::io::Result<usize>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    let mut attempt = 0;
+    loop {
+        match reader.read_line(line).await {
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted && attempt < MAX_INTERRUPTED_RETRIES => {
+                attempt += 1;
+                warn!("Read interrupted ({}), retrying ({}/{})", e, attempt, MAX_INTERRUPTED_RETRIES);
+                tokio::time::sleep(std::time::Duration::from_millis(10 * u64::from(attempt))).await;
+            }
+            // Partial data from before an interruption counts towards the line
+            Ok(0) if !line.is_empty() => return Ok(line.len()),
+            result => return result,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl Transport for StdioTransport {
     async fn read_message(&mut self) -> McpResult<JsonRpcRequest> {
         let mut line = String::new();
-        
-        match self.stdin_reader.read_line(&mut line).await {
-            Ok(0) => {
-                debug!("EOF reached on stdin");
-                return Err(McpError::transport_error("EOF reached"));
-            }
-            Ok(_) => {
-                trace!("Read line from stdin: {}", line.trim());
+        loop {
+            line.clear();
+            match read_line_retrying(&mut self.stdin_reader, &mut line).await {
+                Ok(0) => {
+                    debug!("EOF reached on stdin");
+                    return Err(McpError::transport_error("EOF reached"));
+                }
+                Ok(_) => {
+                    trace!("Read line from stdin: {}", line.trim());
+                }
+                Err(e) => {
+                    error!("Failed to read from stdin: {}", e);
+                    return Err(McpError::transport_error(format!("Failed to read from stdin: {}", e)));
+                }
             }
-            Err(e) => {
-                error!("Failed to read from stdin: {}", e);
-                return Err(McpError::transport_error(format!("Failed to read from stdin: {}", e)));
+            if !line.trim().is_empty() {
+                break;
             }
+            warn!("Received empty line, skipping");
         }
         
         // Trim whitespace and parse JSON
         let line = line.trim();
-        if line.is_empty() {
-            warn!("Received empty line, skipping");
-            return self.read_message().await; // Recursively try again
-        }
         
         match serde_json::from_str::<JsonRpcRequest>(line) {
             Ok(request) => {
@@ -121,6 +168,21 @@ impl Transport for StdioTransport {
         }
     }
     
+    async fn write_notification(&mut self, notification: JsonRpcNotification) -> McpResult<()> {
+        let json = serde_json::to_string(&notification).map_err(|e| {
+            error!("Failed to serialize notification: {}", e);
+            McpError::serialization_error(format!("Failed to serialize notification: {}", e))
+        })?;
+        
+        debug!("Writing JSON-RPC notification: method={}", notification.method);
+        trace!("Notification JSON: {}", json);
+        
+        self.stdout.write_all(format!("{}\n", json).as_bytes()).await
+            .map_err(|e| McpError::transport_error(format!("Failed to write to stdout: {}", e)))?;
+        self.stdout.flush().await
+            .map_err(|e| McpError::transport_error(format!("Failed to flush stdout: {}", e)))
+    }
+    
     async fn close(&mut self) -> McpResult<()> {
         info!("Closing stdio transport");
         if let Err(e) = self.stdout.flush().await {
@@ -138,6 +200,7 @@ impl Transport for StdioTransport {
 pub struct ChannelTransport {
     request_receiver: mpsc::UnboundedReceiver<JsonRpcRequest>,
     response_sender: mpsc::UnboundedSender<JsonRpcResponse>,
+    notification_sender: Option<mpsc::UnboundedSender<JsonRpcNotification>>,
 }
 
 impl ChannelTransport {
@@ -156,10 +219,21 @@ impl ChannelTransport {
         let transport = Self {
             request_receiver,
             response_sender,
+            notification_sender: None,
         };
         
         (transport, request_sender, response_receiver)
     }
+    
+    /// Receive the notifications written to this transport.
+    /// 
+    /// Notifications are dropped until this is called; calling it again
+    /// replaces the previous receiver.
+    pub fn notifications(&mut self) -> mpsc::UnboundedReceiver<JsonRpcNotification> {
+        let (notification_sender, notification_receiver) = mpsc::unbounded_channel();
+        self.notification_sender = Some(notification_sender);
+        notification_receiver
+    }
 }
 
 #[async_trait::async_trait]
@@ -190,9 +264,137 @@ impl Transport for ChannelTransport {
         }
     }
     
+    async fn write_notification(&mut self, notification: JsonRpcNotification) -> McpResult<()> {
+        match &self.notification_sender {
+            Some(sender) => sender
+                .send(notification)
+                .map_err(|_| McpError::transport_error("Notification channel closed")),
+            None => {
+                trace!("No notification receiver, dropping {}", notification.method);
+                Ok(())
+            }
+        }
+    }
+    
     async fn close(&mut self) -> McpResult<()> {
         debug!("Closing channel transport");
         // Channels will be closed when dropped
         Ok(())
     }
 }
+
+/// WebSocket transport implementation.
+///
+/// Each JSON-RPC message is exchanged as a single text frame. This transport
+/// is intended for browser-based or gateway-based MCP clients that cannot
+/// spawn the server as a subprocess. A close frame (or the stream ending) is
+/// reported as EOF so the server follows the normal client-disconnect path.
+pub struct WebSocketTransport<S = TcpStream> {
+    stream: WebSocketStream<S>,
+}
+
+impl<S> WebSocketTransport<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + Sync,
+{
+    /// Create a transport from an already-established WebSocket stream.
+    pub fn new(stream: WebSocketStream<S>) -> Self {
+        Self { stream }
+    }
+
+    /// Perform the server-side WebSocket handshake on a raw stream.
+    pub async fn accept(stream: S) -> McpResult<Self> {
+        let stream = tokio_tungstenite::accept_async(stream)
+            .await
+            .map_err(|e| McpError::transport_error(format!("WebSocket handshake failed: {}", e)))?;
+        info!("Initializing WebSocket transport");
+        Ok(Self::new(stream))
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> Transport for WebSocketTransport<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + Sync,
+{
+    async fn read_message(&mut self) -> McpResult<JsonRpcRequest> {
+        loop {
+            let text = match self.stream.next().await {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Binary(data))) => String::from_utf8(data)
+                    .map_err(|e| McpError::parse_error(format!("Binary frame is not valid UTF-8: {}", e)))?,
+                Some(Ok(Message::Close(frame))) => {
+                    debug!("WebSocket close frame received: {:?}", frame);
+                    return Err(McpError::transport_error("EOF reached"));
+                }
+                // Pings are answered by tungstenite itself; nothing to dispatch
+                Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) | Some(Ok(Message::Frame(_))) => continue,
+                Some(Err(e)) => {
+                    error!("Failed to read from WebSocket: {}", e);
+                    return Err(McpError::transport_error(format!("Failed to read from WebSocket: {}", e)));
+                }
+                None => {
+                    debug!("WebSocket stream ended");
+                    return Err(McpError::transport_error("EOF reached"));
+                }
+            };
+
+            let text = text.trim();
+            if text.is_empty() {
+                warn!("Received empty WebSocket frame, skipping");
+                continue;
+            }
+            trace!("Read frame from WebSocket: {}", text);
+
+            return match serde_json::from_str::<JsonRpcRequest>(text) {
+                Ok(request) => {
+                    debug!("Parsed JSON-RPC request: method={}, id={:?}", request.method, request.id);
+                    Ok(request)
+                }
+                Err(e) => {
+                    error!("Failed to parse JSON-RPC request from frame '{}': {}", text, e);
+                    Err(McpError::parse_error(format!("Invalid JSON-RPC request: {}", e)))
+                }
+            };
+        }
+    }
+
+    async fn write_response(&mut self, response: JsonRpcResponse) -> McpResult<()> {
+        let json = serde_json::to_string(&response).map_err(|e| {
+            error!("Failed to serialize response: {}", e);
+            McpError::serialization_error(format!("Failed to serialize response: {}", e))
+        })?;
+
+        debug!("Writing JSON-RPC response: id={:?}, error={:?}", response.id, response.error.is_some());
+        trace!("Response JSON: {}", json);
+
+        self.stream.send(Message::Text(json)).await.map_err(|e| {
+            error!("Failed to write to WebSocket: {}", e);
+            McpError::transport_error(format!("Failed to write to WebSocket: {}", e))
+        })
+    }
+
+    async fn write_notification(&mut self, notification: JsonRpcNotification) -> McpResult<()> {
+        let json = serde_json::to_string(&notification).map_err(|e| {
+            error!("Failed to serialize notification: {}", e);
+            McpError::serialization_error(format!("Failed to serialize notification: {}", e))
+        })?;
+
+        debug!("Writing JSON-RPC notification: method={}", notification.method);
+        trace!("Notification JSON: {}", json);
+
+        self.stream.send(Message::Text(json)).await.map_err(|e| {
+            error!("Failed to write to WebSocket: {}", e);
+            McpError::transport_error(format!("Failed to write to WebSocket: {}", e))
+        })
+    }
+
+    async fn close(&mut self) -> McpResult<()> {
+        info!("Closing WebSocket transport");
+        if let Err(e) = self.stream.close(None).await {
+            // The peer usually initiated the close already
+            debug!("WebSocket close returned: {}", e);
+        }
+        Ok(())
+    }
+}
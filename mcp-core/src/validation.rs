@@ -0,0 +1,46 @@
+//! Tool result validation against declared output schemas.
+//!
+//! Enabled by the `output-validation` feature, meant for debug and test
+//! builds. A mismatch never fails the call; it is logged so drift between a
+//! backend's real responses and the declared schema gets noticed.
+
+use crate::registry::ToolResult;
+use crate::protocol::ToolContent;
+use serde_json::Value;
+use tracing::warn;
+
+/// Validate the JSON text of `result` against `schema`, logging any mismatch.
+pub fn check_output(tool_name: &str, schema: &Value, result: &ToolResult) {
+    if result.is_error {
+        return;
+    }
+    let Some(text) = result.content.iter().find_map(|content| match content {
+        ToolContent::Text { text } => Some(text),
+        _ => None,
+    }) else {
+        return;
+    };
+
+    let instance: Value = match serde_json::from_str(text) {
+        Ok(instance) => instance,
+        Err(e) => {
+            warn!("Tool '{}' declares an output schema but returned non-JSON text: {}", tool_name, e);
+            return;
+        }
+    };
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(e) => {
+            warn!("Tool '{}' has an invalid output schema: {}", tool_name, e);
+            return;
+        }
+    };
+    for error in validator.iter_errors(&instance) {
+        warn!(
+            "Tool '{}' output does not match its schema at '{}': {}",
+            tool_name,
+            error.instance_path(),
+            error
+        );
+    }
+}
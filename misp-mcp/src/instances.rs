@@ -0,0 +1,126 @@
+//! Additional MISP instances.
+//!
+//! Teams that query several MISP servers list the extra ones in a JSON file
+//! passed as `--instances-config`:
+//!
+//! ```json
+//! { "staging": { "url": "https://misp-staging.local", "api_key": "...", "verify_tls": true } }
+//! ```
+//!
+//! Every MISP tool then takes an optional `target` argument naming the
+//! instance to call. Without it, or with `"primary"`, the tool calls the
+//! instance configured by `--misp-url`/`--api-key`.
+
+use crate::misp_client::MispClient;
+use mcp_core::{McpError, McpResult, ToolInput};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Name of the instance configured by `--misp-url`/`--api-key`.
+pub const PRIMARY_INSTANCE: &str = "primary";
+
+/// Connection settings for one additional MISP instance.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstanceConfig {
+    /// MISP server base URL
+    pub url: String,
+    /// MISP API key for authentication
+    pub api_key: String,
+    /// Whether to verify TLS certificates
+    #[serde(default)]
+    pub verify_tls: bool,
+    /// PEM CA bundle to trust (enables verification)
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+    /// Request timeout in seconds
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_timeout() -> u64 {
+    30
+}
+
+/// Read the additional instances from a JSON file, keyed by name.
+pub fn load(path: &str) -> anyhow::Result<BTreeMap<String, InstanceConfig>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read instances config {}: {}", path, e))?;
+    let instances: BTreeMap<String, InstanceConfig> =
+        serde_json::from_str(&contents).map_err(|e| anyhow::anyhow!("Invalid instances config {}: {}", path, e))?;
+    for name in instances.keys() {
+        if name.trim().is_empty() || name == PRIMARY_INSTANCE {
+            anyhow::bail!("Invalid instances config {}: instance name '{}' is reserved", path, name);
+        }
+    }
+    Ok(instances)
+}
+
+/// The MISP clients tools can call, selected per call by `target`.
+#[derive(Debug, Clone)]
+pub struct MispClients {
+    primary: MispClient,
+    instances: Arc<BTreeMap<String, MispClient>>,
+}
+
+impl MispClients {
+    /// Only the primary instance.
+    pub fn new(primary: MispClient) -> Self {
+        Self {
+            primary,
+            instances: Arc::new(BTreeMap::new()),
+        }
+    }
+
+    /// Add a named instance.
+    pub fn with_instance(mut self, name: String, client: MispClient) -> Self {
+        Arc::make_mut(&mut self.instances).insert(name, client);
+        self
+    }
+
+    /// Instance names, primary first.
+    pub fn names(&self) -> Vec<&str> {
+        std::iter::once(PRIMARY_INSTANCE)
+            .chain(self.instances.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// The client named by `name`, if configured.
+    pub fn get(&self, name: &str) -> Option<&MispClient> {
+        match name {
+            PRIMARY_INSTANCE => Some(&self.primary),
+            _ => self.instances.get(name),
+        }
+    }
+
+    /// The client a tool call targets.
+    ///
+    /// Takes the `target` and `debug` arguments off `input`, so tools that
+    /// pass their arguments through to MISP never forward them. With
+    /// `debug: true` the client puts MISP's status, headers and body excerpt
+    /// into its errors.
+    pub fn select(&self, input: &mut ToolInput) -> McpResult<MispClient> {
+        let debug = match input.arguments.remove("debug") {
+            None => false,
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| McpError::invalid_argument("debug", "debug must be true or false"))?,
+        };
+        let client = match input.arguments.remove("target") {
+            None => &self.primary,
+            Some(target) => {
+                let name = target
+                    .as_str()
+                    .ok_or_else(|| McpError::invalid_argument("target", "target must be an instance name"))?;
+                self.get(name).ok_or_else(|| {
+                    McpError::invalid_argument(
+                        "target",
+                        format!("Unknown MISP instance '{}'; configured: {}", name, self.names().join(", ")),
+                    )
+                })?
+            }
+        };
+        Ok(client.clone().with_debug_errors(debug))
+    }
+}
@@ -0,0 +1,600 @@
+//! End-to-end tests for the MISP MCP server.
+//!
+//! Each test stands up a mock MISP with `wiremock`, points a real
+//! `MispClient` at it, registers the full tool set and drives the server
+//! through a `ChannelTransport`, so protocol handling, tool code and the
+//! HTTP client are exercised together. A few tests drive the `mcp-core`
+//! registry and transports directly.
+
+use crate::instances::MispClients;
+use crate::misp_client::{CircuitBreakerConfig, DefaultSearchFilters, MispClient, MispError};
+use crate::register_misp_tools;
+use crate::tools_config::ToolsConfig;
+use mcp_core::transport::{read_line_retrying, ChannelTransport, MAX_INTERRUPTED_RETRIES};
+use mcp_core::{JsonRpcRequest, JsonRpcResponse, McpResult, Server, Tool, ToolInput, ToolRegistry, ToolResult};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader, ReadBuf};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY: &str = "test-api-key";
+
+/// A running server wired to a mock MISP.
+struct Harness {
+    misp: MockServer,
+    requests: mpsc::UnboundedSender<JsonRpcRequest>,
+    responses: mpsc::UnboundedReceiver<JsonRpcResponse>,
+    server: JoinHandle<McpResult<()>>,
+    next_id: i64,
+}
+
+impl Harness {
+    /// Start the mock MISP and an initialized server in front of it.
+    async fn start() -> Self {
+        let misp = MockServer::start().await;
+        let client = mock_client(&misp).await;
+        Self::with_clients(misp, MispClients::new(client)).await
+    }
+
+    /// Start an initialized server using `clients`, which talk to `misp`.
+    async fn with_clients(misp: MockServer, clients: MispClients) -> Self {
+        let mut server = Server::new("misp-mcp-test", "0.0.0");
+        register_misp_tools(&mut server, clients, &ToolsConfig::default()).await.expect("tools register");
+
+        let (mut transport, requests, responses) = ChannelTransport::new();
+        let server = tokio::spawn(async move { server.run_with_transport(&mut transport).await });
+
+        let mut harness = Self { misp, requests, responses, server, next_id: 0 };
+        let init = harness
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {},
+                    "clientInfo": { "name": "integration-test", "version": "0.0.0" },
+                }),
+            )
+            .await;
+        assert!(init.error.is_none(), "initialize failed: {:?}", init.error);
+        harness
+    }
+
+    /// Send a request and wait for its response.
+    async fn request(&mut self, method: &str, params: Value) -> JsonRpcResponse {
+        self.next_id += 1;
+        let request = JsonRpcRequest::with_params(self.next_id, method, params).expect("request serializes");
+        self.requests.send(request).expect("server is running");
+        let response = self.responses.recv().await.expect("server answered");
+        assert_eq!(response.id, Some(json!(self.next_id)));
+        response
+    }
+
+    /// Call a tool and return the JSON it produced as text content.
+    async fn call_tool(&mut self, name: &str, arguments: Value) -> Value {
+        let response = self.call_tool_raw(name, arguments).await;
+        assert!(response.error.is_none(), "{} failed: {:?}", name, response.error);
+        let result = response.result.expect("tools/call result");
+        let text = result["content"][0]["text"].as_str().expect("text content");
+        serde_json::from_str(text).expect("tool output is JSON")
+    }
+
+    /// Call a tool and return the raw JSON-RPC response.
+    async fn call_tool_raw(&mut self, name: &str, arguments: Value) -> JsonRpcResponse {
+        self.request("tools/call", json!({ "name": name, "arguments": arguments })).await
+    }
+
+    /// Stop the server and make sure it shut down cleanly.
+    async fn shutdown(self) {
+        drop(self.requests);
+        self.server.await.expect("server task").expect("server exits cleanly");
+    }
+}
+
+/// A client for `misp` with no defaults configured.
+async fn mock_client(misp: &MockServer) -> MispClient {
+    MispClient::new(misp.uri(), API_KEY.to_string(), false, None, 5, None)
+        .await
+        .expect("client for mock MISP")
+}
+
+fn user_entry(id: &str, email: &str) -> Value {
+    json!({
+        "User": {
+            "id": id, "org_id": "1", "email": email, "autoalert": false, "invited_by": "0",
+            "nids_sid": "4000000", "termsaccepted": true, "newsread": "0", "role_id": "1",
+            "change_pw": false, "contactalert": false, "disabled": false, "current_login": "0",
+            "last_login": "0", "force_logout": false, "date_modified": "1700000000",
+        },
+        "Role": { "id": "1", "name": "admin" },
+        "Organisation": { "id": "1", "name": "ORGNAME" },
+    })
+}
+
+#[tokio::test]
+async fn get_users_returns_users_from_misp() {
+    let mut harness = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .and(header("Authorization", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            user_entry("1", "admin@admin.test"),
+            user_entry("2", "analyst@example.org"),
+        ])))
+        .expect(1)
+        .mount(&harness.misp)
+        .await;
+
+    let users = harness.call_tool("get_users", json!({})).await;
+
+    let emails: Vec<&str> = users
+        .as_array()
+        .expect("user list")
+        .iter()
+        .map(|entry| entry["User"]["email"].as_str().expect("email"))
+        .collect();
+    assert_eq!(emails, ["admin@admin.test", "analyst@example.org"]);
+    assert_eq!(users[0]["Role"]["name"], "admin");
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn misp_http_errors_become_is_error_results() {
+    let mut harness = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path("/admin/users/view/42"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "message": "Invalid user" })))
+        .mount(&harness.misp)
+        .await;
+
+    let response = harness.call_tool_raw("get_user", json!({ "user_id": "42" })).await;
+
+    assert!(response.error.is_none(), "MISP failure surfaced as a JSON-RPC error: {:?}", response.error);
+    let result = response.result.expect("tools/call result");
+    assert_eq!(result["isError"], true);
+    let text = result["content"][0]["text"].as_str().expect("error text");
+    assert!(text.starts_with("Failed to get user 42"), "unexpected error text: {}", text);
+    let details: serde_json::Value =
+        serde_json::from_str(result["content"][1]["text"].as_str().expect("error details")).expect("error details JSON");
+    assert_eq!(details, json!({ "tool": "get_user", "status": 404 }));
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn csv_export_asks_misp_for_csv_and_returns_it_verbatim() {
+    let mut harness = Harness::start().await;
+    let csv = "uuid,event_id,category,type,value\n5f1c-..,12,Network activity,ip-dst,203.0.113.7\n";
+    Mock::given(method("POST"))
+        .and(path("/attributes/restSearch"))
+        .and(header("Accept", "text/csv"))
+        .and(body_partial_json(json!({ "returnFormat": "csv", "eventid": "12" })))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(csv, "text/csv"))
+        .expect(1)
+        .mount(&harness.misp)
+        .await;
+
+    let response = harness.call_tool_raw("export_attributes_csv", json!({ "eventid": "12" })).await;
+
+    assert!(response.error.is_none(), "export failed: {:?}", response.error);
+    let result = response.result.expect("tools/call result");
+    assert_eq!(result["content"][0]["text"], csv);
+    harness.shutdown().await;
+}
+
+/// Minimal HTTP/1.1 server answering every request with `[]`; returns its
+/// base URL and the number of TCP connections it has accepted.
+async fn start_connection_counting_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind local port");
+    let url = format!("http://{}", listener.local_addr().expect("local address"));
+    let connections = Arc::new(AtomicUsize::new(0));
+    let accepted = connections.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            accepted.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                loop {
+                    let read = match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(read) => read,
+                    };
+                    request.extend_from_slice(&buffer[..read]);
+                    // Only body-less GETs are sent, so a blank line ends each request
+                    if request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        request.clear();
+                        let response = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\n\r\n[]";
+                        if stream.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+    });
+    (url, connections)
+}
+
+#[tokio::test]
+async fn sequential_requests_reuse_one_connection() {
+    let (url, connections) = start_connection_counting_server().await;
+    let client = MispClient::new(url, API_KEY.to_string(), false, None, 5, Some(Duration::from_secs(15)))
+        .await
+        .expect("client for counting server");
+
+    for _ in 0..5 {
+        // Tool closures each call through their own clone of the client
+        client.clone().get_users().await.expect("users from counting server");
+    }
+
+    assert_eq!(connections.load(Ordering::SeqCst), 1, "every request after the first reuses the pooled connection");
+}
+
+/// Client for the mock MISP with a breaker that opens after two failures
+/// and probes again after 200ms.
+async fn client_with_fast_breaker(misp: &MockServer) -> MispClient {
+    MispClient::new(misp.uri(), API_KEY.to_string(), false, None, 5, None)
+        .await
+        .expect("client for mock MISP")
+        .with_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_millis(200),
+            max_cooldown: Duration::from_secs(1),
+        })
+}
+
+#[tokio::test]
+async fn circuit_breaker_opens_fails_fast_and_recovers_after_cooldown() {
+    let misp = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(2)
+        .mount(&misp)
+        .await;
+    let client = client_with_fast_breaker(&misp).await;
+
+    for _ in 0..2 {
+        assert!(matches!(client.get_users().await, Err(MispError::Api { status: 500, .. })));
+    }
+    // Open: fails without reaching MISP (the mock expects exactly two calls)
+    assert!(matches!(client.get_users().await, Err(MispError::CircuitOpen { .. })));
+    misp.verify().await;
+
+    misp.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&misp)
+        .await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    client.get_users().await.expect("probe after the cooldown closes the breaker");
+    client.get_users().await.expect("closed breaker lets calls through");
+}
+
+#[tokio::test]
+async fn maintenance_responses_do_not_open_circuit_breaker() {
+    let misp = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "120"))
+        .expect(4)
+        .mount(&misp)
+        .await;
+    let client = client_with_fast_breaker(&misp).await;
+
+    // Twice the failure threshold, and every call still reaches MISP
+    for _ in 0..4 {
+        assert!(matches!(client.get_users().await, Err(MispError::Maintenance { retry_after: Some(120) })));
+    }
+    misp.verify().await;
+}
+
+#[tokio::test]
+async fn abandoned_probe_reopens_circuit_breaker() {
+    let misp = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(2)
+        .mount(&misp)
+        .await;
+    let client = client_with_fast_breaker(&misp).await;
+    for _ in 0..2 {
+        assert!(client.get_users().await.is_err());
+    }
+
+    // The probe hangs and its caller gives up on it
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])).set_delay(Duration::from_secs(2)))
+        .up_to_n_times(1)
+        .mount(&misp)
+        .await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert!(tokio::time::timeout(Duration::from_millis(50), client.get_users()).await.is_err());
+
+    // Open again with a fresh cooldown rather than stuck half-open
+    assert!(matches!(client.get_users().await, Err(MispError::CircuitOpen { retry_in_secs }) if retry_in_secs > 0));
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&misp)
+        .await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    client.get_users().await.expect("next probe goes through and closes the breaker");
+}
+
+#[tokio::test]
+async fn default_org_reaches_every_search_endpoint() {
+    let misp = MockServer::start().await;
+    let client = mock_client(&misp).await.with_default_search_filters(DefaultSearchFilters {
+        org: Some("CIRCL".to_string()),
+        ..Default::default()
+    });
+    let mut harness = Harness::with_clients(misp, MispClients::new(client)).await;
+    // Each endpoint serves two tools, except the objects search
+    let endpoints = [
+        ("/events/restSearch", json!({ "response": [] }), 2),
+        ("/events/index", json!([]), 2),
+        ("/attributes/restSearch", json!({ "response": { "Attribute": [] } }), 2),
+        ("/objects/restsearch", json!({ "response": [] }), 1),
+    ];
+    for (endpoint, body, calls) in endpoints {
+        Mock::given(method("POST"))
+            .and(path(endpoint))
+            .and(body_partial_json(json!({ "org": "CIRCL" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(calls)
+            .mount(&harness.misp)
+            .await;
+    }
+
+    harness.call_tool("events_rest_search", json!({})).await;
+    harness.call_tool("recent_changes", json!({})).await;
+    harness.call_tool("search_events", json!({ "request_json": "{}" })).await;
+    harness.call_tool("get_events", json!({})).await;
+    harness.call_tool("attributes_rest_search", json!({ "filter_json": "{}" })).await;
+    harness.call_tool("list_attributes", json!({})).await;
+    harness.call_tool("objects_rest_search", json!({})).await;
+
+    harness.misp.verify().await;
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn target_argument_routes_calls_to_that_instance() {
+    let production = MockServer::start().await;
+    let staging = MockServer::start().await;
+    let clients = MispClients::new(mock_client(&production).await)
+        .with_instance("staging".to_string(), mock_client(&staging).await);
+    let mut harness = Harness::with_clients(production, clients).await;
+    let event = json!({ "id": "7", "info": "Staging event", "distribution": "0" });
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([event])))
+        .expect(1)
+        .mount(&staging)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&harness.misp)
+        .await;
+
+    let events = harness.call_tool("get_events", json!({ "target": "staging" })).await;
+
+    assert_eq!(events[0]["info"], "Staging event");
+    staging.verify().await;
+    harness.misp.verify().await;
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn count_events_reads_the_result_count_header() {
+    let mut harness = Harness::start().await;
+    Mock::given(method("POST"))
+        .and(path("/events/restSearch"))
+        .and(body_partial_json(json!({ "org": "CIRCL", "minimal": true, "limit": 1, "page": 1 })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Result-Count", "1234")
+                .set_body_json(json!({ "response": [{ "Event": { "id": "1", "timestamp": "1700000000" } }] })),
+        )
+        .expect(1)
+        .mount(&harness.misp)
+        .await;
+
+    let counted = harness.call_tool("count_events", json!({ "org": "CIRCL" })).await;
+
+    assert_eq!(counted, json!({ "org": "CIRCL", "count": 1234 }));
+    harness.misp.verify().await;
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn count_events_pages_through_metadata_under_a_distribution_floor() {
+    let misp = MockServer::start().await;
+    let client = mock_client(&misp).await.with_default_search_filters(DefaultSearchFilters {
+        min_distribution: Some(misp_types::Distribution::ThisCommunityOnly),
+        ..Default::default()
+    });
+    let mut harness = Harness::with_clients(misp, MispClients::new(client)).await;
+    let event = |id: &str, distribution: &str| json!({ "Event": { "id": id, "info": "Event", "distribution": distribution } });
+    Mock::given(method("POST"))
+        .and(path("/events/restSearch"))
+        .and(body_partial_json(json!({ "metadata": true, "limit": 500, "page": 1 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "response": [event("1", "0"), event("2", "1"), event("3", "3")],
+        })))
+        .expect(1)
+        .mount(&harness.misp)
+        .await;
+
+    let counted = harness.call_tool("count_events", json!({})).await;
+
+    // The organisation-only event is below the floor
+    assert_eq!(counted["count"], 2);
+    harness.misp.verify().await;
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn concurrency_limit_serializes_one_tool_only() {
+    let slow_running = Arc::new(AtomicUsize::new(0));
+    let slow_peak = Arc::new(AtomicUsize::new(0));
+    let mut registry = ToolRegistry::new();
+    let (running, peak) = (slow_running.clone(), slow_peak.clone());
+    registry.register(
+        Tool::new("slow", "Sleeps while counted as running", move |_input: ToolInput| {
+            let (running, peak) = (running.clone(), peak.clone());
+            Box::pin(async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(ToolResult::text("slow"))
+            })
+        })
+        .with_concurrency_limit(1),
+    );
+    let running = slow_running.clone();
+    registry.register(Tool::new("other", "Reports whether slow is running", move |_input: ToolInput| {
+        let running = running.clone();
+        Box::pin(async move { Ok(ToolResult::text(running.load(Ordering::SeqCst).to_string())) })
+    }));
+
+    let slow = || registry.execute_tool("slow", Default::default());
+    let other = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        registry.execute_tool("other", Default::default()).await
+    };
+    let started = std::time::Instant::now();
+    let (first, second, other) = tokio::join!(slow(), slow(), other);
+
+    first.expect("first slow call");
+    second.expect("second slow call");
+    assert_eq!(slow_peak.load(Ordering::SeqCst), 1, "limited calls overlapped");
+    assert!(started.elapsed() >= Duration::from_millis(200), "limited calls did not wait for each other");
+    // The unlimited tool ran while a slow call held the permit
+    let other = other.expect("other call");
+    assert_eq!(serde_json::to_value(&other.content[0]).expect("content")["text"], "1");
+}
+
+#[tokio::test]
+async fn websocket_server_initializes_and_calls_tools() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    // run_websocket binds the address itself, so reserve a free port first
+    let addr = TcpListener::bind("127.0.0.1:0").await.expect("bind").local_addr().expect("local address");
+    let mut server = Server::new("misp-mcp-test", "0.0.0");
+    server.add_tool(Tool::new("echo", "Returns its text argument", |input: ToolInput| {
+        Box::pin(async move { Ok(ToolResult::text(input.get_argument::<String>("text")?)) })
+    }));
+    let server = tokio::spawn(async move { server.run_websocket(addr).await });
+
+    let url = format!("ws://{}", addr);
+    let mut socket = None;
+    for _ in 0..50 {
+        match tokio_tungstenite::connect_async(url.as_str()).await {
+            Ok((stream, _)) => {
+                socket = Some(stream);
+                break;
+            }
+            Err(_) => tokio::time::sleep(Duration::from_millis(20)).await,
+        }
+    }
+    let mut socket = socket.expect("server accepts WebSocket connections");
+    let message = |id: i64, method: &str, params: Value| {
+        let request = JsonRpcRequest::with_params(id, method, params).expect("request serializes");
+        Message::text(serde_json::to_string(&request).expect("request JSON"))
+    };
+
+    socket
+        .send(message(
+            1,
+            "initialize",
+            json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "integration-test", "version": "0.0.0" },
+            }),
+        ))
+        .await
+        .expect("send initialize");
+    let init: JsonRpcResponse = match socket.next().await.expect("initialize answer").expect("frame") {
+        Message::Text(text) => serde_json::from_str(&text).expect("response JSON"),
+        other => panic!("unexpected frame: {:?}", other),
+    };
+    assert!(init.error.is_none(), "initialize failed: {:?}", init.error);
+
+    socket
+        .send(message(2, "tools/call", json!({ "name": "echo", "arguments": { "text": "over websocket" } })))
+        .await
+        .expect("send tools/call");
+    let response: JsonRpcResponse = match socket.next().await.expect("tools/call answer").expect("frame") {
+        Message::Text(text) => serde_json::from_str(&text).expect("response JSON"),
+        other => panic!("unexpected frame: {:?}", other),
+    };
+    assert_eq!(response.id, Some(json!(2)));
+    assert_eq!(response.result.expect("tools/call result")["content"][0]["text"], "over websocket");
+
+    socket.close(None).await.expect("close");
+    server.abort();
+}
+
+/// A reader that fails with `Interrupted` a given number of times, then
+/// yields its data.
+struct InterruptedReader {
+    interruptions: u32,
+    data: VecDeque<u8>,
+}
+
+impl AsyncRead for InterruptedReader {
+    fn poll_read(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        if self.interruptions > 0 {
+            self.interruptions -= 1;
+            return Poll::Ready(Err(std::io::Error::from(std::io::ErrorKind::Interrupted)));
+        }
+        while buf.remaining() > 0 {
+            match self.data.pop_front() {
+                Some(byte) => buf.put_slice(&[byte]),
+                None => break,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn interrupted_reader(interruptions: u32, data: &str) -> BufReader<InterruptedReader> {
+    BufReader::new(InterruptedReader { interruptions, data: data.bytes().collect() })
+}
+
+#[tokio::test]
+async fn interrupted_reads_are_retried() {
+    let mut line = String::new();
+    let mut reader = interrupted_reader(1, "{\"jsonrpc\":\"2.0\"}\n");
+    let read = read_line_retrying(&mut reader, &mut line).await.expect("line after one interruption");
+    assert_eq!(read, line.len());
+    assert_eq!(line, "{\"jsonrpc\":\"2.0\"}\n");
+
+    let mut line = String::new();
+    let mut reader = interrupted_reader(MAX_INTERRUPTED_RETRIES, "ok\n");
+    read_line_retrying(&mut reader, &mut line).await.expect("line after the last tolerated interruption");
+    assert_eq!(line, "ok\n");
+
+    let mut line = String::new();
+    let mut reader = interrupted_reader(MAX_INTERRUPTED_RETRIES + 1, "never read\n");
+    let error = read_line_retrying(&mut reader, &mut line).await.expect_err("one interruption too many");
+    assert_eq!(error.kind(), std::io::ErrorKind::Interrupted);
+}
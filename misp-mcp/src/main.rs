@@ -11,13 +11,28 @@
 //! misp-types for strongly-typed MISP data structures.
 
 use clap::{Arg, Command};
-use mcp_core::{Server, Tool, ToolInput, ToolResult};
-use tracing::{error, info};
+use futures_util::StreamExt;
+use mcp_core::{Server, Tool, ToolContent, ToolInput, ToolResult};
+use tracing::{debug, error, info, warn};
 use tracing_subscriber::{fmt, EnvFilter};
 
+mod instances;
 mod misp_client;
-use misp_client::{MispClient, MispError};
-use misp_types::{types::CollectionFilterBody, AttributeRestSearchRequest, EventIndexRequest, EventsRestSearchRequest, ObjectsRestSearchRequest};
+mod permissions;
+mod prompts;
+mod summary;
+mod sync_cursor;
+mod timeline;
+mod tools_config;
+#[cfg(test)]
+mod integration_tests;
+use instances::{InstanceConfig, MispClients, PRIMARY_INSTANCE};
+use misp_client::{CircuitBreakerConfig, DefaultSearchFilters, MispClient, MispError, WriteDefaults};
+use std::collections::{BTreeMap, HashSet};
+use sync_cursor::CursorStore;
+use tools_config::ToolsConfig;
+use misp_types::indicators::{detect_type as detect_indicator_type, refang as refang_indicator};
+use misp_types::{types::CollectionFilterBody, AddAttributeRequest, AddCollectionElementRequest, AddObjectReferenceRequest, CreateCollectionRequest, AttributeRestSearchRequest, EventIndexRequest, EventsRestSearchRequest, GetTaxonomiesResponse, ObjectsRestSearchRequest};
 
 /// Application configuration loaded from environment variables and command line.
 #[derive(Debug, Clone)]
@@ -28,16 +43,50 @@ pub struct Config {
     pub api_key: String,
     /// Whether to verify TLS certificates (default: true)
     pub verify_tls: bool,
+    /// PEM CA bundle to trust for the MISP server (enables verification)
+    pub ca_cert: Option<String>,
     /// Request timeout in seconds (default: 30)
     pub timeout_seconds: u64,
+    /// TCP keepalive interval for pooled connections in seconds (default: 15, 0 disables)
+    pub tcp_keepalive_seconds: u64,
+    /// Filters applied to every event/attribute search
+    pub default_search_filters: DefaultSearchFilters,
+    /// Distribution given to created attributes that set none
+    pub write_defaults: WriteDefaults,
+    /// When to stop calling an unavailable MISP, and for how long
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// Username and password for the cookie-session fallback
+    pub session_login: Option<(String, String)>,
+    /// Route prefixes whose API-key refusals fall back to the cookie session
+    pub session_fallback_routes: Vec<String>,
+    /// Largest tool result returned inline; bigger ones become resources (0: no limit)
+    pub max_result_bytes: usize,
+    /// Largest MISP response body read (0: no limit)
+    pub max_response_bytes: usize,
+    /// Whether to expose the `misp_raw_request` tool
+    pub enable_raw_api: bool,
+    /// Which tools to register
+    pub tools: ToolsConfig,
+    /// File holding the `sync_events_since` cursors (tool disabled when unset)
+    pub sync_cursor_file: Option<String>,
+    /// Additional MISP instances tools can target, by name
+    pub instances: BTreeMap<String, InstanceConfig>,
+    /// Whether to check the API key's permissions against the tools at startup
+    pub check_permissions: bool,
 }
 
 impl Config {
+    /// TCP keepalive for MISP connections, `None` when disabled.
+    fn tcp_keepalive(&self) -> Option<std::time::Duration> {
+        (self.tcp_keepalive_seconds > 0).then(|| std::time::Duration::from_secs(self.tcp_keepalive_seconds))
+    }
+
     /// Load configuration from command line matches.
     pub fn from_matches(matches: &clap::ArgMatches) -> anyhow::Result<Self> {
         let misp_url = matches.get_one::<String>("misp-url").unwrap().clone();
         let api_key = matches.get_one::<String>("api-key").unwrap().clone();
         let verify_tls = matches.get_flag("verify-tls");
+        let ca_cert = matches.get_one::<String>("ca-cert").cloned();
         let timeout_seconds: u64 = matches
             .get_one::<String>("timeout")
             .unwrap()
@@ -48,7 +97,21 @@ impl Config {
             misp_url,
             api_key,
             verify_tls,
+            ca_cert,
             timeout_seconds,
+            tcp_keepalive_seconds: tcp_keepalive_from(matches)?,
+            default_search_filters: default_search_filters_from(matches)?,
+            write_defaults: write_defaults_from(matches)?,
+            circuit_breaker: circuit_breaker_from(matches)?,
+            session_login: session_login_from(matches),
+            session_fallback_routes: session_fallback_routes_from(matches),
+            max_result_bytes: max_result_bytes_from(matches)?,
+            max_response_bytes: max_response_bytes_from(matches)?,
+            enable_raw_api: matches.get_flag("enable-raw-api"),
+            check_permissions: matches.get_flag("check-permissions"),
+            tools: tools_config_from(matches)?,
+            sync_cursor_file: matches.get_one::<String>("sync-cursor-file").cloned(),
+            instances: instances_from(matches)?,
         })
     }
 
@@ -80,6 +143,13 @@ impl Config {
                     .help("Verify TLS certificates")
                     .action(clap::ArgAction::SetTrue)
             )
+            .arg(
+                Arg::new("ca-cert")
+                    .long("ca-cert")
+                    .env("MISP_CA_CERT")
+                    .help("PEM CA certificate bundle to trust for MISP (keeps TLS verification on)")
+                    .value_name("FILE")
+            )
             .arg(
                 Arg::new("timeout")
                     .long("timeout")
@@ -88,6 +158,144 @@ impl Config {
                     .default_value("30")
                     .value_name("SECONDS")
             )
+            .arg(
+                Arg::new("tcp-keepalive")
+                    .long("tcp-keepalive")
+                    .env("MISP_TCP_KEEPALIVE")
+                    .help("TCP keepalive interval in seconds for pooled MISP connections (0 disables)")
+                    .default_value("15")
+                    .value_name("SECONDS")
+            )
+            .arg(
+                Arg::new("default-org")
+                    .long("default-org")
+                    .env("MISP_DEFAULT_ORG")
+                    .help("Scope every event/attribute/object search to this organisation unless the request sets org")
+                    .value_name("ORG")
+            )
+            .arg(
+                Arg::new("exclude-tags")
+                    .long("exclude-tags")
+                    .env("MISP_EXCLUDE_TAGS")
+                    .help("Comma-separated tags excluded from every search unless the request names them")
+                    .value_delimiter(',')
+                    .value_name("TAGS")
+            )
+            .arg(
+                Arg::new("min-distribution")
+                    .long("min-distribution")
+                    .env("MISP_MIN_DISTRIBUTION")
+                    .help("Drop search results below this distribution level (0-3 or its name)")
+                    .value_name("LEVEL")
+            )
+            .arg(
+                Arg::new("default-distribution")
+                    .long("default-distribution")
+                    .env("MISP_DEFAULT_DISTRIBUTION")
+                    .help("Distribution (0-5 or its name) for created attributes that set none")
+                    .value_name("LEVEL")
+            )
+            .arg(
+                Arg::new("default-sharing-group")
+                    .long("default-sharing-group")
+                    .env("MISP_DEFAULT_SHARING_GROUP_ID")
+                    .help("Sharing group ID for created attributes with distribution 4 and no sharing group")
+                    .value_name("ID")
+            )
+            .arg(
+                Arg::new("breaker-threshold")
+                    .long("breaker-threshold")
+                    .env("MISP_BREAKER_THRESHOLD")
+                    .help("Consecutive MISP failures that pause calls to it (0 disables the circuit breaker)")
+                    .default_value("5")
+                    .value_name("COUNT")
+            )
+            .arg(
+                Arg::new("breaker-cooldown")
+                    .long("breaker-cooldown")
+                    .env("MISP_BREAKER_COOLDOWN")
+                    .help("Seconds to pause MISP calls once the circuit breaker opens (doubles while MISP stays down)")
+                    .default_value("30")
+                    .value_name("SECONDS")
+            )
+            .arg(
+                Arg::new("session-user")
+                    .long("session-user")
+                    .env("MISP_SESSION_USER")
+                    .help("MISP user (email) for a cookie session, used when a --session-fallback-route rejects the API key")
+                    .requires("session-password")
+                    .requires("session-fallback-route")
+                    .value_name("EMAIL")
+            )
+            .arg(
+                Arg::new("session-password")
+                    .long("session-password")
+                    .env("MISP_SESSION_PASSWORD")
+                    .help("Password for --session-user")
+                    .requires("session-user")
+                    .hide_env_values(true)
+                    .value_name("PASSWORD")
+            )
+            .arg(
+                Arg::new("session-fallback-route")
+                    .long("session-fallback-route")
+                    .env("MISP_SESSION_FALLBACK_ROUTES")
+                    .help("Comma-separated route prefixes (e.g. /galaxies/export) whose 401/403 answers are replayed over the cookie session")
+                    .value_delimiter(',')
+                    .requires("session-user")
+                    .value_name("ROUTES")
+            )
+            .arg(
+                Arg::new("max-result-bytes")
+                    .long("max-result-bytes")
+                    .env("MISP_MAX_RESULT_BYTES")
+                    .help("Largest tool result returned inline; larger results are returned as a resource to read (0 disables)")
+                    .default_value("0")
+                    .value_name("BYTES")
+            )
+            .arg(
+                Arg::new("max-response-bytes")
+                    .long("max-response-bytes")
+                    .env("MISP_MAX_RESPONSE_BYTES")
+                    .help("Largest MISP response body read; bigger responses fail instead of being loaded into memory (0 disables)")
+                    .default_value("268435456")
+                    .value_name("BYTES")
+            )
+            .arg(
+                Arg::new("enable-raw-api")
+                    .long("enable-raw-api")
+                    .env("MISP_ENABLE_RAW_API")
+                    .help("Expose the misp_raw_request tool, which can call any MISP endpoint")
+                    .action(clap::ArgAction::SetTrue)
+            )
+            .arg(
+                Arg::new("check-permissions")
+                    .long("check-permissions")
+                    .env("MISP_CHECK_PERMISSIONS")
+                    .help("Check at startup that the API key's role allows the registered write tools")
+                    .action(clap::ArgAction::SetTrue)
+            )
+            .arg(
+                Arg::new("tools-config")
+                    .long("tools-config")
+                    .env("MISP_TOOLS_CONFIG")
+                    .help("JSON file with the tools to register: {\"allow\": [...], \"deny\": [...]}")
+                    .value_name("FILE")
+            )
+            .arg(
+                Arg::new("sync-cursor-file")
+                    .long("sync-cursor-file")
+                    .env("MISP_SYNC_CURSOR_FILE")
+                    .help("JSON file storing sync cursors; enables the sync_events_since tool")
+                    .value_name("FILE")
+            )
+            .arg(
+                Arg::new("instances-config")
+                    .long("instances-config")
+                    .env("MISP_INSTANCES_CONFIG")
+                    .help("JSON file listing additional named MISP instances tools can target")
+                    .value_name("FILE")
+            )
             .arg(
                 Arg::new("quiet")
                     .long("quiet")
@@ -100,6 +308,7 @@ impl Config {
         let misp_url = matches.get_one::<String>("misp-url").unwrap().clone();
         let api_key = matches.get_one::<String>("api-key").unwrap().clone();
         let verify_tls = matches.get_flag("verify-tls");
+        let ca_cert = matches.get_one::<String>("ca-cert").cloned();
         let timeout_seconds: u64 = matches
             .get_one::<String>("timeout")
             .unwrap()
@@ -110,11 +319,148 @@ impl Config {
             misp_url,
             api_key,
             verify_tls,
+            ca_cert,
             timeout_seconds,
+            tcp_keepalive_seconds: tcp_keepalive_from(&matches)?,
+            default_search_filters: default_search_filters_from(&matches)?,
+            write_defaults: write_defaults_from(&matches)?,
+            circuit_breaker: circuit_breaker_from(&matches)?,
+            session_login: session_login_from(&matches),
+            session_fallback_routes: session_fallback_routes_from(&matches),
+            max_result_bytes: max_result_bytes_from(&matches)?,
+            max_response_bytes: max_response_bytes_from(&matches)?,
+            enable_raw_api: matches.get_flag("enable-raw-api"),
+            check_permissions: matches.get_flag("check-permissions"),
+            tools: tools_config_from(&matches)?,
+            sync_cursor_file: matches.get_one::<String>("sync-cursor-file").cloned(),
+            instances: instances_from(&matches)?,
         })
     }
 }
 
+/// Read the default search filters from command line matches.
+fn default_search_filters_from(matches: &clap::ArgMatches) -> anyhow::Result<DefaultSearchFilters> {
+    let min_distribution = match matches.get_one::<String>("min-distribution") {
+        Some(level) => {
+            let level: misp_types::Distribution = level.parse()?;
+            if matches!(level, misp_types::Distribution::SharingGroup | misp_types::Distribution::InheritEvent) {
+                anyhow::bail!("Invalid min-distribution '{}': use a level from 0 to 3", level.as_str());
+            }
+            Some(level)
+        }
+        None => None,
+    };
+    Ok(DefaultSearchFilters {
+        org: matches.get_one::<String>("default-org").cloned().filter(|org| !org.trim().is_empty()),
+        exclude_tags: matches
+            .get_many::<String>("exclude-tags")
+            .map(|tags| tags.map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect())
+            .unwrap_or_default(),
+        min_distribution,
+    })
+}
+
+/// Read the write defaults from command line matches.
+fn write_defaults_from(matches: &clap::ArgMatches) -> anyhow::Result<WriteDefaults> {
+    let sharing_group_id = matches
+        .get_one::<String>("default-sharing-group")
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    let distribution = match matches.get_one::<String>("default-distribution") {
+        Some(level) => Some(level.parse::<misp_types::Distribution>()?),
+        // A sharing group alone implies distribution 4
+        None => sharing_group_id.as_ref().map(|_| misp_types::Distribution::SharingGroup),
+    };
+    match (distribution, &sharing_group_id) {
+        (Some(misp_types::Distribution::SharingGroup), None) => {
+            anyhow::bail!("default-distribution 4 (sharing group) needs default-sharing-group")
+        }
+        (Some(level), Some(_)) if level != misp_types::Distribution::SharingGroup => {
+            anyhow::bail!("default-sharing-group only applies with default-distribution 4, not {}", level.as_str())
+        }
+        _ => {}
+    }
+    Ok(WriteDefaults { distribution, sharing_group_id })
+}
+
+/// Read the circuit breaker settings from command line matches.
+fn circuit_breaker_from(matches: &clap::ArgMatches) -> anyhow::Result<CircuitBreakerConfig> {
+    let failure_threshold: u32 = matches
+        .get_one::<String>("breaker-threshold")
+        .unwrap()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid breaker-threshold value: {}", e))?;
+    let cooldown_seconds: u64 = matches
+        .get_one::<String>("breaker-cooldown")
+        .unwrap()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid breaker-cooldown value: {}", e))?;
+    let defaults = CircuitBreakerConfig::default();
+    let cooldown = std::time::Duration::from_secs(cooldown_seconds);
+    Ok(CircuitBreakerConfig {
+        failure_threshold,
+        cooldown,
+        max_cooldown: defaults.max_cooldown.max(cooldown),
+    })
+}
+
+/// Read the TCP keepalive interval from command line matches.
+fn tcp_keepalive_from(matches: &clap::ArgMatches) -> anyhow::Result<u64> {
+    matches
+        .get_one::<String>("tcp-keepalive")
+        .unwrap()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid tcp-keepalive value: {}", e))
+}
+
+/// Read the cookie-session credentials from command line matches.
+fn session_login_from(matches: &clap::ArgMatches) -> Option<(String, String)> {
+    let username = matches.get_one::<String>("session-user")?;
+    let password = matches.get_one::<String>("session-password")?;
+    Some((username.clone(), password.clone()))
+}
+
+/// Read the cookie-session fallback routes from command line matches.
+fn session_fallback_routes_from(matches: &clap::ArgMatches) -> Vec<String> {
+    matches
+        .get_many::<String>("session-fallback-route")
+        .map(|routes| routes.cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Read the inline tool result budget from command line matches.
+fn max_result_bytes_from(matches: &clap::ArgMatches) -> anyhow::Result<usize> {
+    matches
+        .get_one::<String>("max-result-bytes")
+        .unwrap()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid max-result-bytes value: {}", e))
+}
+
+/// Read the MISP response size limit from command line matches.
+fn max_response_bytes_from(matches: &clap::ArgMatches) -> anyhow::Result<usize> {
+    matches
+        .get_one::<String>("max-response-bytes")
+        .unwrap()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid max-response-bytes value: {}", e))
+}
+
+/// Load the tools config named on the command line, if any.
+fn tools_config_from(matches: &clap::ArgMatches) -> anyhow::Result<ToolsConfig> {
+    match matches.get_one::<String>("tools-config") {
+        Some(path) => ToolsConfig::load(path),
+        None => Ok(ToolsConfig::default()),
+    }
+}
+
+fn instances_from(matches: &clap::ArgMatches) -> anyhow::Result<BTreeMap<String, InstanceConfig>> {
+    match matches.get_one::<String>("instances-config") {
+        Some(path) => instances::load(path),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Parse command line arguments first to check for quiet mode
@@ -144,6 +490,13 @@ async fn main() -> anyhow::Result<()> {
                 .help("Verify TLS certificates")
                 .action(clap::ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new("ca-cert")
+                .long("ca-cert")
+                .env("MISP_CA_CERT")
+                .help("PEM CA certificate bundle to trust for MISP (keeps TLS verification on)")
+                .value_name("FILE")
+        )
         .arg(
             Arg::new("timeout")
                 .long("timeout")
@@ -152,6 +505,144 @@ async fn main() -> anyhow::Result<()> {
                 .default_value("30")
                 .value_name("SECONDS")
         )
+        .arg(
+            Arg::new("tcp-keepalive")
+                .long("tcp-keepalive")
+                .env("MISP_TCP_KEEPALIVE")
+                .help("TCP keepalive interval in seconds for pooled MISP connections (0 disables)")
+                .default_value("15")
+                .value_name("SECONDS")
+        )
+        .arg(
+            Arg::new("default-org")
+                .long("default-org")
+                .env("MISP_DEFAULT_ORG")
+                .help("Scope every event/attribute/object search to this organisation unless the request sets org")
+                .value_name("ORG")
+        )
+        .arg(
+            Arg::new("exclude-tags")
+                .long("exclude-tags")
+                .env("MISP_EXCLUDE_TAGS")
+                .help("Comma-separated tags excluded from every search unless the request names them")
+                .value_delimiter(',')
+                .value_name("TAGS")
+        )
+        .arg(
+            Arg::new("min-distribution")
+                .long("min-distribution")
+                .env("MISP_MIN_DISTRIBUTION")
+                .help("Drop search results below this distribution level (0-3 or its name)")
+                .value_name("LEVEL")
+        )
+        .arg(
+            Arg::new("default-distribution")
+                .long("default-distribution")
+                .env("MISP_DEFAULT_DISTRIBUTION")
+                .help("Distribution (0-5 or its name) for created attributes that set none")
+                .value_name("LEVEL")
+        )
+        .arg(
+            Arg::new("default-sharing-group")
+                .long("default-sharing-group")
+                .env("MISP_DEFAULT_SHARING_GROUP_ID")
+                .help("Sharing group ID for created attributes with distribution 4 and no sharing group")
+                .value_name("ID")
+        )
+        .arg(
+            Arg::new("breaker-threshold")
+                .long("breaker-threshold")
+                .env("MISP_BREAKER_THRESHOLD")
+                .help("Consecutive MISP failures that pause calls to it (0 disables the circuit breaker)")
+                .default_value("5")
+                .value_name("COUNT")
+        )
+        .arg(
+            Arg::new("breaker-cooldown")
+                .long("breaker-cooldown")
+                .env("MISP_BREAKER_COOLDOWN")
+                .help("Seconds to pause MISP calls once the circuit breaker opens (doubles while MISP stays down)")
+                .default_value("30")
+                .value_name("SECONDS")
+        )
+        .arg(
+            Arg::new("session-user")
+                .long("session-user")
+                .env("MISP_SESSION_USER")
+                .help("MISP user (email) for a cookie session, used when a --session-fallback-route rejects the API key")
+                .requires("session-password")
+                .requires("session-fallback-route")
+                .value_name("EMAIL")
+        )
+        .arg(
+            Arg::new("session-password")
+                .long("session-password")
+                .env("MISP_SESSION_PASSWORD")
+                .help("Password for --session-user")
+                .requires("session-user")
+                .hide_env_values(true)
+                .value_name("PASSWORD")
+        )
+        .arg(
+            Arg::new("session-fallback-route")
+                .long("session-fallback-route")
+                .env("MISP_SESSION_FALLBACK_ROUTES")
+                .help("Comma-separated route prefixes (e.g. /galaxies/export) whose 401/403 answers are replayed over the cookie session")
+                .value_delimiter(',')
+                .requires("session-user")
+                .value_name("ROUTES")
+        )
+        .arg(
+            Arg::new("max-result-bytes")
+                .long("max-result-bytes")
+                .env("MISP_MAX_RESULT_BYTES")
+                .help("Largest tool result returned inline; larger results are returned as a resource to read (0 disables)")
+                .default_value("0")
+                .value_name("BYTES")
+        )
+        .arg(
+            Arg::new("max-response-bytes")
+                .long("max-response-bytes")
+                .env("MISP_MAX_RESPONSE_BYTES")
+                .help("Largest MISP response body read; bigger responses fail instead of being loaded into memory (0 disables)")
+                .default_value("268435456")
+                .value_name("BYTES")
+        )
+        .arg(
+            Arg::new("enable-raw-api")
+                .long("enable-raw-api")
+                .env("MISP_ENABLE_RAW_API")
+                .help("Expose the misp_raw_request tool, which can call any MISP endpoint")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("check-permissions")
+                .long("check-permissions")
+                .env("MISP_CHECK_PERMISSIONS")
+                .help("Check at startup that the API key's role allows the registered write tools")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("tools-config")
+                .long("tools-config")
+                .env("MISP_TOOLS_CONFIG")
+                .help("JSON file with the tools to register: {\"allow\": [...], \"deny\": [...]}")
+                .value_name("FILE")
+        )
+        .arg(
+            Arg::new("sync-cursor-file")
+                .long("sync-cursor-file")
+                .env("MISP_SYNC_CURSOR_FILE")
+                .help("JSON file storing sync cursors; enables the sync_events_since tool")
+                .value_name("FILE")
+        )
+        .arg(
+            Arg::new("instances-config")
+                .long("instances-config")
+                .env("MISP_INSTANCES_CONFIG")
+                .help("JSON file listing additional named MISP instances tools can target")
+                .value_name("FILE")
+        )
         .arg(
             Arg::new("quiet")
                 .long("quiet")
@@ -171,7 +662,7 @@ async fn main() -> anyhow::Result<()> {
             .add_directive("misp_client=info".parse()?);
         
         fmt()
-            .with_writer(std::io::stderr)  // Send logs to stderr, keep stdout clean for JSON-RPC
+            .with_writer(mcp_core::transport::log_writer)  // Logs to stderr, stdout is for JSON-RPC only
             .with_env_filter(filter)
             .with_target(false)
             .init();
@@ -188,8 +679,8 @@ async fn main() -> anyhow::Result<()> {
     })?;
 
     if !quiet_mode {
-        info!("Loaded configuration: MISP URL = {}, Verify TLS = {}, Timeout = {}s", 
-              config.misp_url, config.verify_tls, config.timeout_seconds);
+        info!("Loaded configuration: MISP URL = {}, Verify TLS = {}, CA cert = {}, Timeout = {}s", 
+              config.misp_url, config.verify_tls || config.ca_cert.is_some(), config.ca_cert.as_deref().unwrap_or("system"), config.timeout_seconds);
     }
 
     // Create MISP client
@@ -197,19 +688,115 @@ async fn main() -> anyhow::Result<()> {
         config.misp_url.clone(),
         config.api_key.clone(),
         config.verify_tls,
+        config.ca_cert.as_deref(),
         config.timeout_seconds,
+        config.tcp_keepalive(),
     ).await.map_err(|e| {
         error!("Failed to create MISP client: {}", e);
         e
-    })?;
+    })?
+    .with_circuit_breaker(config.circuit_breaker.clone())
+    .with_max_response_bytes(config.max_response_bytes);
+    let misp_client = if config.default_search_filters.is_empty() {
+        misp_client
+    } else {
+        if !quiet_mode {
+            info!("Default search filters: {:?}", config.default_search_filters);
+        }
+        misp_client.with_default_search_filters(config.default_search_filters.clone())
+    };
+    let misp_client = if config.write_defaults.is_empty() {
+        misp_client
+    } else {
+        if !quiet_mode {
+            info!("Write defaults: {:?}", config.write_defaults);
+        }
+        misp_client.with_write_defaults(config.write_defaults.clone())
+    };
+
+    let misp_client = match &config.session_login {
+        Some((username, password)) => {
+            let misp_client = misp_client.with_session_fallback(
+                username.clone(),
+                password.clone(),
+                config.session_fallback_routes.clone(),
+            )?;
+            // Log in up front so bad credentials show at startup; the fallback
+            // logs in again on demand if this fails or the session expires
+            if let Err(e) = misp_client.login(username, password).await {
+                warn!("Cookie session login as {} failed: {}", username, e);
+            }
+            misp_client
+        }
+        None => misp_client,
+    };
+
+    let mut clients = MispClients::new(misp_client.clone());
+    for (name, instance) in &config.instances {
+        let client = MispClient::new(
+            instance.url.clone(),
+            instance.api_key.clone(),
+            instance.verify_tls,
+            instance.ca_cert.as_deref(),
+            instance.timeout,
+            config.tcp_keepalive(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create MISP client for instance {}: {}", name, e))?
+        .with_circuit_breaker(config.circuit_breaker.clone())
+        .with_max_response_bytes(config.max_response_bytes)
+        // Operator policy holds on every instance; the session login is the
+        // primary's account, so the cookie fallback stays with it
+        .with_default_search_filters(config.default_search_filters.clone())
+        .with_write_defaults(config.write_defaults.clone());
+        if !quiet_mode {
+            info!("Additional MISP instance {} = {}", name, instance.url);
+        }
+        clients = clients.with_instance(name.clone(), client);
+    }
 
     // Create MCP server
-    let mut server = Server::new("misp-mcp-server", "0.1.0");
+    let mut server = Server::new("misp-mcp-server", "0.1.0").with_prompts();
+    if config.max_result_bytes > 0 {
+        server = server.with_result_budget(config.max_result_bytes, "misp://result/");
+    }
 
     // Register MISP tools
-    register_misp_tools(&mut server, misp_client).await?;
+    if config.enable_raw_api {
+        warn!("Raw MISP API access enabled: misp_raw_request can call any endpoint");
+        register_raw_api_tool(&mut server, clients.clone(), &config.tools);
+    }
+    if let Some(path) = &config.sync_cursor_file {
+        register_sync_tool(&mut server, clients.clone(), &config.tools, CursorStore::new(path));
+    }
+    register_misp_tools(&mut server, clients, &config.tools).await?;
+    let registered: HashSet<String> = server.tool_catalog().list().into_iter().map(|tool| tool.name).collect();
+    for name in config.tools.unknown_allowed(&registered) {
+        warn!("Tools config allows {}, which is not a known tool", name);
+    }
 
     info!("Registered {} tools", server.tool_count());
+    prompts::register_misp_prompts(&mut server);
+    if config.check_permissions {
+        match permissions::probe(&misp_client, &registered).await {
+            Ok(report) => {
+                info!(
+                    "API key belongs to {} ({}), role {}: {}",
+                    report.user,
+                    report.org.as_deref().unwrap_or("unknown organisation"),
+                    report.role,
+                    report.granted.join(", ")
+                );
+                for (tool, lacking) in &report.missing {
+                    warn!("{} is registered but role {} lacks {}", tool, report.role, lacking.join(", "));
+                }
+                if !report.is_sufficient() {
+                    warn!("The API key cannot use every registered write tool; deny them in the tools config or use a key with more permissions");
+                }
+            }
+            Err(e) => warn!("Permission check failed, MISP may be unreachable or the key invalid: {}", e),
+        }
+    }
 
     // Run the server
     server.run_stdio().await.map_err(|e| {
@@ -221,18 +808,539 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Concurrent calls allowed per /events/restSearch-backed tool when they
+/// overlap (`_batch` items); heavy filters there are expensive for the MISP
+/// server.
+const EXPENSIVE_SEARCH_CONCURRENCY: usize = 2;
+
+/// Most event ids `get_events_by_ids` fetches in one call.
+const MAX_BULK_EVENT_IDS: usize = 100;
+
+/// Event fetches `get_events_by_ids` keeps in flight at once.
+const BULK_FETCH_CONCURRENCY: usize = 4;
+
+/// Whether an ATT&CK cluster value is the technique asked for.
+///
+/// Cluster values read "Name - T1059"; a galaxy cluster search also returns
+/// sub-techniques (T1059.001) and partial name matches, which do not count.
+fn attack_cluster_matches(value: &str, technique: &str) -> bool {
+    match value.rsplit_once(" - ") {
+        Some((name, id)) => id.eq_ignore_ascii_case(technique) || name.eq_ignore_ascii_case(technique),
+        None => value.eq_ignore_ascii_case(technique),
+    }
+}
+
+/// Whether `value` is an ATT&CK technique ID such as T1566 or T1566.001.
+fn is_attack_technique_id(value: &str) -> bool {
+    let Some(digits) = value.strip_prefix(['T', 't']) else {
+        return false;
+    };
+    let (technique, sub_technique) = match digits.split_once('.') {
+        Some((technique, sub)) => (technique, Some(sub)),
+        None => (digits, None),
+    };
+    let all_digits = |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    all_digits(technique, 4) && sub_technique.is_none_or(|sub| all_digits(sub, 3))
+}
+
+/// `isError` result for a failed tool call.
+///
+/// The model reads the message; a second block carries `{"tool", "status"}`
+/// as JSON so clients can tell e.g. a 404 from a 403 without parsing text.
+/// `status` is the HTTP status MISP answered with and is left out when
+/// there was none.
+fn tool_error_result(tool_name: &str, message: String, status: Option<u16>) -> ToolResult {
+    let mut details = serde_json::json!({ "tool": tool_name });
+    if let Some(status) = status {
+        details["status"] = serde_json::json!(status);
+    }
+    let mut result = ToolResult::error(message);
+    result.content.push(mcp_core::ToolContent::text(details.to_string()));
+    result
+}
+
+/// `isError` result for a failed MISP call.
+fn misp_tool_error(tool_name: &str, message: String, err: &MispError) -> ToolResult {
+    tool_error_result(tool_name, message, err.status_code())
+}
+
+/// Tool result for a MISP write.
+///
+/// Some endpoints save a write but answer with an empty body; the result is
+/// then `{"saved": true}` with a note, rather than `null`.
+fn write_result<T: serde::Serialize>(written: Option<T>) -> mcp_core::McpResult<ToolResult> {
+    let json = match written {
+        Some(written) => serde_json::to_string_pretty(&written),
+        None => serde_json::to_string_pretty(&serde_json::json!({
+            "saved": true,
+            "note": "MISP saved the change but returned no body",
+        })),
+    }
+    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+    Ok(ToolResult::text(json))
+}
+
+/// Tool result for fetching one entity by id.
+///
+/// The entity is returned as JSON. A missing entity is an ordinary result,
+/// `{"found": false, "id": ...}`, the same for every get-by-id tool; any other
+/// failure is an `isError` result naming the `kind` of entity.
+fn get_by_id_result<T: serde::Serialize>(
+    tool_name: &str,
+    kind: &str,
+    id: &str,
+    result: Result<T, MispError>,
+) -> mcp_core::McpResult<ToolResult> {
+    let value = match result {
+        Ok(entity) => serde_json::to_value(&entity),
+        Err(MispError::NotFound { .. }) => {
+            info!("{}: {} {} not found", tool_name, kind, id);
+            Ok(serde_json::json!({ "found": false, "id": id }))
+        }
+        Err(e) => {
+            error!("{} failed for {} {}: {}", tool_name, kind, id, e);
+            return Ok(misp_tool_error(tool_name, format!("Failed to get {} {}: {}", kind, id, e), &e));
+        }
+    };
+    let json = value
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+    Ok(ToolResult::text(json))
+}
+
+/// Compact taxonomy listing, optionally keeping only one enabled state.
+///
+/// A missing `enabled` flag counts as disabled.
+fn taxonomy_summaries(taxonomies: &GetTaxonomiesResponse, enabled: Option<bool>) -> Vec<serde_json::Value> {
+    taxonomies
+        .iter()
+        .map(|entry| &entry.taxonomy)
+        .filter(|taxonomy| enabled.is_none_or(|wanted| taxonomy.enabled.unwrap_or(false) == wanted))
+        .map(|taxonomy| serde_json::json!({
+            "id": taxonomy.id,
+            "namespace": taxonomy.namespace,
+            "description": taxonomy.description,
+            "version": taxonomy.version,
+            "enabled": taxonomy.enabled.unwrap_or(false),
+        }))
+        .collect()
+}
+
+/// Most entries each breakdown of a `summarize: true` search lists.
+const SUMMARY_BREAKDOWN_LIMIT: usize = 20;
+
+/// Breakdown of counts, largest first and capped at [`SUMMARY_BREAKDOWN_LIMIT`],
+/// with the number of distinct keys so truncation is visible.
+fn count_breakdown(counts: BTreeMap<String, usize>) -> serde_json::Value {
+    let distinct = counts.len();
+    let mut top: Vec<(String, usize)> = counts.into_iter().collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top.truncate(SUMMARY_BREAKDOWN_LIMIT);
+    let top: Vec<serde_json::Value> = top
+        .into_iter()
+        .map(|(name, count)| serde_json::json!({ "name": name, "count": count }))
+        .collect();
+    serde_json::json!({ "distinct": distinct, "top": top })
+}
+
+/// Name of an event's creator organisation, falling back to its id.
+fn event_org_name(event: &misp_types::Event) -> String {
+    event
+        .orgc
+        .as_ref()
+        .and_then(|org| org.name.clone())
+        .or_else(|| event.orgc_id.clone())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// `{from, to}` over the given dates, or `null` when there are none.
+fn date_range<'a>(dates: impl Iterator<Item = &'a str>) -> serde_json::Value {
+    let mut range: Option<(&str, &str)> = None;
+    for date in dates.filter(|date| !date.is_empty()) {
+        range = Some(match range {
+            Some((from, to)) => (from.min(date), to.max(date)),
+            None => (date, date),
+        });
+    }
+    match range {
+        Some((from, to)) => serde_json::json!({ "from": from, "to": to }),
+        None => serde_json::Value::Null,
+    }
+}
+
+/// Aggregates of an events search, for `summarize: true`: event count,
+/// creator organisations, event tags, attribute types (object attributes
+/// included) and the range of event dates.
+fn summarize_events(events: &[misp_types::EventWrapper]) -> serde_json::Value {
+    let mut orgs = BTreeMap::new();
+    let mut tags = BTreeMap::new();
+    let mut types = BTreeMap::new();
+    for event in events.iter().map(|wrapper| &wrapper.event) {
+        *orgs.entry(event_org_name(event)).or_default() += 1;
+        for name in event.tag.iter().filter_map(|tag| tag.name.clone()) {
+            *tags.entry(name).or_default() += 1;
+        }
+        let attributes = event
+            .attribute
+            .iter()
+            .chain(event.object.iter().flat_map(|object| object.attributes.iter().flatten()));
+        for attribute in attributes.filter(|a| !a.deleted) {
+            *types.entry(attribute.attribute_type.clone()).or_default() += 1;
+        }
+    }
+    serde_json::json!({
+        "total": events.len(),
+        "by_org": count_breakdown(orgs),
+        "by_tag": count_breakdown(tags),
+        "by_attribute_type": count_breakdown(types),
+        "date_range": date_range(events.iter().filter_map(|wrapper| wrapper.event.date.as_deref())),
+    })
+}
+
+/// Aggregates of an attributes search, for `summarize: true`: attribute and
+/// event counts, organisations of the owning events, attribute tags, types
+/// and the range of attribute modification dates.
+fn summarize_attributes(attributes: &[misp_types::Attribute]) -> serde_json::Value {
+    let mut orgs = BTreeMap::new();
+    let mut tags = BTreeMap::new();
+    let mut types = BTreeMap::new();
+    let mut events = HashSet::new();
+    let mut dates = Vec::new();
+    for attribute in attributes {
+        events.insert(attribute.event_id.as_str());
+        let org = attribute.event.as_ref().map(event_org_name).unwrap_or_else(|| "unknown".to_string());
+        *orgs.entry(org).or_default() += 1;
+        for name in attribute.tag.iter().flatten().filter_map(|tag| tag.name.clone()) {
+            *tags.entry(name).or_default() += 1;
+        }
+        *types.entry(attribute.attribute_type.clone()).or_default() += 1;
+        let modified = attribute
+            .timestamp
+            .as_deref()
+            .and_then(|timestamp| timestamp.parse::<i64>().ok())
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0));
+        if let Some(modified) = modified {
+            dates.push(modified.format("%Y-%m-%d").to_string());
+        }
+    }
+    serde_json::json!({
+        "total": attributes.len(),
+        "events": events.len(),
+        "by_org": count_breakdown(orgs),
+        "by_tag": count_breakdown(tags),
+        "by_type": count_breakdown(types),
+        "date_range": date_range(dates.iter().map(String::as_str)),
+    })
+}
+
+/// In strict mode, reject request fields that `request_type` does not define.
+///
+/// `argument` names the tool argument the request came from, for the error.
+fn check_strict_fields(request_type: &str, argument: &str, request: &serde_json::Value) -> mcp_core::McpResult<()> {
+    let unknown = misp_types::schema::unknown_fields(request_type, request).unwrap_or_default();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(mcp_core::McpError::invalid_argument(
+        argument,
+        format!("Unknown field(s) for {} (strict mode): {}", request_type, unknown.join(", ")),
+    ))
+}
+
+/// Split a machine tag `namespace:predicate="value"` into its parts.
+fn split_machine_tag(name: &str) -> Option<(&str, &str, Option<&str>)> {
+    let (namespace, rest) = name.split_once(':')?;
+    match rest.split_once('=') {
+        Some((predicate, value)) => Some((namespace, predicate, Some(value.trim_matches('"')))),
+        None => Some((namespace, rest, None)),
+    }
+}
+
+/// Values of an attribute to check against warninglists: composite values
+/// ("domain|ip") are checked whole and part by part.
+fn warninglist_values(attribute: &misp_types::Attribute) -> Vec<String> {
+    let mut values = vec![attribute.value.clone()];
+    if attribute.attribute_type.contains('|') {
+        values.extend(attribute.value.split('|').map(str::to_string));
+    }
+    values
+}
+
+/// Warninglists an attribute hits, each listed once.
+fn warninglist_hits<'a>(
+    attribute: &misp_types::Attribute,
+    hits: &'a misp_types::CheckWarninglistValuesResponse,
+) -> Vec<&'a misp_types::WarninglistHit> {
+    let mut seen = HashSet::new();
+    warninglist_values(attribute)
+        .iter()
+        .filter_map(|value| hits.get(value))
+        .flatten()
+        .filter(|hit| seen.insert(hit.name.as_str()))
+        .collect()
+}
+
+/// MISP hash types `export_detection_values` lists under `hashes`.
+const DETECTION_HASH_TYPES: &[&str] = &[
+    "md5", "sha1", "sha224", "sha256", "sha384", "sha512", "ssdeep", "imphash", "tlsh", "authentihash", "pehash",
+];
+
+/// Detection group and value of each part of an attribute.
+///
+/// Composite attributes ("filename|sha256", "ip-dst|port") are split so each
+/// part lands in its own group; ports are dropped. Groups are `ips`,
+/// `domains`, `urls`, `emails`, `filenames`, a hash type, or the MISP type
+/// for anything else.
+fn detection_values(attribute: &misp_types::Attribute) -> Vec<(&str, &str)> {
+    let types = attribute.attribute_type.split('|');
+    let values: Vec<&str> = if attribute.attribute_type.contains('|') {
+        attribute.value.split('|').collect()
+    } else {
+        vec![attribute.value.as_str()]
+    };
+    types
+        .zip(values)
+        .filter(|(_, value)| !value.trim().is_empty())
+        .filter_map(|(part_type, value)| {
+            let group = match part_type {
+                "ip-src" | "ip-dst" | "ip" => "ips",
+                "domain" | "hostname" => "domains",
+                "url" => "urls",
+                "email" | "email-src" | "email-dst" => "emails",
+                "filename" => "filenames",
+                "port" => return None,
+                other => other,
+            };
+            Some((group, value.trim()))
+        })
+        .collect()
+}
+
+/// Confidence levels `resolve_confidence_tag` understands, most confident
+/// first, with the words of a label that name them.
+const CONFIDENCE_LEVELS: &[(&str, &[&str])] = &[
+    ("very-high", &["very high", "very-high", "certain", "confirmed", "complete", "completely"]),
+    ("high", &["high", "usually", "likely", "strong"]),
+    ("moderate", &["moderate", "medium", "fair", "fairly"]),
+    ("low", &["low", "rarely", "doubtful", "weak"]),
+    ("none", &["none", "no", "unconfident", "improbable"]),
+    ("unknown", &["unknown", "cannot be evaluated", "cannot be judged", "unrated"]),
+];
+
+/// Machine tag per confidence level in each taxonomy `resolve_confidence_tag`
+/// maps to, in order of preference.
+const CONFIDENCE_TAGS: &[(&str, &[(&str, &str)])] = &[
+    ("misp", &[
+        ("very-high", "misp:confidence-level=\"completely-confident\""),
+        ("high", "misp:confidence-level=\"usually-confident\""),
+        ("moderate", "misp:confidence-level=\"fairly-confident\""),
+        ("low", "misp:confidence-level=\"rarely-confident\""),
+        ("none", "misp:confidence-level=\"unconfident\""),
+        ("unknown", "misp:confidence-level=\"confidence-cannot-be-evaluated\""),
+    ]),
+    ("estimative-language", &[
+        ("very-high", "estimative-language:confidence-in-analytic-judgment=\"high\""),
+        ("high", "estimative-language:confidence-in-analytic-judgment=\"high\""),
+        ("moderate", "estimative-language:confidence-in-analytic-judgment=\"moderate\""),
+        ("low", "estimative-language:confidence-in-analytic-judgment=\"low\""),
+    ]),
+    ("admiralty-scale", &[
+        ("very-high", "admiralty-scale:information-credibility=\"1\""),
+        ("high", "admiralty-scale:information-credibility=\"2\""),
+        ("moderate", "admiralty-scale:information-credibility=\"3\""),
+        ("low", "admiralty-scale:information-credibility=\"4\""),
+        ("none", "admiralty-scale:information-credibility=\"5\""),
+        ("unknown", "admiralty-scale:information-credibility=\"6\""),
+    ]),
+];
+
+/// Confidence level a human label such as "high confidence" names.
+///
+/// The longest matching phrase wins, so "very high" is not read as "high".
+fn confidence_level(label: &str) -> Option<&'static str> {
+    let label = label.to_ascii_lowercase().replace(['_', '-'], " ");
+    let words: Vec<&str> = label.split_whitespace().collect();
+    let label = format!(" {} ", words.join(" "));
+    CONFIDENCE_LEVELS
+        .iter()
+        .flat_map(|(level, phrases)| phrases.iter().map(move |phrase| (*level, phrase.replace('-', " "))))
+        .filter(|(_, phrase)| label.contains(&format!(" {} ", phrase)))
+        .max_by_key(|(_, phrase)| phrase.len())
+        .map(|(level, _)| level)
+}
+
+/// Namespaces `auto_tag_attribute` has a rule for.
+const AUTO_TAG_NAMESPACES: &[&str] = &["tlp"];
+
+/// Predicates to suggest for an attribute shared at `distribution`, most
+/// preferred first, or `None` for a namespace without a rule.
+///
+/// TLP follows the sharing scope: anything kept to the organisation or a
+/// sharing group is amber, community sharing is green and sharing with
+/// all communities is clear (`white` on taxonomies older than TLP 2.0).
+fn auto_tag_predicates(namespace: &str, distribution: misp_types::Distribution) -> Option<&'static [&'static str]> {
+    use misp_types::Distribution::*;
+    match namespace {
+        "tlp" => Some(match distribution {
+            YourOrganisationOnly | SharingGroup | InheritEvent => &["amber"],
+            ThisCommunityOnly | ConnectedCommunities => &["green"],
+            AllCommunities => &["clear", "white"],
+        }),
+        _ => None,
+    }
+}
+
+/// Add a tool unless the tools config leaves it out.
+fn add_permitted_tool(server: &mut Server, tools: &ToolsConfig, tool: Tool) {
+    if tools.permits(&tool.definition.name) {
+        server.add_tool(tool);
+    } else {
+        debug!("Tool {} disabled by the tools config", tool.definition.name);
+    }
+}
+
+/// Register `misp_raw_request`, the opt-in escape hatch for endpoints no
+/// other tool wraps.
+fn register_raw_api_tool(server: &mut Server, clients: MispClients, tools: &ToolsConfig) {
+    add_permitted_tool(server, tools, Tool::new(
+        "misp_raw_request",
+        "Call any MISP endpoint directly and return its JSON. Arguments: method (GET or POST), path (e.g. /servers/getPyMISPVersion), optional body (JSON, POST only)",
+        move |mut input: ToolInput| {
+            let clients = clients.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let method: String = input.get_argument("method")?;
+                let method = match method.to_ascii_uppercase().as_str() {
+                    "GET" => reqwest::Method::GET,
+                    "POST" => reqwest::Method::POST,
+                    _ => return Err(mcp_core::McpError::invalid_argument("method", format!("must be GET or POST, got '{}'", method))),
+                };
+                let path: String = input.get_argument("path")?;
+                if !path.starts_with('/') {
+                    return Err(mcp_core::McpError::invalid_argument("path", format!("must start with '/', got '{}'", path)));
+                }
+                let body = input.arguments.get("body");
+                match client.raw_request(&method, &path, body).await {
+                    Ok(value) => {
+                        let json = serde_json::to_string_pretty(&value)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("misp_raw_request failed for {} {}: {}", method, path, e);
+                        Ok(misp_tool_error("misp_raw_request", format!("{} {} failed: {}", method, path, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+}
+
+/// Register `sync_events_since`, which needs a cursor file to remember
+/// where the previous sync stopped.
+fn register_sync_tool(server: &mut Server, clients: MispClients, tools: &ToolsConfig, cursors: CursorStore) {
+    let cursors = std::sync::Arc::new(cursors);
+    // Tool 75: sync_events_since
+    add_permitted_tool(server, tools, Tool::new(
+        "sync_events_since",
+        "Incremental sync: list the events modified since the previous call with the same key, then move that key's cursor past them. Arguments: optional key (default \"default\"; one cursor per key and instance), initial_days (default 1: how far back the first sync of a key looks)",
+        move |mut input: ToolInput| {
+            let clients = clients.clone();
+            let cursors = cursors.clone();
+            Box::pin(async move {
+                let instance = input
+                    .arguments
+                    .get("target")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or(PRIMARY_INSTANCE)
+                    .to_string();
+                let client = clients.select(&mut input)?;
+                let key = input.get_optional_argument::<String>("key")?.unwrap_or_else(|| "default".to_string());
+                // Primary cursors keep their bare key, as before instances existed
+                let cursor_key = if instance == PRIMARY_INSTANCE {
+                    key.clone()
+                } else {
+                    format!("{}:{}", instance, key)
+                };
+                let initial_days = input.get_optional_argument::<u32>("initial_days")?.unwrap_or(1);
+                let cursor = cursors
+                    .get(&cursor_key)
+                    .await
+                    .map_err(|e| mcp_core::McpError::internal_error(e.to_string()))?;
+                // MISP's timestamp filter is inclusive, so start just past the cursor
+                let since = match cursor {
+                    Some(cursor) => cursor + 1,
+                    None => (chrono::Utc::now() - chrono::Duration::days(i64::from(initial_days))).timestamp(),
+                };
+                let params = EventsRestSearchRequest {
+                    timestamp: Some(since.to_string()),
+                    metadata: Some(true),
+                    ..Default::default()
+                };
+                let mut events: Vec<misp_types::Event> = match client.events_rest_search(&params).await {
+                    Ok(response) => response.response.into_iter().map(|wrapper| wrapper.event).collect(),
+                    Err(e) => {
+                        error!("sync_events_since failed for key {} on {}: {}", key, instance, e);
+                        return Ok(misp_tool_error("sync_events_since", format!("Failed to search events modified since {}: {}", since, e), &e));
+                    }
+                };
+                let modified_at = |event: &misp_types::Event| {
+                    event.timestamp.as_deref().and_then(|ts| ts.parse::<i64>().ok()).unwrap_or(0)
+                };
+                events.sort_by_key(modified_at);
+
+                // Only advance once the events are in hand
+                let newest = events.iter().map(modified_at).max();
+                if let Some(newest) = newest {
+                    cursors
+                        .advance(&cursor_key, newest)
+                        .await
+                        .map_err(|e| mcp_core::McpError::internal_error(e.to_string()))?;
+                }
+                let to_rfc3339 = |timestamp: i64| {
+                    chrono::DateTime::from_timestamp(timestamp, 0).map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+                };
+                let entries: Vec<serde_json::Value> = events
+                    .iter()
+                    .map(|event| serde_json::json!({
+                        "id": event.id,
+                        "uuid": event.uuid,
+                        "info": event.info,
+                        "date": event.date,
+                        "org": event.orgc.as_ref().and_then(|org| org.name.clone()),
+                        "modified": to_rfc3339(modified_at(event)),
+                        "published": event.published,
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "instance": instance,
+                    "key": key,
+                    "since": to_rfc3339(since),
+                    "cursor": to_rfc3339(newest.or(cursor).unwrap_or(since - 1)),
+                    "count": entries.len(),
+                    "events": entries,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    // One sync at a time, so two calls never return the same events
+    ).with_concurrency_limit(1));
+}
+
 /// Register all MISP tools with the MCP server.
-async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow::Result<()> {
+async fn register_misp_tools(server: &mut Server, clients: MispClients, tools: &ToolsConfig) -> anyhow::Result<()> {
     info!("Registering MISP tools...");
 
     // Clone client for each tool handler
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_users",
         "Retrieve all users from MISP",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.get_users().await {
                     Ok(users) => {
                         let json = serde_json::to_string_pretty(&users)
@@ -241,21 +1349,43 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_users failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get users: {}", e)))
+                        Ok(misp_tool_error("get_users", format!("Failed to get users: {}", e), &e))
                     }
                 }
             })
         }
-    ));
+    ).with_output_schema(serde_json::json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "required": ["User", "Role", "Organisation"],
+            "properties": {
+                "User": {
+                    "type": "object",
+                    "required": ["id", "email", "org_id", "role_id"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "email": { "type": "string" },
+                        "org_id": { "type": "string" },
+                        "role_id": { "type": "string" },
+                        "disabled": { "type": "boolean" }
+                    }
+                },
+                "Role": { "type": "object", "required": ["id", "name"] },
+                "Organisation": { "type": "object" }
+            }
+        }
+    })));
 
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_user",
         "Retrieve a specific user by ID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let user_id: String = input.get_argument("user_id")?;
+                let client = clients.select(&mut input)?;
+                let user_id: String = input.get_id("user_id")?;
                 
                 match client.get_user_by_id(&user_id).await {
                     Ok(user) => {
@@ -265,68 +1395,278 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_user failed for user_id {}: {}", user_id, e);
-                        Ok(ToolResult::error(format!("Failed to get user {}: {}", user_id, e)))
+                        Ok(misp_tool_error("get_user", format!("Failed to get user {}: {}", user_id, e), &e))
                     }
                 }
             })
         }
     ));
 
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_galaxies",
-        "Retrieve all galaxies from MISP",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 66: get_org_users
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_org_users",
+        "List the users of an organisation. Arguments: org_id",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                match client.get_galaxies().await {
-                    Ok(galaxies) => {
-                        let json = serde_json::to_string_pretty(&galaxies)
+                let client = clients.select(&mut input)?;
+                let org_id: String = input.get_id("org_id")?;
+                match client.get_users_by_org(&org_id).await {
+                    Ok(users) => {
+                        let json = serde_json::to_string_pretty(&users)
                             .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("get_galaxies failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get galaxies: {}", e)))
+                        error!("get_org_users failed for org_id {}: {}", org_id, e);
+                        Ok(misp_tool_error("get_org_users", format!("Failed to get users of organisation {}: {}", org_id, e), &e))
                     }
                 }
             })
         }
     ));
 
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_galaxy",
-        "Retrieve a specific galaxy by ID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 74: get_roles
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_roles",
+        "List the MISP roles with the permissions each one grants (e.g. perm_admin, perm_publish), plus whether it is the default role",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let galaxy_id: String = input.get_argument("galaxy_id")?;
-                
-                match client.get_galaxy_by_id(&galaxy_id).await {
-                    Ok(galaxy) => {
-                        let json = serde_json::to_string_pretty(&galaxy)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                let client = clients.select(&mut input)?;
+                match client.get_roles().await {
+                    Ok(roles) => {
+                        let roles: Vec<serde_json::Value> = roles
+                            .iter()
+                            .map(|entry| serde_json::json!({
+                                "id": entry.role.id,
+                                "name": entry.role.name,
+                                "default_role": entry.role.default_role.unwrap_or(false),
+                                "permissions": entry.role.granted_permissions(),
+                            }))
+                            .collect();
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "count": roles.len(),
+                            "roles": roles,
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("get_galaxy failed for galaxy_id {}: {}", galaxy_id, e);
-                        Ok(ToolResult::error(format!("Failed to get galaxy {}: {}", galaxy_id, e)))
+                        error!("get_roles failed: {}", e);
+                        Ok(misp_tool_error("get_roles", format!("Failed to get roles: {}", e), &e))
                     }
                 }
             })
         }
     ));
 
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "search_galaxies",
-        "Search MISP galaxies by value filter",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 64: whoami
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "whoami",
+        "Identify the MISP user the API key belongs to: email, organisation and role. Needs no admin rights",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let value: String = input.get_argument("value")?;
-                
+                let client = clients.select(&mut input)?;
+                match client.get_current_user().await {
+                    Ok(me) => {
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "id": me.user.id,
+                            "email": me.user.email,
+                            "org": { "id": me.organisation.id, "name": me.organisation.name },
+                            "role": { "id": me.role.id, "name": me.role.name },
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("whoami failed: {}", e);
+                        Ok(misp_tool_error("whoami", format!("Failed to get the current user: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 81: check_permissions
+    let clients_clone = clients.clone();
+    let catalog = server.tool_catalog();
+    add_permitted_tool(server, tools, Tool::new(
+        "check_permissions",
+        "Check the API key's role against the write tools this server exposes: who the key belongs to, the permissions its role grants, and which registered tools it lacks permissions for",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            let catalog = catalog.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let registered: HashSet<String> = catalog.list().into_iter().map(|tool| tool.name).collect();
+                match permissions::probe(&client, &registered).await {
+                    Ok(report) => {
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "sufficient": report.is_sufficient(),
+                            "report": report,
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("check_permissions failed: {}", e);
+                        Ok(misp_tool_error("check_permissions", format!("Failed to check the API key's permissions: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 77: list_instances
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "list_instances",
+        "List the MISP instances this server can call. Every MISP tool takes an optional target argument naming one of them (default: primary)",
+        move |_input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let instances: Vec<serde_json::Value> = clients
+                    .names()
+                    .into_iter()
+                    .filter_map(|name| clients.get(name).map(|client| (name, client)))
+                    .map(|(name, client)| serde_json::json!({
+                        "name": name,
+                        "url": client.base_url(),
+                        "primary": name == instances::PRIMARY_INSTANCE,
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&instances)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 44: get_user_settings
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_user_settings",
+        "Get a user's settings (dashboard access, default restSearch parameters, hidden columns, ...) in a consistent object shape",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let user_id: String = input.get_id("user_id")?;
+                
+                match client.get_user_by_id(&user_id).await {
+                    Ok(user) => {
+                        let json = serde_json::to_string_pretty(&user.user_setting.normalized())
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_user_settings failed for user_id {}: {}", user_id, e);
+                        Ok(misp_tool_error("get_user_settings", format!("Failed to get settings for user {}: {}", user_id, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_galaxies",
+        "Retrieve all galaxies from MISP",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                match client.get_galaxies().await {
+                    Ok(galaxies) => {
+                        let json = serde_json::to_string_pretty(&galaxies)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_galaxies failed: {}", e);
+                        Ok(misp_tool_error("get_galaxies", format!("Failed to get galaxies: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 56: list_galaxies_by_type
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "list_galaxies_by_type",
+        "List galaxies of one type (e.g. mitre-attack), returning id, name and namespace. Arguments: type",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let galaxy_type: String = input.get_argument("type")?;
+                match client.get_galaxies().await {
+                    Ok(galaxies) => {
+                        // MISP has no type filter on /galaxies, so filter here
+                        let matching: Vec<serde_json::Value> = galaxies
+                            .iter()
+                            .map(|entry| &entry.galaxy)
+                            .filter(|galaxy| galaxy.galaxy_type.eq_ignore_ascii_case(&galaxy_type))
+                            .map(|galaxy| serde_json::json!({
+                                "id": galaxy.id,
+                                "name": galaxy.name,
+                                "namespace": galaxy.namespace,
+                            }))
+                            .collect();
+                        let json = serde_json::to_string_pretty(&matching)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("list_galaxies_by_type failed for type {}: {}", galaxy_type, e);
+                        Ok(misp_tool_error("list_galaxies_by_type", format!("Failed to list galaxies of type {}: {}", galaxy_type, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_galaxy",
+        "Retrieve a specific galaxy by ID from MISP",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let galaxy_id: String = input.get_id("galaxy_id")?;
+                
+                match client.get_galaxy_by_id(&galaxy_id).await {
+                    Ok(galaxy) => {
+                        let json = serde_json::to_string_pretty(&galaxy)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_galaxy failed for galaxy_id {}: {}", galaxy_id, e);
+                        Ok(misp_tool_error("get_galaxy", format!("Failed to get galaxy {}: {}", galaxy_id, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "search_galaxies",
+        "Search MISP galaxies by value filter",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let value: String = input.get_argument("value")?;
+                
                 match client.search_galaxies(&value).await {
                     Ok(galaxies) => {
                         let json = serde_json::to_string_pretty(&galaxies)
@@ -335,7 +1675,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("search_galaxies failed for value '{}': {}", value, e);
-                        Ok(ToolResult::error(format!("Failed to search galaxies with value '{}': {}", value, e)))
+                        Ok(misp_tool_error("search_galaxies", format!("Failed to search galaxies with value '{}': {}", value, e), &e))
                     }
                 }
             })
@@ -343,14 +1683,15 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 6: get_galaxy_clusters
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_galaxy_clusters",
         "Get galaxy clusters for a specific galaxy by ID",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let galaxy_id: String = input.get_argument("galaxy_id")?;
+                let client = clients.select(&mut input)?;
+                let galaxy_id: String = input.get_id("galaxy_id")?;
                 
                 match client.get_galaxy_clusters(&galaxy_id).await {
                     Ok(clusters) => {
@@ -360,47 +1701,121 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_galaxy_clusters failed for galaxy_id '{}': {}", galaxy_id, e);
-                        Ok(ToolResult::error(format!("Failed to get galaxy clusters for galaxy_id '{}': {}", galaxy_id, e)))
+                        Ok(misp_tool_error("get_galaxy_clusters", format!("Failed to get galaxy clusters for galaxy_id '{}': {}", galaxy_id, e), &e))
                     }
                 }
             })
         }
     ));
 
+    // Tool 91: galaxy_cluster_counts
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "galaxy_cluster_counts",
+        "Number of clusters in each galaxy, most populated first, to see which galaxies are in use. Arguments: optional galaxy_ids (array of galaxy IDs; default every galaxy). Clusters are fetched a few galaxies at a time",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let wanted: Option<HashSet<String>> = match input.arguments.get("galaxy_ids") {
+                    Some(serde_json::Value::Null) | None => None,
+                    Some(_) => Some(input.get_ids("galaxy_ids")?.into_iter().collect()),
+                };
+                let galaxies = match client.get_galaxies().await {
+                    Ok(galaxies) => galaxies,
+                    Err(e) => {
+                        error!("galaxy_cluster_counts failed to list galaxies: {}", e);
+                        return Ok(misp_tool_error("galaxy_cluster_counts", format!("Failed to get galaxies: {}", e), &e));
+                    }
+                };
+                let galaxies: Vec<misp_types::Galaxy> = galaxies
+                    .into_iter()
+                    .map(|entry| entry.galaxy)
+                    .filter(|galaxy| wanted.as_ref().is_none_or(|wanted| wanted.contains(&galaxy.id)))
+                    .collect();
+                let mut unknown: Vec<String> = wanted
+                    .into_iter()
+                    .flatten()
+                    .filter(|id| !galaxies.iter().any(|galaxy| &galaxy.id == id))
+                    .collect();
+                unknown.sort();
+
+                let fetches = galaxies.into_iter().map(|galaxy| {
+                    let client = client.clone();
+                    async move {
+                        let result = client.get_galaxy_clusters(&galaxy.id).await;
+                        (galaxy, result)
+                    }
+                });
+                let results: Vec<_> = futures_util::stream::iter(fetches)
+                    .buffer_unordered(BULK_FETCH_CONCURRENCY)
+                    .collect()
+                    .await;
+
+                let mut total = 0;
+                let mut counts = Vec::new();
+                let mut failed = Vec::new();
+                for (galaxy, result) in results {
+                    match result {
+                        Ok(clusters) => {
+                            total += clusters.len();
+                            counts.push((clusters.len(), galaxy));
+                        }
+                        Err(e) => {
+                            warn!("galaxy_cluster_counts failed for galaxy {}: {}", galaxy.id, e);
+                            failed.push(serde_json::json!({ "id": galaxy.id, "name": galaxy.name, "error": e.to_string() }));
+                        }
+                    }
+                }
+                counts.sort_by(|(a, galaxy_a), (b, galaxy_b)| b.cmp(a).then_with(|| galaxy_a.name.cmp(&galaxy_b.name)));
+                let counts: Vec<serde_json::Value> = counts
+                    .into_iter()
+                    .map(|(clusters, galaxy)| serde_json::json!({
+                        "id": galaxy.id,
+                        "name": galaxy.name,
+                        "type": galaxy.galaxy_type,
+                        "clusters": clusters,
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "galaxies": counts.len(),
+                    "total_clusters": total,
+                    "counts": counts,
+                    "failed": failed,
+                    "unknown_galaxy_ids": unknown,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
     // Tool 7: get_galaxy_cluster_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_galaxy_cluster_by_id",
         "Get detailed information about a specific galaxy cluster by ID",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let galaxy_cluster_id: String = input.get_argument("galaxy_cluster_id")?;
+                let client = clients.select(&mut input)?;
+                let galaxy_cluster_id: String = input.get_id("galaxy_cluster_id")?;
                 
-                match client.get_galaxy_cluster_by_id(&galaxy_cluster_id).await {
-                    Ok(cluster) => {
-                        let json = serde_json::to_string_pretty(&cluster)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
-                    }
-                    Err(e) => {
-                        error!("get_galaxy_cluster_by_id failed for galaxy_cluster_id '{}': {}", galaxy_cluster_id, e);
-                        Ok(ToolResult::error(format!("Failed to get galaxy cluster for galaxy_cluster_id '{}': {}", galaxy_cluster_id, e)))
-                    }
-                }
+                get_by_id_result("get_galaxy_cluster_by_id", "galaxy cluster", &galaxy_cluster_id, client.get_galaxy_cluster_by_id_lenient(&galaxy_cluster_id).await)
             })
         }
     ));
 
     // Tool 8: search_galaxy_clusters
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "search_galaxy_clusters",
         "Search galaxy clusters within a specific galaxy using search criteria",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let galaxy_id: String = input.get_argument("galaxy_id")?;
+                let client = clients.select(&mut input)?;
+                let galaxy_id: String = input.get_id("galaxy_id")?;
                 let context: String = input.get_argument("context")?;
                 let searchall: String = input.get_argument("searchall")?;
                 
@@ -412,21 +1827,290 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("search_galaxy_clusters failed for galaxy_id '{}', context '{}', searchall '{}': {}", galaxy_id, context, searchall, e);
-                        Ok(ToolResult::error(format!("Failed to search galaxy clusters: {}", e)))
+                        Ok(misp_tool_error("search_galaxy_clusters", format!("Failed to search galaxy clusters: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 69: attack_technique
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "attack_technique",
+        "Look up a MITRE ATT&CK technique in the mitre-attack-pattern galaxy and return its cluster: name, description, kill chain phases, platforms and tag. Arguments: technique (ID such as T1059 or T1059.001, or technique name)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let technique: String = input.get_argument("technique")?;
+                let technique = technique.trim().to_string();
+                if technique.is_empty() {
+                    return Err(mcp_core::McpError::invalid_argument("technique", "technique must not be empty"));
+                }
+                let galaxy_id = match client.attack_pattern_galaxy_id().await {
+                    Ok(id) => id.to_string(),
+                    Err(e) => {
+                        error!("attack_technique failed to find the ATT&CK galaxy: {}", e);
+                        return Ok(misp_tool_error("attack_technique", format!("Failed to find the MITRE ATT&CK galaxy: {}", e), &e));
+                    }
+                };
+                let clusters = match client.search_galaxy_clusters(&galaxy_id, "all", &technique).await {
+                    Ok(clusters) => clusters,
+                    Err(e) => {
+                        error!("attack_technique failed for technique {}: {}", technique, e);
+                        return Ok(misp_tool_error("attack_technique", format!("Failed to search ATT&CK techniques: {}", e), &e));
+                    }
+                };
+
+                let Some(entry) = clusters.iter().find(|entry| attack_cluster_matches(&entry.galaxy_cluster.value, &technique)) else {
+                    let candidates: Vec<&str> = clusters.iter().map(|entry| entry.galaxy_cluster.value.as_str()).collect();
+                    let json = serde_json::to_string_pretty(&serde_json::json!({
+                        "found": false,
+                        "technique": technique,
+                        "candidates": candidates,
+                    }))
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                    return Ok(ToolResult::text(json));
+                };
+
+                // The cluster index leaves out elements (kill chain, platforms)
+                let cluster = match client.get_galaxy_cluster_by_id(&entry.galaxy_cluster.id).await {
+                    Ok(response) => response.galaxy_cluster,
+                    Err(e) => {
+                        error!("attack_technique failed for cluster {}: {}", entry.galaxy_cluster.id, e);
+                        return Ok(misp_tool_error("attack_technique", format!("Failed to get galaxy cluster {}: {}", entry.galaxy_cluster.id, e), &e));
+                    }
+                };
+                let elements = cluster.galaxy_element.as_deref().unwrap_or_default();
+                let element_values = |key: &str| -> Vec<&str> {
+                    elements.iter().filter(|element| element.key == key).map(|element| element.value.as_str()).collect()
+                };
+                let (name, technique_id) = match cluster.value.rsplit_once(" - ") {
+                    Some((name, id)) => (name, Some(id)),
+                    None => (cluster.value.as_str(), element_values("external_id").first().copied()),
+                };
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "found": true,
+                    "technique_id": technique_id,
+                    "name": name,
+                    "id": cluster.id,
+                    "uuid": cluster.uuid,
+                    "value": cluster.value,
+                    "description": cluster.description,
+                    "kill_chain_phases": element_values("kill_chain"),
+                    "platforms": element_values("mitre_platforms"),
+                    "tag_name": cluster.tag_name,
+                    "galaxy_id": cluster.galaxy_id,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 88: events_by_cluster
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "events_by_cluster",
+        "Find the events tagged with a galaxy cluster, e.g. every event using ATT&CK technique T1566. Arguments: tag (full cluster tag, e.g. misp-galaxy:mitre-attack-pattern=\"Phishing - T1566\") or cluster (cluster value, or an ATT&CK technique ID which is looked up) with optional galaxy (galaxy type, default mitre-attack-pattern); optional limit (default 100), published, from and to (YYYY-MM-DD). Returns the tag searched and event id, uuid, info, date, creator org and threat level",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let tag = match input.get_optional_argument::<String>("tag")? {
+                    Some(tag) => tag.trim().to_string(),
+                    None => {
+                        let cluster: String = input.get_argument("cluster")?;
+                        let cluster = cluster.trim().to_string();
+                        let galaxy: String = input.get_optional_argument("galaxy")?.unwrap_or_else(|| "mitre-attack-pattern".to_string());
+                        if galaxy == "mitre-attack-pattern" && is_attack_technique_id(&cluster) {
+                            // Tags carry the full "Name - T1566" value, so find the cluster for the ID
+                            let galaxy_id = match client.attack_pattern_galaxy_id().await {
+                                Ok(id) => id.to_string(),
+                                Err(e) => {
+                                    error!("events_by_cluster failed to find the ATT&CK galaxy: {}", e);
+                                    return Ok(misp_tool_error("events_by_cluster", format!("Failed to find the MITRE ATT&CK galaxy: {}", e), &e));
+                                }
+                            };
+                            let clusters = match client.search_galaxy_clusters(&galaxy_id, "all", &cluster).await {
+                                Ok(clusters) => clusters,
+                                Err(e) => {
+                                    error!("events_by_cluster failed for technique {}: {}", cluster, e);
+                                    return Ok(misp_tool_error("events_by_cluster", format!("Failed to search ATT&CK techniques: {}", e), &e));
+                                }
+                            };
+                            match clusters.iter().find(|entry| attack_cluster_matches(&entry.galaxy_cluster.value, &cluster)) {
+                                Some(entry) => entry.galaxy_cluster.tag_name.clone(),
+                                None => {
+                                    let json = serde_json::to_string_pretty(&serde_json::json!({
+                                        "found": false,
+                                        "technique": cluster,
+                                    }))
+                                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                                    return Ok(ToolResult::text(json));
+                                }
+                            }
+                        } else {
+                            format!("misp-galaxy:{}=\"{}\"", galaxy, cluster)
+                        }
+                    }
+                };
+                if tag.is_empty() {
+                    return Err(mcp_core::McpError::invalid_argument("tag", "tag must not be empty"));
+                }
+
+                let params = EventsRestSearchRequest {
+                    tags: Some(vec![tag.clone()]),
+                    metadata: Some(true),
+                    limit: Some(input.get_optional_argument("limit")?.unwrap_or(100)),
+                    published: input.get_optional_argument("published")?,
+                    from: input.get_optional_argument("from")?,
+                    to: input.get_optional_argument("to")?,
+                    ..Default::default()
+                };
+                let response = match client.events_rest_search(&params).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        error!("events_by_cluster failed for tag {}: {}", tag, e);
+                        return Ok(misp_tool_error("events_by_cluster", format!("Failed to search events tagged {}: {}", tag, e), &e));
+                    }
+                };
+                let events: Vec<serde_json::Value> = response
+                    .response
+                    .iter()
+                    .map(|wrapper| &wrapper.event)
+                    .map(|event| serde_json::json!({
+                        "id": event.id,
+                        "uuid": event.uuid,
+                        "info": event.info,
+                        "date": event.date,
+                        "orgc": event.orgc.as_ref().and_then(|org| org.name.as_deref()),
+                        "threat_level_id": event.threat_level_id,
+                        "published": event.published,
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "found": true,
+                    "tag": tag,
+                    "count": events.len(),
+                    "events": events,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    // Tool 76: cluster_relationships
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "cluster_relationships",
+        "Get a galaxy cluster's relationships as a graph: outbound relations (this cluster to others) and inbound ones (others to this cluster) as {source, target, type} edges, with cluster UUIDs resolved to values where possible. E.g. a threat actor cluster lists the tools and techniques it uses. Arguments: cluster_id (galaxy cluster ID or UUID)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let cluster_id: String = input.get_argument("cluster_id")?;
+                let cluster = match client.get_galaxy_cluster_by_id(&cluster_id).await {
+                    Ok(response) => response.galaxy_cluster,
+                    Err(e) => {
+                        error!("cluster_relationships failed for cluster {}: {}", cluster_id, e);
+                        return Ok(misp_tool_error("cluster_relationships", format!("Failed to get galaxy cluster {}: {}", cluster_id, e), &e));
+                    }
+                };
+
+                // (source uuid, target uuid, type, direction)
+                let mut relations: Vec<(String, String, Option<String>, &str)> = Vec::new();
+                for relation in cluster.galaxy_cluster_relation.iter().flatten() {
+                    relations.push((
+                        cluster.uuid.clone(),
+                        relation.referenced_galaxy_cluster_uuid.clone(),
+                        relation.referenced_galaxy_cluster_type.clone(),
+                        "outbound",
+                    ));
+                }
+                for relation in cluster.targeting_cluster_relation.iter().flatten() {
+                    relations.push((
+                        relation.galaxy_cluster_uuid.clone(),
+                        cluster.uuid.clone(),
+                        Some(relation.referenced_galaxy_cluster_type.clone()),
+                        "inbound",
+                    ));
+                }
+
+                // Look up the other end of each relation, once per cluster and
+                // with a cap so a hub cluster does not fan out into hundreds of calls
+                const MAX_LOOKUPS: usize = 25;
+                let mut clusters: std::collections::HashMap<String, Option<(String, String)>> = std::collections::HashMap::new();
+                clusters.insert(cluster.uuid.clone(), Some((cluster.value.clone(), cluster.cluster_type.clone())));
+                let mut unresolved = 0;
+                for (source, target, _, _) in &relations {
+                    for uuid in [source, target] {
+                        if clusters.contains_key(uuid) {
+                            continue;
+                        }
+                        if clusters.len() > MAX_LOOKUPS {
+                            unresolved += 1;
+                            clusters.insert(uuid.clone(), None);
+                            continue;
+                        }
+                        // A target that is not on this instance (or not visible) stays a bare UUID
+                        let resolved = match client.get_galaxy_cluster_by_id(uuid).await {
+                            Ok(response) => Some((response.galaxy_cluster.value, response.galaxy_cluster.cluster_type)),
+                            Err(e) => {
+                                debug!("cluster_relationships could not resolve cluster {}: {}", uuid, e);
+                                unresolved += 1;
+                                None
+                            }
+                        };
+                        clusters.insert(uuid.clone(), resolved);
                     }
                 }
+                let node = |uuid: &String| {
+                    let resolved = clusters.get(uuid).cloned().flatten();
+                    serde_json::json!({
+                        "uuid": uuid,
+                        "value": resolved.as_ref().map(|(value, _)| value),
+                        "cluster_type": resolved.as_ref().map(|(_, cluster_type)| cluster_type),
+                    })
+                };
+                let edges: Vec<serde_json::Value> = relations
+                    .iter()
+                    .map(|(source, target, relation_type, direction)| serde_json::json!({
+                        "source": node(source),
+                        "target": node(target),
+                        "type": relation_type,
+                        "direction": direction,
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "cluster": {
+                        "id": cluster.id,
+                        "uuid": cluster.uuid,
+                        "value": cluster.value,
+                        "cluster_type": cluster.cluster_type,
+                    },
+                    "outbound": cluster.galaxy_cluster_relation.as_ref().map_or(0, Vec::len),
+                    "inbound": cluster.targeting_cluster_relation.as_ref().map_or(0, Vec::len),
+                    "unresolved": unresolved,
+                    "edges": edges,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
             })
         }
     ));
 
     // Tool 9: get_organisations
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_organisations",
         "Get all organisations from the MISP instance",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.get_organisations().await {
                     Ok(organisations) => {
                         let json = serde_json::to_string_pretty(&organisations)
@@ -435,7 +2119,35 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_organisations failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get organisations: {}", e)))
+                        Ok(misp_tool_error("get_organisations", format!("Failed to get organisations: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 71: get_server_settings
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_server_settings",
+        "Get the MISP server settings and diagnostics for troubleshooting, with secret values masked. Requires a site admin API key",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                match client.get_server_settings().await {
+                    Ok(settings) => {
+                        let json = serde_json::to_string_pretty(&settings)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e @ MispError::Authentication { .. }) => {
+                        error!("get_server_settings refused: {}", e);
+                        Ok(misp_tool_error("get_server_settings", "Server settings require a site admin API key".to_string(), &e))
+                    }
+                    Err(e) => {
+                        error!("get_server_settings failed: {}", e);
+                        Ok(misp_tool_error("get_server_settings", format!("Failed to get server settings: {}", e), &e))
                     }
                 }
             })
@@ -443,13 +2155,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 11: get_tags
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_tags",
         "Get all tags from the MISP instance",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.get_tags().await {
                     Ok(tags) => {
                         let json = serde_json::to_string_pretty(&tags)
@@ -458,7 +2171,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_tags failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get tags: {}", e)))
+                        Ok(misp_tool_error("get_tags", format!("Failed to get tags: {}", e), &e))
                     }
                 }
             })
@@ -466,41 +2179,33 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 12: get_tag_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_tag_by_id",
         "Get a specific tag by ID from the MISP instance",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 let tag_id = input.arguments.get("tag_id")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("tag_id parameter is required".to_string()))?
                     .as_str()
                     .ok_or_else(|| mcp_core::McpError::invalid_params("tag_id must be a string".to_string()))?;
 
-                match client.get_tag_by_id(tag_id).await {
-                    Ok(tag) => {
-                        let json = serde_json::to_string_pretty(&tag)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
-                    }
-                    Err(e) => {
-                        error!("get_tag_by_id failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get tag by ID: {}", e)))
-                    }
-                }
+                get_by_id_result("get_tag_by_id", "tag", tag_id, client.get_tag_by_id(tag_id).await)
             })
         }
     ));
 
     // Tool 13: search_tags
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "search_tags",
         "Search for tags by search term in the MISP instance",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 let search_term = input.arguments.get("search_term")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("search_term parameter is required".to_string()))?
                     .as_str()
@@ -514,7 +2219,186 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("search_tags failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to search tags: {}", e)))
+                        Ok(misp_tool_error("search_tags", format!("Failed to search tags: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 49: get_tag_numerical_value
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_tag_numerical_value",
+        "Resolve a tag by exact name (e.g. enisa:nefarious-activity-abuse) to its numerical_value used for scoring, with the taxonomy and predicate it comes from",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let tag_name: String = input.get_argument("tag_name")?;
+                let entries = match client.search_tags(&tag_name).await {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        error!("get_tag_numerical_value failed for {}: {}", tag_name, e);
+                        return Ok(misp_tool_error("get_tag_numerical_value", format!("Failed to search tag {}: {}", tag_name, e), &e));
+                    }
+                };
+                // The search is a substring match, so pick the exact tag
+                let Some(entry) = entries
+                    .into_iter()
+                    .find(|entry| entry.tag.name.as_deref().is_some_and(|name| name.eq_ignore_ascii_case(&tag_name)))
+                else {
+                    return Ok(tool_error_result("get_tag_numerical_value", format!("Tag not found: {}", tag_name), Some(404)));
+                };
+
+                // A value set on the tag itself overrides the taxonomy's
+                let tag_value = entry.tag.numerical_value
+                    .as_deref()
+                    .and_then(|value| value.trim().parse::<i64>().ok());
+                let predicate_value = entry.taxonomy_predicate
+                    .as_ref()
+                    .and_then(|predicate| predicate.numerical_value)
+                    .map(i64::from);
+                let (numerical_value, source) = match (tag_value, predicate_value) {
+                    (Some(value), _) => (Some(value), Some("tag")),
+                    (None, Some(value)) => (Some(value), Some("taxonomy_predicate")),
+                    (None, None) => (None, None),
+                };
+
+                let result = serde_json::json!({
+                    "tag": entry.tag.name,
+                    "tag_id": entry.tag.id,
+                    "numerical_value": numerical_value,
+                    "source": source,
+                    "taxonomy": entry.taxonomy.as_ref().map(|taxonomy| serde_json::json!({
+                        "namespace": taxonomy.namespace,
+                        "description": taxonomy.description,
+                    })),
+                    "predicate": entry.taxonomy_predicate.as_ref().map(|predicate| serde_json::json!({
+                        "value": predicate.value,
+                        "expanded": predicate.expanded,
+                        "description": predicate.description,
+                        "numerical_value": predicate.numerical_value,
+                    })),
+                });
+                let json = serde_json::to_string_pretty(&result)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 60: export_tags_with_taxonomy
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "export_tags_with_taxonomy",
+        "Export all tags, each annotated with its taxonomy namespace, predicate, value and descriptions when it comes from a taxonomy. Arguments: optional namespace (only that taxonomy's tags) and taxonomy_only (default false)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let namespace_filter: Option<String> = input.get_optional_argument("namespace")?;
+                let taxonomy_only = input.get_optional_argument::<bool>("taxonomy_only")?.unwrap_or(false);
+                let tags = match client.get_tags().await {
+                    Ok(tags) => tags,
+                    Err(e) => {
+                        error!("export_tags_with_taxonomy failed to get tags: {}", e);
+                        return Ok(misp_tool_error("export_tags_with_taxonomy", format!("Failed to get tags: {}", e), &e));
+                    }
+                };
+                let taxonomies = match client.get_taxonomies().await {
+                    Ok(taxonomies) => taxonomies,
+                    Err(e) => {
+                        error!("export_tags_with_taxonomy failed to get taxonomies: {}", e);
+                        return Ok(misp_tool_error("export_tags_with_taxonomy", format!("Failed to get taxonomies: {}", e), &e));
+                    }
+                };
+                let taxonomies: std::collections::HashMap<String, misp_types::Taxonomy> = taxonomies
+                    .into_iter()
+                    .map(|entry| (entry.taxonomy.namespace.to_lowercase(), entry.taxonomy))
+                    .collect();
+
+                // Predicate descriptions are fetched once per taxonomy in use, not per tag;
+                // tag (lowercased) -> (expanded, description)
+                let mut predicates: std::collections::HashMap<String, std::collections::HashMap<String, (String, String)>> =
+                    std::collections::HashMap::new();
+                let mut exported = Vec::new();
+                for tag in &tags {
+                    let Some(name) = tag.name.as_deref() else { continue };
+                    let parts = split_machine_tag(name);
+                    let taxonomy = parts.and_then(|(namespace, _, _)| taxonomies.get(&namespace.to_lowercase()));
+                    if let Some(wanted) = &namespace_filter {
+                        if !taxonomy.is_some_and(|t| t.namespace.eq_ignore_ascii_case(wanted)) {
+                            continue;
+                        }
+                    }
+                    let (Some(taxonomy), Some((_, predicate, value))) = (taxonomy, parts) else {
+                        if !taxonomy_only {
+                            exported.push(serde_json::json!({ "id": tag.id, "name": name, "taxonomy": null }));
+                        }
+                        continue;
+                    };
+                    let namespace_key = taxonomy.namespace.to_lowercase();
+                    if !predicates.contains_key(&namespace_key) {
+                        let entries = match client.get_taxonomy_by_id(&taxonomy.id).await {
+                            Ok(response) => response
+                                .entries
+                                .into_iter()
+                                .map(|entry| (entry.tag.to_lowercase(), (entry.expanded, entry.description)))
+                                .collect(),
+                            Err(e) => {
+                                // Namespace-level context is still worth returning
+                                warn!("export_tags_with_taxonomy: no predicates for taxonomy {}: {}", taxonomy.namespace, e);
+                                std::collections::HashMap::new()
+                            }
+                        };
+                        predicates.insert(namespace_key.clone(), entries);
+                    }
+                    let detail = predicates[&namespace_key].get(&name.to_lowercase());
+                    exported.push(serde_json::json!({
+                        "id": tag.id,
+                        "name": name,
+                        "taxonomy": {
+                            "id": taxonomy.id,
+                            "namespace": taxonomy.namespace,
+                            "description": taxonomy.description,
+                            "predicate": predicate,
+                            "value": value,
+                            "expanded": detail.map(|(expanded, _)| expanded),
+                            "tag_description": detail.map(|(_, description)| description).filter(|d| !d.is_empty()),
+                        },
+                    }));
+                }
+
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "count": exported.len(),
+                    "tags": exported,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 43: get_tag_statistics
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_tag_statistics",
+        "Get usage counts per tag and per taxonomy. Optional `percentage` (default false) returns percentages instead of counts",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let percentage = input.get_optional_argument::<bool>("percentage")?.unwrap_or(false);
+                match client.tag_statistics(percentage).await {
+                    Ok(stats) => {
+                        let json = serde_json::to_string_pretty(&stats)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_tag_statistics failed: {}", e);
+                        Ok(misp_tool_error("get_tag_statistics", format!("Failed to get tag statistics: {}", e), &e))
                     }
                 }
             })
@@ -522,24 +2406,49 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 14: get_organisation_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_organisation_by_id",
         "Get a specific organisation by its ID from the MISP instance",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let organisation_id: String = input.get_argument("organisation_id")?;
+                let client = clients.select(&mut input)?;
+                let organisation_id: String = input.get_id("organisation_id")?;
 
-                match client.get_organisation_by_id(&organisation_id).await {
-                    Ok(organisation) => {
-                        let json = serde_json::to_string_pretty(&organisation)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                get_by_id_result("get_organisation_by_id", "organisation", &organisation_id, client.get_organisation_by_id(&organisation_id).await)
+            })
+        }
+    ));
+
+    // Tool 36: resolve_org
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "resolve_org",
+        "Resolve an organisation ID (e.g. an event's org_id/orgc_id) to its name; repeated lookups are cached",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                // Accept the ID either as a string or as a bare JSON number
+                let org_id = match input.get_argument::<serde_json::Value>("org_id")? {
+                    serde_json::Value::String(id) => id,
+                    other => other.to_string(),
+                };
+
+                match client.resolve_org_name(&org_id).await {
+                    Ok((name, cached)) => {
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "id": org_id,
+                            "name": name,
+                            "cached": cached,
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("get_organisation_by_id failed for organisation_id {}: {}", organisation_id, e);
-                        Ok(ToolResult::error(format!("Failed to get organisation {}: {}", organisation_id, e)))
+                        error!("resolve_org failed for org_id {}: {}", org_id, e);
+                        Ok(misp_tool_error("resolve_org", format!("Failed to resolve organisation {}: {}", org_id, e), &e))
                     }
                 }
             })
@@ -547,13 +2456,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 15: get_taxonomies
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_taxonomies",
         "Get all taxonomies from the MISP instance",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.get_taxonomies().await {
                     Ok(taxonomies) => {
                         let json = serde_json::to_string_pretty(&taxonomies)
@@ -562,78 +2472,352 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_taxonomies failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get taxonomies: {}", e)))
+                        Ok(misp_tool_error("get_taxonomies", format!("Failed to get taxonomies: {}", e), &e))
                     }
                 }
             })
         }
     ));
 
-    // Tool 16: get_taxonomy_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_taxonomy_by_id",
-        "Get a specific taxonomy by its ID from the MISP instance",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 47: list_taxonomies
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "list_taxonomies",
+        "List taxonomies as namespace/description/version. Optional argument: enabled (true or false) to keep only enabled or disabled taxonomies",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let taxonomy_id = input.arguments.get("taxonomy_id")
-                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id parameter is required".to_string()))?
-                    .as_str()
-                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id must be a string".to_string()))?;
-
-                match client.get_taxonomy_by_id(taxonomy_id).await {
-                    Ok(taxonomy) => {
-                        let json = serde_json::to_string_pretty(&taxonomy)
+                let client = clients.select(&mut input)?;
+                let enabled = input.get_optional_argument::<bool>("enabled")?;
+                match client.get_taxonomies().await {
+                    Ok(taxonomies) => {
+                        let json = serde_json::to_string_pretty(&taxonomy_summaries(&taxonomies, enabled))
                             .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("get_taxonomy_by_id failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get taxonomy by ID: {}", e)))
+                        error!("list_taxonomies failed: {}", e);
+                        Ok(misp_tool_error("list_taxonomies", format!("Failed to get taxonomies: {}", e), &e))
                     }
                 }
             })
         }
     ));
 
-    // Tool 17: get_taxonomy_extended_with_tags
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_taxonomy_extended_with_tags",
-        "Get a taxonomy with its extended tags from the MISP instance",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 48: list_enabled_taxonomies
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "list_enabled_taxonomies",
+        "List only the enabled taxonomies (namespace/description/version), i.e. the ones whose tags can be used",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let taxonomy_id = input.arguments.get("taxonomy_id")
-                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id parameter is required".to_string()))?
-                    .as_str()
-                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id must be a string".to_string()))?;
-
-                 match client.get_taxonomy_extended_with_tags(taxonomy_id).await {
-                    Ok(taxonomy_ext) => {
-                        let json = serde_json::to_string_pretty(&taxonomy_ext)
+                let client = clients.select(&mut input)?;
+                let enabled = Some(true);
+                match client.get_taxonomies().await {
+                    Ok(taxonomies) => {
+                        let json = serde_json::to_string_pretty(&taxonomy_summaries(&taxonomies, enabled))
                             .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("get_taxonomy_extended_with_tags failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get taxonomy extended with tags: {}", e)))
+                        error!("list_enabled_taxonomies failed: {}", e);
+                        Ok(misp_tool_error("list_enabled_taxonomies", format!("Failed to get taxonomies: {}", e), &e))
                     }
                 }
             })
         }
     ));
-    // Tool 18: get_sightings_by_event_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_sightings_by_event_id",
+
+    // Tool 16: get_taxonomy_by_id
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_taxonomy_by_id",
+        "Get a specific taxonomy by its ID from the MISP instance",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let taxonomy_id = input.arguments.get("taxonomy_id")
+                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id parameter is required".to_string()))?
+                    .as_str()
+                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id must be a string".to_string()))?;
+
+                get_by_id_result("get_taxonomy_by_id", "taxonomy", taxonomy_id, client.get_taxonomy_by_id(taxonomy_id).await)
+            })
+        }
+    ));
+
+    // Tool 17: get_taxonomy_extended_with_tags
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_taxonomy_extended_with_tags",
+        "Get a taxonomy with its extended tags from the MISP instance",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let taxonomy_id = input.arguments.get("taxonomy_id")
+                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id parameter is required".to_string()))?
+                    .as_str()
+                    .ok_or_else(|| mcp_core::McpError::invalid_params("taxonomy_id must be a string".to_string()))?;
+
+                 match client.get_taxonomy_extended_with_tags_lenient(taxonomy_id).await {
+                    Ok(taxonomy_ext) => {
+                        let json = serde_json::to_string_pretty(&taxonomy_ext)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_taxonomy_extended_with_tags failed: {}", e);
+                        Ok(misp_tool_error("get_taxonomy_extended_with_tags", format!("Failed to get taxonomy extended with tags: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 68: get_taxonomy_tags
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_taxonomy_tags",
+        "List the valid tags of a taxonomy as full tag strings (namespace:predicate=\"value\") with their descriptions, for tagging with correct names. Arguments: taxonomy (namespace such as tlp, or taxonomy ID)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let taxonomy: String = input.get_id("taxonomy")?;
+                // Numeric values are IDs; anything else is a namespace to look up
+                let response = if taxonomy.chars().all(|c| c.is_ascii_digit()) {
+                    client.get_taxonomy_extended_with_tags(&taxonomy).await
+                } else {
+                    client.get_taxonomy_tags_by_namespace(&taxonomy).await
+                };
+                match response {
+                    Ok(response) => {
+                        let tags: Vec<serde_json::Value> = response
+                            .entries
+                            .iter()
+                            .map(|entry| serde_json::json!({
+                                "tag": entry.tag,
+                                "expanded": entry.expanded,
+                                "description": entry.description,
+                                "exclusive_predicate": entry.exclusive_predicate,
+                                "existing_tag": entry.existing_tag,
+                            }))
+                            .collect();
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "taxonomy": {
+                                "id": response.taxonomy.id,
+                                "namespace": response.taxonomy.namespace,
+                                "description": response.taxonomy.description,
+                                "enabled": response.taxonomy.enabled.unwrap_or(false),
+                                "exclusive": response.taxonomy.exclusive.unwrap_or(false),
+                            },
+                            "tags": tags,
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(MispError::NotFound { .. }) if !taxonomy.chars().all(|c| c.is_ascii_digit()) => Err(
+                        mcp_core::McpError::invalid_argument("taxonomy", format!("No taxonomy with namespace '{}'", taxonomy)),
+                    ),
+                    Err(e) => {
+                        error!("get_taxonomy_tags failed for taxonomy {}: {}", taxonomy, e);
+                        Ok(misp_tool_error("get_taxonomy_tags", format!("Failed to get the tags of taxonomy {}: {}", taxonomy, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 80: auto_tag_attribute
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "auto_tag_attribute",
+        "Suggest the taxonomy tag an attribute should carry from its properties (tlp: from its distribution, e.g. tlp:amber for organisation-only or sharing-group attributes), and optionally attach it. Attributes already tagged in the namespace are left alone. Arguments: attribute_id, namespace (currently tlp), optional apply (default false: only suggest)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let attribute_id: String = input.get_id("attribute_id")?;
+                let namespace: String = input.get_argument::<String>("namespace")?.trim().to_ascii_lowercase();
+                let apply = input.get_optional_argument::<bool>("apply")?.unwrap_or(false);
+                if !AUTO_TAG_NAMESPACES.contains(&namespace.as_str()) {
+                    return Err(mcp_core::McpError::invalid_argument(
+                        "namespace",
+                        format!("No auto-tagging rule for namespace '{}'; supported: {}", namespace, AUTO_TAG_NAMESPACES.join(", ")),
+                    ));
+                }
+                let attribute = match client.get_attribute_by_id(&attribute_id).await {
+                    Ok(attribute) => attribute,
+                    Err(e) => {
+                        error!("auto_tag_attribute failed for attribute {}: {}", attribute_id, e);
+                        return Ok(misp_tool_error("auto_tag_attribute", format!("Failed to get attribute {}: {}", attribute_id, e), &e));
+                    }
+                };
+
+                // An attribute inheriting its distribution shares as widely as its event
+                let mut distribution: misp_types::Distribution = attribute.distribution.parse()
+                    .map_err(|e| mcp_core::McpError::internal_error(format!("Attribute {} has an unknown distribution: {}", attribute_id, e)))?;
+                if distribution == misp_types::Distribution::InheritEvent {
+                    match client.get_event_by_id(&attribute.event_id).await {
+                        Ok(response) => {
+                            if let Some(level) = response.event.distribution.as_deref().and_then(|d| d.parse().ok()) {
+                                distribution = level;
+                            }
+                        }
+                        Err(e) => {
+                            error!("auto_tag_attribute failed for event {}: {}", attribute.event_id, e);
+                            return Ok(misp_tool_error("auto_tag_attribute", format!("Failed to get event {}: {}", attribute.event_id, e), &e));
+                        }
+                    }
+                }
+
+                let prefix = format!("{}:", namespace);
+                let existing: Vec<&str> = attribute
+                    .tag
+                    .iter()
+                    .flatten()
+                    .filter_map(|tag| tag.name.as_deref())
+                    .filter(|name| name.to_ascii_lowercase().starts_with(&prefix))
+                    .collect();
+
+                // Only suggest tags the taxonomy on this instance defines
+                let taxonomy = match client.get_taxonomy_tags_by_namespace(&namespace).await {
+                    Ok(taxonomy) => taxonomy,
+                    Err(e) => {
+                        error!("auto_tag_attribute failed to get taxonomy {}: {}", namespace, e);
+                        return Ok(misp_tool_error("auto_tag_attribute", format!("Failed to get taxonomy {}: {}", namespace, e), &e));
+                    }
+                };
+                let suggested = auto_tag_predicates(&namespace, distribution)
+                    .into_iter()
+                    .flatten()
+                    .map(|predicate| format!("{}{}", prefix, predicate))
+                    .find(|tag| taxonomy.entries.iter().any(|entry| entry.tag.eq_ignore_ascii_case(tag)));
+                let reason = format!("distribution {} ({})", distribution.as_str(), distribution.name());
+
+                let mut applied = false;
+                if apply && existing.is_empty() {
+                    if let Some(tag) = &suggested {
+                        if let Err(e) = client.tag_attribute(&attribute.uuid, tag).await {
+                            error!("auto_tag_attribute failed to tag attribute {}: {}", attribute_id, e);
+                            return Ok(misp_tool_error("auto_tag_attribute", format!("Failed to attach {} to attribute {}: {}", tag, attribute_id, e), &e));
+                        }
+                        applied = true;
+                    }
+                }
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "attribute_id": attribute.id,
+                    "namespace": namespace,
+                    "suggested": suggested,
+                    "reason": reason,
+                    "existing": existing,
+                    "applied": applied,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 85: resolve_confidence_tag
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "resolve_confidence_tag",
+        "Map a human confidence label (e.g. \"high confidence\", \"fairly confident\", \"low\") to the machine tag of a confidence taxonomy on this instance: misp:confidence-level, estimative-language or admiralty-scale (information credibility). Returns the tag string to attach, e.g. misp:confidence-level=\"usually-confident\". Arguments: label, optional namespace (default: the first of those taxonomies the instance has)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let label: String = input.get_argument("label")?;
+                let namespace: Option<String> = input
+                    .get_optional_argument::<String>("namespace")?
+                    .map(|namespace| namespace.trim().to_ascii_lowercase());
+                let level = confidence_level(&label).ok_or_else(|| {
+                    mcp_core::McpError::invalid_argument(
+                        "label",
+                        format!("No confidence level in '{}'; use e.g. very high, high, moderate, low, none or unknown", label),
+                    )
+                })?;
+                let candidates: Vec<&(&str, &[(&str, &str)])> = match &namespace {
+                    Some(namespace) => {
+                        let found: Vec<_> = CONFIDENCE_TAGS.iter().filter(|(name, _)| name == namespace).collect();
+                        if found.is_empty() {
+                            let supported: Vec<&str> = CONFIDENCE_TAGS.iter().map(|(name, _)| *name).collect();
+                            return Err(mcp_core::McpError::invalid_argument(
+                                "namespace",
+                                format!("No confidence mapping for namespace '{}'; supported: {}", namespace, supported.join(", ")),
+                            ));
+                        }
+                        found
+                    }
+                    None => CONFIDENCE_TAGS.iter().collect(),
+                };
+
+                // Only resolve to tags a taxonomy on this instance defines
+                let mut resolved = None;
+                let mut unavailable = Vec::new();
+                for (taxonomy_namespace, tags) in candidates {
+                    let Some((_, tag)) = tags.iter().find(|(tag_level, _)| *tag_level == level) else {
+                        unavailable.push(serde_json::json!({ "namespace": taxonomy_namespace, "reason": format!("no {} level", level) }));
+                        continue;
+                    };
+                    let taxonomy = match client.get_taxonomy_tags_by_namespace(taxonomy_namespace).await {
+                        Ok(taxonomy) => taxonomy,
+                        Err(MispError::NotFound { .. }) => {
+                            unavailable.push(serde_json::json!({ "namespace": taxonomy_namespace, "reason": "taxonomy not on this instance" }));
+                            continue;
+                        }
+                        Err(e) => {
+                            error!("resolve_confidence_tag failed to get taxonomy {}: {}", taxonomy_namespace, e);
+                            return Ok(misp_tool_error("resolve_confidence_tag", format!("Failed to get taxonomy {}: {}", taxonomy_namespace, e), &e));
+                        }
+                    };
+                    match taxonomy.entries.iter().find(|entry| entry.tag.eq_ignore_ascii_case(tag)) {
+                        Some(entry) => {
+                            resolved = Some(serde_json::json!({
+                                "namespace": taxonomy_namespace,
+                                "tag": entry.tag,
+                                "expanded": entry.expanded,
+                                "enabled": taxonomy.taxonomy.enabled,
+                            }));
+                            break;
+                        }
+                        None => unavailable.push(serde_json::json!({ "namespace": taxonomy_namespace, "reason": format!("taxonomy has no {}", tag) })),
+                    }
+                }
+
+                let mut result = serde_json::json!({
+                    "label": label,
+                    "level": level,
+                    "tag": serde_json::Value::Null,
+                    "unavailable": unavailable,
+                });
+                if let Some(resolved) = resolved {
+                    for key in ["namespace", "tag", "expanded", "enabled"] {
+                        result[key] = resolved[key].clone();
+                    }
+                }
+                let json = serde_json::to_string_pretty(&result)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 18: get_sightings_by_event_id
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_sightings_by_event_id",
         "Retrieve sightings for a specific event by ID or UUID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 // Extract required event_id argument (string)
-                let event_id: String = input.get_argument("event_id")?;
+                let event_id: String = input.get_id("event_id")?;
                 match client.get_sightings_by_event_id(&event_id).await {
                     Ok(sightings) => {
                         let json = serde_json::to_string_pretty(&sightings)
@@ -642,7 +2826,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_sightings_by_event_id failed for event_id '{}': {}", event_id, e);
-                        Ok(ToolResult::error(format!("Failed to get sightings for event_id '{}': {}", event_id, e)))
+                        Ok(misp_tool_error("get_sightings_by_event_id", format!("Failed to get sightings for event_id '{}': {}", event_id, e), &e))
                     }
                 }
             })
@@ -650,13 +2834,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 19: get_warninglists
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_warninglists",
         "Retrieve all warninglists from MISP",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.get_warninglists().await {
                     Ok(warninglists) => {
                         let json = serde_json::to_string_pretty(&warninglists)
@@ -665,7 +2850,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_warninglists failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get warninglists: {}", e)))
+                        Ok(misp_tool_error("get_warninglists", format!("Failed to get warninglists: {}", e), &e))
                     }
                 }
             })
@@ -673,13 +2858,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 20: get_noticelists
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_noticelists",
         "Retrieve all noticelists from MISP",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.get_noticelists().await {
                     Ok(noticelists) => {
                         let json = serde_json::to_string_pretty(&noticelists)
@@ -688,7 +2874,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_noticelists failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get noticelists: {}", e)))
+                        Ok(misp_tool_error("get_noticelists", format!("Failed to get noticelists: {}", e), &e))
                     }
                 }
             })
@@ -696,62 +2882,92 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 21: get_warninglist_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_warninglist_by_id",
         "Retrieve a specific warninglist by its ID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let warninglist_id: String = input.get_argument("warninglist_id")?;
-                match client.get_warninglist_by_id(&warninglist_id).await {
-                    Ok(warninglist) => {
-                        let json = serde_json::to_string_pretty(&warninglist)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
-                    }
+                let client = clients.select(&mut input)?;
+                let warninglist_id: String = input.get_id("warninglist_id")?;
+                get_by_id_result("get_warninglist_by_id", "warninglist", &warninglist_id, client.get_warninglist_by_id(&warninglist_id).await)
+            })
+        }
+    ));
+
+    // Tool 53: get_warninglist_entries
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_warninglist_entries",
+        "Page through the values of a warninglist. Arguments: warninglist_id, optional page (default 1) and limit (default 500)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let warninglist_id: String = input.get_id("warninglist_id")?;
+                let page = input.get_optional_argument::<usize>("page")?.unwrap_or(1).max(1);
+                let limit = input.get_optional_argument::<usize>("limit")?.unwrap_or(500).max(1);
+                let (warninglist, entries) = match client.get_warninglist_entries(&warninglist_id).await {
+                    Ok(result) => result,
                     Err(e) => {
-                        error!("get_warninglist_by_id failed for warninglist_id {}: {}", warninglist_id, e);
-                        Ok(ToolResult::error(format!("Failed to get warninglist {}: {}", warninglist_id, e)))
+                        error!("get_warninglist_entries failed for warninglist_id {}: {}", warninglist_id, e);
+                        return Ok(misp_tool_error("get_warninglist_entries", format!("Failed to get entries of warninglist {}: {}", warninglist_id, e), &e));
                     }
-                }
+                };
+                let total = entries.len();
+                let page_entries: Vec<serde_json::Value> = entries
+                    .into_iter()
+                    .skip((page - 1).saturating_mul(limit))
+                    .take(limit)
+                    .map(|entry| serde_json::json!({
+                        "id": entry.id,
+                        "value": entry.value,
+                        "comment": entry.comment,
+                    }))
+                    .collect();
+                let result = serde_json::json!({
+                    "warninglist_id": warninglist.id,
+                    "name": warninglist.name,
+                    "type": warninglist.type_,
+                    "total": total,
+                    "page": page,
+                    "limit": limit,
+                    "has_more": page.saturating_mul(limit) < total,
+                    "entries": page_entries,
+                });
+                let json = serde_json::to_string_pretty(&result)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
             })
         }
     ));
 
         // Tool 22: get_noticelist_by_id
-        let client_clone = client.clone();
-        server.add_tool(Tool::new(
+        let clients_clone = clients.clone();
+        add_permitted_tool(server, tools, Tool::new(
             "get_noticelist_by_id",
             "Retrieve a specific noticelist by its ID from MISP",
-            move |input: ToolInput| {
-                let client = client_clone.clone();
+            move |mut input: ToolInput| {
+                let clients = clients_clone.clone();
                 Box::pin(async move {
-                    let noticelist_id: String = input.get_argument("noticelist_id")?;
-                    match client.get_noticelist_by_id(&noticelist_id).await {
-                        Ok(noticelist) => {
-                            let json = serde_json::to_string_pretty(&noticelist)
-                                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                            Ok(ToolResult::text(json))
-                        }
-                        Err(e) => {
-                            error!("get_noticelist_by_id failed for noticelist_id {}: {}", noticelist_id, e);
-                            Ok(ToolResult::error(format!("Failed to get noticelist {}: {}", noticelist_id, e)))
-                        }
-                    }
+                    let client = clients.select(&mut input)?;
+                    let noticelist_id: String = input.get_id("noticelist_id")?;
+                    get_by_id_result("get_noticelist_by_id", "noticelist", &noticelist_id, client.get_noticelist_by_id(&noticelist_id).await)
                 })
             }
         ));
 
 
     // Tool 23: search_warninglists
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "search_warninglists",
         "Search warninglists by value in MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 // Extract "value" argument, error if missing or not a string
                 let value = input.arguments.get("value")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("value parameter is required".to_string()))?
@@ -766,7 +2982,43 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("search_warninglists failed for value '{}': {}", value, e);
-                        Ok(ToolResult::error(format!("Failed to search warninglists with value '{}': {}", value, e)))
+                        Ok(misp_tool_error("search_warninglists", format!("Failed to search warninglists with value '{}': {}", value, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 90: check_value_warninglist
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "check_value_warninglist",
+        "Check one value against the enabled warninglists, e.g. whether 127.0.0.1 or 8.8.8.8 is a known benign or noisy indicator. Arguments: value (defanged values are accepted). Returns the names of the matching warninglists, empty if none match",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let value: String = input.get_argument("value")?;
+                let value = refang_indicator(value.trim());
+                if value.is_empty() {
+                    return Err(mcp_core::McpError::invalid_argument("value", "value must not be empty"));
+                }
+                match client.check_warninglist_values(std::slice::from_ref(&value)).await {
+                    Ok(hits) => {
+                        // Only one value was sent, so every hit is for it
+                        let mut warninglists: Vec<String> = hits.into_values().flatten().map(|hit| hit.name).collect();
+                        warninglists.sort();
+                        warninglists.dedup();
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "value": value,
+                            "warninglists": warninglists,
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("check_value_warninglist failed for value '{}': {}", value, e);
+                        Ok(misp_tool_error("check_value_warninglist", format!("Failed to check '{}' against warninglists: {}", value, e), &e))
                     }
                 }
             })
@@ -774,13 +3026,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 24: get_eventreports
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_eventreports",
         "Retrieve all event reports from MISP",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.get_event_reports().await {
                     Ok(eventreports) => {
                         let json = serde_json::to_string_pretty(&eventreports)
@@ -789,7 +3042,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_eventreports failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get event reports: {}", e)))
+                        Ok(misp_tool_error("get_eventreports", format!("Failed to get event reports: {}", e), &e))
                     }
                 }
             })
@@ -797,25 +3050,16 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 25: get_event_report_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_event_report_by_id",
         "Retrieve a single event report by its ID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let event_report_id: String = input.get_argument("event_report_id")?;
-                match client.get_event_report_by_id(&event_report_id).await {
-                    Ok(event_report) => {
-                        let json = serde_json::to_string_pretty(&event_report)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
-                    }
-                    Err(e) => {
-                        error!("get_event_report_by_id failed for event_report_id {}: {}", event_report_id, e);
-                        Ok(ToolResult::error(format!("Failed to get event report {}: {}", event_report_id, e)))
-                    }
-                }
+                let client = clients.select(&mut input)?;
+                let event_report_id: String = input.get_id("event_report_id")?;
+                get_by_id_result("get_event_report_by_id", "event report", &event_report_id, client.get_event_report_by_id(&event_report_id).await)
             })
         }
     ));
@@ -823,42 +3067,30 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     // Tool 26: get_collection_by_id
     // Register the get_collection_by_id tool for retrieving a single collection by its ID from MISP.
     // This follows the same pattern as get_event_report_by_id for consistency and maintainability.
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_collection_by_id",
         "Retrieve a single collection by its ID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let collection_id: String = input.get_argument("collection_id")?;
-                match client.get_collection_by_id(&collection_id).await {
-                    Ok(collection) => {
-                        let json = serde_json::to_string_pretty(&collection)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
-                    }
-                    Err(MispError::NotFound { .. }) => {
-                        // Gracefully handle "not found" by returning an empty JSON object
-                        Ok(ToolResult::text("{}".to_string()))
-                    }                    
-                    Err(e) => {
-                        error!("get_collection_by_id failed for collection_id {}: {}", collection_id, e);
-                        Ok(ToolResult::error(format!("Failed to get collection {}: {}", collection_id, e)))
-                    }
-                }
+                let client = clients.select(&mut input)?;
+                let collection_id: String = input.get_id("collection_id")?;
+                get_by_id_result("get_collection_by_id", "collection", &collection_id, client.get_collection_by_id(&collection_id).await)
             })
         }
     ));
 
 
     // Tool 27: search_collections
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "search_collections",
         "Search for collections with filtering from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 let filter = input.arguments.get("filter")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("filter parameter is required".to_string()))?
                     .as_str()
@@ -878,7 +3110,63 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("search_collections failed for filter '{}': {}", filter, e);
-                        Ok(ToolResult::error(format!("Failed to search collections for filter '{}': {}", filter, e)))
+                        Ok(misp_tool_error("search_collections", format!("Failed to search collections for filter '{}': {}", filter, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 86: create_collection
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "create_collection",
+        "Create a collection grouping events and galaxy clusters. Arguments: type (campaign, intrusion_set, named_set or other), name, optional description, distribution (0-5) and sharing_group_id. Add elements with add_to_collection",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let map: serde_json::Map<String, serde_json::Value> = input.arguments.into_iter().collect();
+                let request: CreateCollectionRequest = serde_json::from_value(serde_json::Value::Object(map))
+                    .map_err(|e| mcp_core::McpError::invalid_params(format!("Invalid collection: {}", e)))?;
+                match client.create_collection(&request).await {
+                    Ok(collection) => write_result(collection),
+                    Err(e) => {
+                        error!("create_collection failed for '{}': {}", request.name, e);
+                        Ok(misp_tool_error("create_collection", format!("Failed to create collection '{}': {}", request.name, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 87: add_to_collection
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "add_to_collection",
+        "Add an event or galaxy cluster to a collection. Arguments: collection_id, element_uuid (UUID of the event or cluster), optional element_type (Event or GalaxyCluster, default Event) and description",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let collection_id: String = input.get_id("collection_id")?;
+                let element_type: String = input.get_optional_argument("element_type")?.unwrap_or_else(|| "Event".to_string());
+                if element_type != "Event" && element_type != "GalaxyCluster" {
+                    return Err(mcp_core::McpError::invalid_argument(
+                        "element_type",
+                        format!("must be Event or GalaxyCluster, got '{}'", element_type),
+                    ));
+                }
+                let element = AddCollectionElementRequest {
+                    element_type,
+                    element_uuid: input.get_argument("element_uuid")?,
+                    description: input.get_optional_argument("description")?,
+                };
+                match client.add_collection_element(&collection_id, &element).await {
+                    Ok(element) => write_result(element),
+                    Err(e) => {
+                        error!("add_to_collection failed for collection_id {}: {}", collection_id, e);
+                        Ok(misp_tool_error("add_to_collection", format!("Failed to add {} to collection {}: {}", element.element_uuid, collection_id, e), &e))
                     }
                 }
             })
@@ -887,13 +3175,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
 
 
     // Tool 28: list_analyst_data
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "list_analyst_data",
         "List analyst data of a given type (Note, Opinion, Relationship) from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 let analyst_type = input.arguments.get("analyst_type")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("analyst_type parameter is required".to_string()))?
                     .as_str()
@@ -907,7 +3196,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("list_analyst_data failed for analyst_type '{}': {}", analyst_type, e);
-                        Ok(ToolResult::error(format!("Failed to list analyst data for type '{}': {}", analyst_type, e)))
+                        Ok(misp_tool_error("list_analyst_data", format!("Failed to list analyst data for type '{}': {}", analyst_type, e), &e))
                     }
                 }
             })
@@ -915,13 +3204,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 29: get_analyst_data_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_analyst_data_by_id",
         "Get a single analyst data object by type and ID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 let analyst_type = input.arguments.get("analyst_type")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("analyst_type parameter is required".to_string()))?
                     .as_str()
@@ -931,29 +3221,25 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     .as_str()
                     .ok_or_else(|| mcp_core::McpError::invalid_params("analyst_data_id must be a string".to_string()))?;
 
-                match client.get_analyst_data_by_id(analyst_type, analyst_data_id).await {
-                    Ok(data) => {
-                        let json = serde_json::to_string_pretty(&data)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
-                    }
-                    Err(e) => {
-                        error!("get_analyst_data_by_id failed for type '{}' and id '{}': {}", analyst_type, analyst_data_id, e);
-                        Ok(ToolResult::text("{}".to_string()))
-                    }
-                }
+                get_by_id_result(
+                    "get_analyst_data_by_id",
+                    &format!("{} analyst data", analyst_type),
+                    analyst_data_id,
+                    client.get_analyst_data_by_id(analyst_type, analyst_data_id).await,
+                )
             })
         }
     ));
 
     // Tool 30: list_attributes
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "list_attributes",
         "List all attributes in the MISP instance.",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.list_attributes().await {
                     Ok(data) => {
                         let json = serde_json::to_string_pretty(&data)
@@ -962,7 +3248,7 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("list_attributes failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to list attributes: {}", e)))
+                        Ok(misp_tool_error("list_attributes", format!("Failed to list attributes: {}", e), &e))
                     }
                 }
             })
@@ -970,40 +3256,32 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 31: get_attribute_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_attribute_by_id",
         "Get a single attribute by its ID or UUID.",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 let attribute_id = input.arguments.get("attribute_id")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("attribute_id parameter is required".to_string()))?
                     .as_str()
                     .ok_or_else(|| mcp_core::McpError::invalid_params("attribute_id must be a string".to_string()))?;
-                match client.get_attribute_by_id(attribute_id).await {
-                    Ok(attribute) => {
-                        let json = serde_json::to_string_pretty(&attribute)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
-                    }   
-                    Err(e) => {
-                        error!("get_attribute_by_id failed for id '{}': {}", attribute_id, e);
-                        Ok(ToolResult::error(format!("Failed to get attribute for id '{}': {}", attribute_id, e)))
-                    }
-                }
+                get_by_id_result("get_attribute_by_id", "attribute", attribute_id, client.get_attribute_by_id(attribute_id).await)
             })
         }
     ));
 
     // Tool 32: get_attribute_statistics
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "get_attribute_statistics",
         "Get attribute statistics by context (type/category) and count/percentage.",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 let context = input.arguments.get("context")
                     .ok_or_else(|| mcp_core::McpError::invalid_params("context parameter is required".to_string()))?
                     .as_str()
@@ -1020,7 +3298,45 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("get_attribute_statistics failed for context '{}' and percentage '{}': {}", context, percentage, e);
-                        Ok(ToolResult::error(format!("Failed to get attribute statistics for context '{}' and percentage '{}': {}", context, percentage, e)))
+                        Ok(misp_tool_error("get_attribute_statistics", format!("Failed to get attribute statistics for context '{}' and percentage '{}': {}", context, percentage, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 92: get_decaying_models
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_decaying_models",
+        "List the decaying models with their id, name, enabled flag and parameters (lifetime, decay_speed, threshold, default_base_score).",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                match client.get_decaying_models().await {
+                    Ok(models) => {
+                        let models: Vec<serde_json::Value> = models
+                            .iter()
+                            .map(|model| serde_json::json!({
+                                "id": model.id,
+                                "name": model.name,
+                                "enabled": model.enabled,
+                                "parameters": {
+                                    "lifetime": model.parameters.lifetime,
+                                    "decay_speed": model.parameters.decay_speed,
+                                    "threshold": model.parameters.threshold,
+                                    "default_base_score": model.parameters.default_base_score,
+                                },
+                            }))
+                            .collect();
+                        let json = serde_json::to_string_pretty(&models)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_decaying_models failed: {}", e);
+                        Ok(misp_tool_error("get_decaying_models", format!("Failed to get decaying models: {}", e), &e))
                     }
                 }
             })
@@ -1028,13 +3344,14 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
     ));
 
     // Tool 33: describe_attribute_types
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
         "describe_attribute_types",
         "Get list of available attribute types, categories, and sane defaults.",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
+                let client = clients.select(&mut input)?;
                 match client.describe_attribute_types().await {
                     Ok(result) => {
                         let json = serde_json::to_string_pretty(&result)
@@ -1043,169 +3360,1802 @@ async fn register_misp_tools(server: &mut Server, client: MispClient) -> anyhow:
                     }
                     Err(e) => {
                         error!("describe_attribute_types failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to describe attribute types: {}", e)))
+                        Ok(misp_tool_error("describe_attribute_types", format!("Failed to describe attribute types: {}", e), &e))
                     }
                 }
             })
         }
     ));
 
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "attributes_rest_search",
-        "Search attributes using the /attributes/restSearch endpoint",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 34: list_categories
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "list_categories",
+        "List the valid attribute categories (compact picklist from describeTypes).",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let filter_json: String = input.get_argument("filter_json")?;
-                // println!("DEBUG: filter_json = {:?}", filter_json);
-                // Ensure we always expect a struct, not a sequence
-                let filter: AttributeRestSearchRequest = serde_json::from_str(&filter_json)
-                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                match client.attributes_rest_search(&filter).await {
-                    Ok(response) => {
-                        let json = serde_json::to_string_pretty(&response)
+                let client = clients.select(&mut input)?;
+                match client.describe_attribute_types_cached().await {
+                    Ok(result) => {
+                        let json = serde_json::to_string_pretty(&result.categories)
                             .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("attributes_rest_search failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to search attributes: {}", e)))
+                        error!("list_categories failed: {}", e);
+                        Ok(misp_tool_error("list_categories", format!("Failed to list categories: {}", e), &e))
                     }
                 }
             })
         }
     ));
 
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_events",
-        "Retrieve all events from MISP",
-        move |_input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 35: list_types
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "list_types",
+        "List the valid attribute types (compact picklist from describeTypes).",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                match client.get_events().await {
-                    Ok(events) => {
-                        let json = serde_json::to_string_pretty(&events)
+                let client = clients.select(&mut input)?;
+                match client.describe_attribute_types_cached().await {
+                    Ok(result) => {
+                        let json = serde_json::to_string_pretty(&result.types)
                             .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("get_events failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to get events: {}", e)))
+                        error!("list_types failed: {}", e);
+                        Ok(misp_tool_error("list_types", format!("Failed to list types: {}", e), &e))
                     }
                 }
             })
         }
     ));
 
-
-    // Tool: get_event_by_id
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_event_by_id",
-        "Retrieve a single event by its ID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 37: add_attribute
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "add_attribute",
+        "Add an attribute to an event. Arguments: event_id, type, value, and optionally category, to_ids, distribution, comment, sharing_group_id, disable_correlation, first_seen, last_seen, refang (true to undo defanging such as 1[.]2[.]3[.]4 in value)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                // Extract required event_id argument (string)
-                let event_id: String = input.get_argument("event_id")?;
-                match client.get_event_by_id(&event_id).await {
-                    Ok(event) => {
-                        let json = serde_json::to_string_pretty(&event)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let refang = input.get_optional_argument::<bool>("refang")?.unwrap_or(false);
+                let mut map: serde_json::Map<String, serde_json::Value> = input.arguments.into_iter().collect();
+                map.remove("event_id");
+                map.remove("refang");
+                let mut request: AddAttributeRequest = serde_json::from_value(serde_json::Value::Object(map))
+                    .map_err(|e| mcp_core::McpError::invalid_params(format!("Invalid attribute: {}", e)))?;
+                if refang {
+                    request.value = refang_indicator(&request.value);
+                }
+                match client.add_attribute(&event_id, &request).await {
+                    Ok(attribute) => write_result(attribute),
+                    Err(e) => {
+                        error!("add_attribute failed for event_id {}: {}", event_id, e);
+                        Ok(misp_tool_error("add_attribute", format!("Failed to add attribute to event {}: {}", event_id, e), &e))
                     }
+                }
+            })
+        }
+    ));
+
+    // Tool 51: add_object_reference
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "add_object_reference",
+        "Link an object to another object or attribute. Arguments: object_id (source object), referenced_uuid (target object/attribute UUID), relationship_type (e.g. downloaded-from, drops) and optional comment",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let object_id: String = input.get_id("object_id")?;
+                let request = AddObjectReferenceRequest {
+                    referenced_uuid: input.get_argument("referenced_uuid")?,
+                    relationship_type: input.get_argument("relationship_type")?,
+                    comment: input.get_optional_argument("comment")?,
+                };
+                match client.add_object_reference(&object_id, &request).await {
+                    Ok(reference) => write_result(reference),
                     Err(e) => {
-                        error!("get_event_by_id failed for event_id '{}': {}", event_id, e);
-                        Ok(ToolResult::error(format!("Failed to get event for event_id '{}': {}", event_id, e)))
+                        error!("add_object_reference failed for object_id {}: {}", object_id, e);
+                        Ok(misp_tool_error("add_object_reference", format!("Failed to add reference to object {}: {}", object_id, e), &e))
                     }
                 }
             })
         }
     ));
 
-    // Tool: search_events
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "search_events",
-        "Search for events using POST /events/index with flexible filters",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 38: upsert_attribute
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "upsert_attribute",
+        "Add an attribute to an event only if the same type/value is not already present. Takes the add_attribute arguments plus optional tags (list of tag names); an existing match gets its comment updated and tags attached",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                // Accepts a single argument: "request_json" (stringified EventIndexRequest)
-                let request_json: String = input.get_argument("request_json")?;
-                let request: EventIndexRequest = serde_json::from_str(&request_json)
-                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                match client.search_events(&request).await {
-                    Ok(events) => {
-                        let json = serde_json::to_string_pretty(&events)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let tags: Vec<String> = input.get_optional_argument("tags")?.unwrap_or_default();
+                let mut map: serde_json::Map<String, serde_json::Value> = input.arguments.into_iter().collect();
+                map.remove("event_id");
+                map.remove("tags");
+                let request: AddAttributeRequest = serde_json::from_value(serde_json::Value::Object(map))
+                    .map_err(|e| mcp_core::McpError::invalid_params(format!("Invalid attribute: {}", e)))?;
+                match client.upsert_attribute(&event_id, &request, &tags).await {
+                    Ok((attribute, created)) => {
+                        let status = if created { "created" } else { "already present" };
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "status": status,
+                            "Attribute": attribute,
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
                         Ok(ToolResult::text(json))
                     }
                     Err(e) => {
-                        error!("search_events failed: {}", e);
-                        Ok(ToolResult::error(format!("Failed to search events: {}", e)))
+                        error!("upsert_attribute failed for event_id {}: {}", event_id, e);
+                        Ok(misp_tool_error("upsert_attribute", format!("Failed to upsert attribute in event {}: {}", event_id, e), &e))
                     }
                 }
             })
         }
     ));
 
-    // Tool: events_rest_search
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "events_rest_search",
-        "Search events using the /events/restSearch endpoint",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 67: restore_attribute
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "restore_attribute",
+        "Restore a soft-deleted attribute. Hard-deleted attributes cannot be restored. Arguments: attribute_id",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                // Parse input as EventsRestSearchRequest
-                let map: serde_json::Map<String, serde_json::Value> = input.arguments.into_iter().collect();
-                let params: EventsRestSearchRequest = serde_json::from_value(serde_json::Value::Object(map))?;
-                match client.events_rest_search(&params).await {
-                    Ok(resp) => {
-                        let json = serde_json::to_string_pretty(&resp)?;
-                        Ok(ToolResult::text(json))
+                let client = clients.select(&mut input)?;
+                let attribute_id: String = input.get_id("attribute_id")?;
+                match client.restore_attribute(&attribute_id).await {
+                    Ok(attribute) => write_result(attribute.map(|attribute| serde_json::json!({ "Attribute": attribute }))),
+                    Err(e) => {
+                        error!("restore_attribute failed for attribute_id {}: {}", attribute_id, e);
+                        let message = match e {
+                            MispError::NotFound { .. } => format!(
+                                "Attribute {} not found: it was hard-deleted or never existed, and only soft-deleted attributes can be restored",
+                                attribute_id
+                            ),
+                            _ => format!("Failed to restore attribute {}: {}", attribute_id, e),
+                        };
+                        Ok(misp_tool_error("restore_attribute", message, &e))
                     }
-                    Err(e) => Ok(ToolResult::error(format!("Failed to search events: {}", e))),
                 }
             })
         }
     ));
 
-    let client_clone = client.clone();
-    server.add_tool(Tool::new(
-        "get_object",
-        "Retrieve a specific object by ID or UUID from MISP",
-        move |input: ToolInput| {
-            let client = client_clone.clone();
+    // Tool 42: get_attributes_by_type
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_attributes_by_type",
+        "Collect every attribute value of one type across the instance (e.g. all sha256 or domain values), deduplicated. Arguments: type, optional values_only (default true; false adds the event IDs per value), with_context (default true: with values_only false, also the event UUIDs and tags per value), page_size (default 1000) and max_pages (default 50)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
             Box::pin(async move {
-                let object_id: String = input.get_argument("object_id")?;
-                match client.get_object_by_id(&object_id).await {
-                    Ok(object) => {
-                        let json = serde_json::to_string_pretty(&object)
-                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
-                        Ok(ToolResult::text(json))
+                let client = clients.select(&mut input)?;
+                let attribute_type: String = input.get_argument("type")?;
+                let values_only = input.get_optional_argument::<bool>("values_only")?.unwrap_or(true);
+                // A bare value list has nowhere to show event context
+                let with_context = !values_only && input.get_optional_argument::<bool>("with_context")?.unwrap_or(true);
+                let page_size = input.get_optional_argument::<u32>("page_size")?.unwrap_or(1000).max(1);
+                let max_pages = input.get_optional_argument::<u32>("max_pages")?.unwrap_or(50);
+
+                // value -> IDs of the events it appears in
+                let mut values: std::collections::BTreeMap<String, std::collections::BTreeSet<String>> =
+                    std::collections::BTreeMap::new();
+                // value -> event UUIDs and tag names, with_context only
+                let mut contexts: std::collections::HashMap<String, (std::collections::BTreeSet<String>, std::collections::BTreeSet<String>)> =
+                    std::collections::HashMap::new();
+                let mut truncated = false;
+                for page in 1..=max_pages {
+                    let filter = AttributeRestSearchRequest {
+                        attribute_type: Some(attribute_type.clone()),
+                        page: Some(page),
+                        limit: Some(page_size),
+                        include_event_uuid: with_context.then_some(true),
+                        include_event_tags: with_context.then_some(true),
+                        ..Default::default()
+                    };
+                    let attributes = match client.attributes_rest_search(&filter).await {
+                        Ok(response) => response.response.attribute,
+                        Err(e) => {
+                            error!("get_attributes_by_type failed for type {} on page {}: {}", attribute_type, page, e);
+                            return Ok(misp_tool_error(
+                                "get_attributes_by_type",
+                                format!("Failed to fetch '{}' attributes (page {}): {}", attribute_type, page, e),
+                                &e,
+                            ));
+                        }
+                    };
+                    let fetched = attributes.len();
+                    for attribute in attributes {
+                        if with_context {
+                            let (event_uuids, tags) = contexts.entry(attribute.value.clone()).or_default();
+                            event_uuids.extend(attribute.event_uuid);
+                            tags.extend(attribute.tag.iter().flatten().filter_map(|tag| tag.name.clone()));
+                        }
+                        values.entry(attribute.value).or_default().insert(attribute.event_id);
                     }
-                    Err(e) => {
-                        error!("get_object failed for object_id {}: {}", object_id, e);
-                        Ok(ToolResult::error(format!("Failed to get object {}: {}", object_id, e)))
+                    if fetched < page_size as usize {
+                        break;
                     }
+                    truncated = page == max_pages;
                 }
+
+                let result = if values_only {
+                    serde_json::json!({
+                        "type": attribute_type,
+                        "count": values.len(),
+                        "truncated": truncated,
+                        "values": values.keys().collect::<Vec<_>>(),
+                    })
+                } else {
+                    serde_json::json!({
+                        "type": attribute_type,
+                        "count": values.len(),
+                        "truncated": truncated,
+                        "values": values
+                            .iter()
+                            .map(|(value, event_ids)| {
+                                let mut entry = serde_json::json!({ "value": value, "event_ids": event_ids });
+                                if let Some((event_uuids, tags)) = contexts.get(value) {
+                                    entry["event_uuids"] = serde_json::json!(event_uuids);
+                                    entry["tags"] = serde_json::json!(tags);
+                                }
+                                entry
+                            })
+                            .collect::<Vec<_>>(),
+                    })
+                };
+                let json = serde_json::to_string_pretty(&result)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
             })
         }
     ));
 
-server.add_tool(Tool::new(
-    "objects_rest_search",
-    "Get a filtered and paginated list of objects from MISP",
-    move |input: ToolInput| {
-        let client = client.clone();
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "attributes_rest_search",
+        "Search attributes using the /attributes/restSearch endpoint. Arguments: filter_json (stringified AttributeRestSearchRequest), optional strict (default false: reject unknown fields instead of ignoring them), optional summarize (default false: true returns the total and breakdowns by org, tag and type plus the date range instead of the attributes)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let filter_json: String = input.get_argument("filter_json")?;
+                if input.get_optional_argument::<bool>("strict")?.unwrap_or(false) {
+                    let value: serde_json::Value = serde_json::from_str(&filter_json)
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                    check_strict_fields("AttributeRestSearchRequest", "filter_json", &value)?;
+                }
+                // Ensure we always expect a struct, not a sequence
+                let filter: AttributeRestSearchRequest = serde_json::from_str(&filter_json)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                let summarize = input.get_optional_argument::<bool>("summarize")?.unwrap_or(false);
+                match client.attributes_rest_search(&filter).await {
+                    Ok(response) => {
+                        let json = if summarize {
+                            serde_json::to_string_pretty(&summarize_attributes(&response.response.attribute))
+                        } else {
+                            serde_json::to_string_pretty(&response)
+                        }
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("attributes_rest_search failed: {}", e);
+                        Ok(misp_tool_error("attributes_rest_search", format!("Failed to search attributes: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 79: export_attributes_csv
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "export_attributes_csv",
+        "Export matching attributes as CSV straight from MISP (for spreadsheets and SIEM imports). Arguments: AttributeRestSearchRequest fields such as eventid, type, tags, to_ids, last; optional requested_attributes (columns, e.g. [\"uuid\", \"value\", \"type\"]) and headerless (true to drop the header row)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let map: serde_json::Map<String, serde_json::Value> = input.arguments.into_iter().collect();
+                let params: AttributeRestSearchRequest = serde_json::from_value(serde_json::Value::Object(map))
+                    .map_err(|e| mcp_core::McpError::invalid_params(format!("Invalid search filters: {}", e)))?;
+                match client.attributes_rest_search_csv(&params).await {
+                    Ok(csv) => Ok(ToolResult::text(csv)),
+                    Err(e) => {
+                        error!("export_attributes_csv failed: {}", e);
+                        Ok(misp_tool_error("export_attributes_csv", format!("Failed to export attributes as CSV: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_events",
+        "Retrieve all events from MISP",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                match client.get_events().await {
+                    Ok(events) => {
+                        let json = serde_json::to_string_pretty(&events)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_events failed: {}", e);
+                        Ok(misp_tool_error("get_events", format!("Failed to get events: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+
+    // Tool: get_event_by_id
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_event_by_id",
+        "Retrieve a single event by its ID from MISP",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                // Extract required event_id argument (string)
+                let event_id: String = input.get_id("event_id")?;
+                let include_org_names = input.get_optional_argument::<bool>("include_org_names")?.unwrap_or(false);
+                let result = match client.get_event_by_id(&event_id).await {
+                    Ok(event) => {
+                        let mut value = serde_json::to_value(&event)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        if include_org_names {
+                            client.enrich_event_org_names(&mut value["Event"]).await;
+                        }
+                        Ok(value)
+                    }
+                    Err(e) => Err(e),
+                };
+                get_by_id_result("get_event_by_id", "event", &event_id, result)
+            })
+        }
+    ));
+
+    // Tool 82: get_events_by_ids
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_events_by_ids",
+        "Fetch several events at once, keyed by the requested id; a missing event maps to {\"found\": false}. Arguments: event_ids (array of IDs or UUIDs, at most 100)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let mut event_ids = input.get_ids("event_ids")?;
+                let mut seen = HashSet::new();
+                event_ids.retain(|id| seen.insert(id.clone()));
+                if event_ids.len() > MAX_BULK_EVENT_IDS {
+                    return Err(mcp_core::McpError::invalid_argument(
+                        "event_ids",
+                        format!("At most {} event ids per call, got {}", MAX_BULK_EVENT_IDS, event_ids.len()),
+                    ));
+                }
+
+                let fetches = event_ids.into_iter().map(|event_id| {
+                    let client = client.clone();
+                    async move {
+                        let result = client.get_event_by_id(&event_id).await;
+                        (event_id, result)
+                    }
+                });
+                let results: Vec<_> = futures_util::stream::iter(fetches)
+                    .buffer_unordered(BULK_FETCH_CONCURRENCY)
+                    .collect()
+                    .await;
+
+                let mut events = BTreeMap::new();
+                let mut found = 0;
+                for (event_id, result) in results {
+                    let entry = match result {
+                        Ok(event) => {
+                            found += 1;
+                            serde_json::to_value(&event)
+                                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?
+                        }
+                        Err(MispError::NotFound { .. }) => serde_json::json!({ "found": false, "id": event_id }),
+                        Err(e) => {
+                            error!("get_events_by_ids failed for event {}: {}", event_id, e);
+                            serde_json::json!({ "found": false, "id": event_id, "error": e.to_string() })
+                        }
+                    };
+                    events.insert(event_id, entry);
+                }
+
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "requested": events.len(),
+                    "found": found,
+                    "events": events,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 55: get_event_history
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_event_history",
+        "Audit history of an event: who added, edited, published or deleted it and its attributes/objects, oldest first. Arguments: event_id, optional action (e.g. add, edit) to filter",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let action: Option<String> = input.get_optional_argument("action")?;
+                let entries = match client.get_event_logs(&event_id).await {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        error!("get_event_history failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("get_event_history", format!("Failed to get history of event {}: {}", event_id, e), &e));
+                    }
+                };
+                let history: Vec<serde_json::Value> = entries
+                    .iter()
+                    .filter(|entry| match (&action, &entry.action) {
+                        (Some(wanted), Some(actual)) => actual.eq_ignore_ascii_case(wanted),
+                        (Some(_), None) => false,
+                        (None, _) => true,
+                    })
+                    .map(|entry| serde_json::json!({
+                        "timestamp": entry.created,
+                        "action": entry.action,
+                        "user": entry.email.clone().or_else(|| entry.user_id.clone()),
+                        "org": entry.org,
+                        "model": entry.model,
+                        "model_id": entry.model_id,
+                        "title": entry.title,
+                        "change": entry.change.as_deref().filter(|c| !c.is_empty()),
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "event_id": event_id,
+                    "count": history.len(),
+                    "history": history,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 58: event_summary
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "event_summary",
+        "Markdown brief of an event: info, date, threat level, organisations, attribute counts per category, galaxies and tags. Arguments: event_id",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                match client.get_event_by_id(&event_id).await {
+                    Ok(response) => Ok(ToolResult::text(summary::event_markdown(&response.event))),
+                    Err(e) => {
+                        error!("event_summary failed for event_id {}: {}", event_id, e);
+                        Ok(misp_tool_error("event_summary", format!("Failed to get event {}: {}", event_id, e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool 89: resolve_event_sharing
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "resolve_event_sharing",
+        "Who receives an event: its distribution level and, for sharing group distribution, the group's member organisations and servers. Arguments: event_id",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("resolve_event_sharing failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("resolve_event_sharing", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+                let distribution = event.distribution.as_deref().and_then(|level| level.parse::<misp_types::Distribution>().ok());
+                let owner = event.orgc.as_ref().and_then(|org| org.name.clone());
+                let mut result = serde_json::json!({
+                    "event_id": event.id,
+                    "info": event.info,
+                    "distribution": event.distribution,
+                    "distribution_name": distribution.map(|level| level.name()),
+                    "owner": owner,
+                });
+
+                let recipients = match distribution {
+                    Some(misp_types::Distribution::SharingGroup) => {
+                        let Some(sharing_group_id) = event.sharing_group_id.as_deref().filter(|id| !id.is_empty() && *id != "0") else {
+                            return Ok(tool_error_result(
+                                "resolve_event_sharing",
+                                format!("Event {} is distributed to a sharing group but names none", event_id),
+                                None,
+                            ));
+                        };
+                        let group = match client.get_sharing_group_by_id(sharing_group_id).await {
+                            Ok(group) => group,
+                            Err(e) => {
+                                error!("resolve_event_sharing failed for sharing group {}: {}", sharing_group_id, e);
+                                return Ok(misp_tool_error("resolve_event_sharing", format!("Failed to get sharing group {}: {}", sharing_group_id, e), &e));
+                            }
+                        };
+                        let organisations: Vec<serde_json::Value> = group
+                            .sharing_group_org
+                            .iter()
+                            .map(|member| {
+                                let org = member.organisation.as_ref();
+                                serde_json::json!({
+                                    "id": member.org_id,
+                                    "name": org.and_then(|org| org.name.as_deref()),
+                                    "uuid": org.and_then(|org| org.uuid.as_deref()),
+                                    "can_edit_group": member.extend.unwrap_or(false),
+                                })
+                            })
+                            .collect();
+                        let servers: Vec<serde_json::Value> = group
+                            .sharing_group_server
+                            .iter()
+                            .map(|member| {
+                                let server = member.server.as_ref();
+                                let local = member.server_id.as_deref() == Some("0");
+                                serde_json::json!({
+                                    "id": member.server_id,
+                                    "name": if local { Some("This instance") } else { server.and_then(|server| server.name.as_deref()) },
+                                    "url": server.and_then(|server| server.url.as_deref()),
+                                    "all_orgs": member.all_orgs.unwrap_or(false),
+                                })
+                            })
+                            .collect();
+                        let names: Vec<&str> = group
+                            .sharing_group_org
+                            .iter()
+                            .filter_map(|member| member.organisation.as_ref()?.name.as_deref())
+                            .collect();
+                        result["sharing_group"] = serde_json::json!({
+                            "id": group.sharing_group.id,
+                            "name": group.sharing_group.name,
+                            "releasability": group.sharing_group.releasability,
+                            "active": group.sharing_group.active,
+                        });
+                        result["organisations"] = serde_json::json!(organisations);
+                        result["servers"] = serde_json::json!(servers);
+                        // all_orgs on a server opens the group to every organisation there
+                        if group.sharing_group_server.iter().any(|member| member.all_orgs == Some(true)) {
+                            format!("The organisations of sharing group {} ({}), plus every organisation on the servers marked all_orgs",
+                                group.sharing_group.name.as_deref().unwrap_or(sharing_group_id), names.join(", "))
+                        } else {
+                            format!("The organisations of sharing group {}: {}",
+                                group.sharing_group.name.as_deref().unwrap_or(sharing_group_id), names.join(", "))
+                        }
+                    }
+                    Some(misp_types::Distribution::YourOrganisationOnly) => {
+                        format!("Only the owning organisation ({})", owner.as_deref().unwrap_or("unknown"))
+                    }
+                    Some(misp_types::Distribution::ThisCommunityOnly) => {
+                        "Every organisation on this MISP instance; not synchronised to other servers".to_string()
+                    }
+                    Some(misp_types::Distribution::ConnectedCommunities) => {
+                        "Every organisation on this instance and on directly connected servers; those servers keep it at this community only".to_string()
+                    }
+                    Some(misp_types::Distribution::AllCommunities) => {
+                        "Every organisation on this instance and on all servers it synchronises to, transitively".to_string()
+                    }
+                    Some(misp_types::Distribution::InheritEvent) | None => {
+                        format!("Unknown: unexpected distribution {:?}", event.distribution)
+                    }
+                };
+                result["recipients"] = serde_json::json!(recipients);
+                let json = serde_json::to_string_pretty(&result)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 73: related_events
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "related_events",
+        "List the events correlating with an event as a flat array: ID, UUID, info, date, creator org, threat level and the number of correlating attributes, most correlated first. Arguments: event_id",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("related_events failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("related_events", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+
+                // related event ID -> IDs of our attributes correlating with it
+                let mut correlations: std::collections::HashMap<&str, std::collections::BTreeSet<&str>> = std::collections::HashMap::new();
+                let object_attributes = event.object.iter().flat_map(|object| object.attributes.iter().flatten());
+                for attribute in event.attribute.iter().chain(object_attributes) {
+                    for related in attribute.related_attribute.iter().flatten() {
+                        correlations.entry(related.event_id.as_str()).or_default().insert(attribute.id.as_str());
+                    }
+                }
+
+                // Only the top level is kept: RelatedEvent entries nest whole events
+                let mut related: Vec<serde_json::Value> = event
+                    .related_event
+                    .iter()
+                    .map(|entry| {
+                        let related = &entry.event;
+                        serde_json::json!({
+                            "id": related.id,
+                            "uuid": related.uuid,
+                            "info": related.info,
+                            "date": related.date,
+                            "org": related.orgc.as_ref().and_then(|org| org.name.clone()),
+                            "threat_level_id": related.threat_level_id,
+                            "published": related.published,
+                            "correlating_attributes": correlations.get(related.id.as_str()).map_or(0, |ids| ids.len()),
+                        })
+                    })
+                    .collect();
+                related.sort_by_key(|entry| std::cmp::Reverse(entry["correlating_attributes"].as_u64().unwrap_or(0)));
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "event_id": event_id,
+                    "count": related.len(),
+                    "related_events": related,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 62: event_attribute_breakdown
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "event_attribute_breakdown",
+        "Count an event's attributes per type and per category (e.g. {\"ip-src\": 12, \"domain\": 5}). Arguments: event_id, optional include_objects (default true: count attributes inside objects too)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let include_objects = input.get_optional_argument::<bool>("include_objects")?.unwrap_or(true);
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("event_attribute_breakdown failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("event_attribute_breakdown", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+                let object_attributes = event
+                    .object
+                    .iter()
+                    .filter(|_| include_objects)
+                    .flat_map(|object| object.attributes.iter().flatten());
+                let mut by_type: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+                let mut by_category: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+                let mut total = 0;
+                for attribute in event.attribute.iter().chain(object_attributes).filter(|a| !a.deleted) {
+                    *by_type.entry(attribute.attribute_type.clone()).or_default() += 1;
+                    *by_category.entry(attribute.category.clone()).or_default() += 1;
+                    total += 1;
+                }
+                // Sorted keys keep the output stable between calls
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "event_id": event_id,
+                    "total": total,
+                    "by_type": by_type.into_iter().collect::<std::collections::BTreeMap<_, _>>(),
+                    "by_category": by_category.into_iter().collect::<std::collections::BTreeMap<_, _>>(),
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 70: find_duplicate_attributes
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "find_duplicate_attributes",
+        "Report an event's duplicate attributes: groups sharing the same type and value, with the attribute to keep (the oldest) and the IDs of the others to delete. Arguments: event_id, optional include_objects (default false: object attributes legitimately repeat values across objects)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let include_objects = input.get_optional_argument::<bool>("include_objects")?.unwrap_or(false);
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("find_duplicate_attributes failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("find_duplicate_attributes", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+                let object_attributes = event
+                    .object
+                    .iter()
+                    .filter(|_| include_objects)
+                    .flat_map(|object| object.attributes.iter().flatten());
+                let mut groups: std::collections::BTreeMap<(&str, &str), Vec<&misp_types::Attribute>> = std::collections::BTreeMap::new();
+                for attribute in event.attribute.iter().chain(object_attributes).filter(|a| !a.deleted) {
+                    groups.entry((attribute.attribute_type.as_str(), attribute.value.as_str())).or_default().push(attribute);
+                }
+
+                let mut duplicates = Vec::new();
+                let mut removable = 0;
+                for ((attribute_type, value), mut attributes) in groups {
+                    if attributes.len() < 2 {
+                        continue;
+                    }
+                    // Keep the oldest copy: lowest timestamp, then lowest ID
+                    attributes.sort_by_key(|a| {
+                        (
+                            a.timestamp.as_deref().and_then(|t| t.parse::<i64>().ok()).unwrap_or(i64::MAX),
+                            a.id.parse::<u64>().unwrap_or(u64::MAX),
+                        )
+                    });
+                    let delete_ids: Vec<&str> = attributes[1..].iter().map(|a| a.id.as_str()).collect();
+                    removable += delete_ids.len();
+                    let copies: Vec<serde_json::Value> = attributes
+                        .iter()
+                        .map(|a| serde_json::json!({
+                            "id": a.id,
+                            "uuid": a.uuid,
+                            "object_id": a.object_id,
+                            "category": a.category,
+                            "to_ids": a.to_ids,
+                            "timestamp": a.timestamp,
+                            "comment": a.comment,
+                        }))
+                        .collect();
+                    duplicates.push(serde_json::json!({
+                        "type": attribute_type,
+                        "value": value,
+                        "count": attributes.len(),
+                        "keep_id": attributes[0].id,
+                        "delete_ids": delete_ids,
+                        "attributes": copies,
+                    }));
+                }
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "event_id": event_id,
+                    "duplicate_groups": duplicates.len(),
+                    "removable_attributes": removable,
+                    "duplicates": duplicates,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 63: prepublish_check
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "prepublish_check",
+        "Check an event's attributes against the enabled warninglists before publishing and list the ones that hit (likely false positives). Arguments: event_id",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("prepublish_check failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("prepublish_check", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+                let attributes: Vec<&misp_types::Attribute> = event
+                    .attribute
+                    .iter()
+                    .chain(event.object.iter().flat_map(|object| object.attributes.iter().flatten()))
+                    .filter(|a| !a.deleted)
+                    .collect();
+                let mut values: Vec<String> = attributes.iter().flat_map(|a| warninglist_values(a)).collect();
+                values.sort();
+                values.dedup();
+
+                let hits = if values.is_empty() {
+                    misp_types::CheckWarninglistValuesResponse::new()
+                } else {
+                    match client.check_warninglist_values(&values).await {
+                        Ok(hits) => hits,
+                        Err(e) => {
+                            error!("prepublish_check failed for event_id {}: {}", event_id, e);
+                            return Ok(misp_tool_error("prepublish_check", format!("Failed to check values of event {} against warninglists: {}", event_id, e), &e));
+                        }
+                    }
+                };
+
+                let flagged: Vec<serde_json::Value> = attributes
+                    .iter()
+                    .filter_map(|attribute| {
+                        let warninglists = warninglist_hits(attribute, &hits);
+                        if warninglists.is_empty() {
+                            return None;
+                        }
+                        Some(serde_json::json!({
+                            "id": attribute.id,
+                            "uuid": attribute.uuid,
+                            "object_id": attribute.object_id,
+                            "type": attribute.attribute_type,
+                            "category": attribute.category,
+                            "value": attribute.value,
+                            "to_ids": attribute.to_ids,
+                            "warninglists": warninglists,
+                        }))
+                    })
+                    .collect();
+                let mut summary = if flagged.is_empty() {
+                    format!("No attribute of event {} hits a warninglist ({} values checked).", event_id, values.len())
+                } else {
+                    format!("{} of {} attributes of event {} hit warninglists:", flagged.len(), attributes.len(), event_id)
+                };
+                for entry in &flagged {
+                    let names: Vec<&str> = entry["warninglists"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|hit| hit["name"].as_str())
+                        .collect();
+                    summary.push_str(&format!(
+                        "\n- {} ({}): {}",
+                        entry["value"].as_str().unwrap_or_default(),
+                        entry["type"].as_str().unwrap_or_default(),
+                        names.join(", ")
+                    ));
+                }
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "event_id": event_id,
+                    "checked_values": values.len(),
+                    "flagged_count": flagged.len(),
+                    "flagged": flagged,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                // JSON stays the first block for clients that parse content[0]
+                Ok(ToolResult::text(json).with_blocks(vec![ToolContent::text(summary)]))
+            })
+        }
+    ));
+
+    // Tool 78: event_warninglist_coverage
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "event_warninglist_coverage",
+        "Share of an event's attributes that match the enabled warninglists, with a count per warninglist. Arguments: event_id, optional to_ids_only (default true: only count attributes flagged for detection)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let to_ids_only = input.get_optional_argument::<bool>("to_ids_only")?.unwrap_or(true);
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("event_warninglist_coverage failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("event_warninglist_coverage", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+                let attributes: Vec<&misp_types::Attribute> = event
+                    .attribute
+                    .iter()
+                    .chain(event.object.iter().flat_map(|object| object.attributes.iter().flatten()))
+                    .filter(|a| !a.deleted && (!to_ids_only || a.to_ids))
+                    .collect();
+                let mut values: Vec<String> = attributes.iter().flat_map(|a| warninglist_values(a)).collect();
+                values.sort();
+                values.dedup();
+
+                let hits = if values.is_empty() {
+                    misp_types::CheckWarninglistValuesResponse::new()
+                } else {
+                    match client.check_warninglist_values(&values).await {
+                        Ok(hits) => hits,
+                        Err(e) => {
+                            error!("event_warninglist_coverage failed for event_id {}: {}", event_id, e);
+                            return Ok(misp_tool_error("event_warninglist_coverage", format!("Failed to check values of event {} against warninglists: {}", event_id, e), &e));
+                        }
+                    }
+                };
+
+                let mut hit_count = 0;
+                let mut by_warninglist: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+                for attribute in &attributes {
+                    let warninglists = warninglist_hits(attribute, &hits);
+                    if !warninglists.is_empty() {
+                        hit_count += 1;
+                    }
+                    for hit in warninglists {
+                        *by_warninglist.entry(hit.name.as_str()).or_default() += 1;
+                    }
+                }
+                // One decimal is enough for a headline figure
+                let coverage_pct = if attributes.is_empty() {
+                    0.0
+                } else {
+                    (hit_count as f64 * 1000.0 / attributes.len() as f64).round() / 10.0
+                };
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "event_id": event_id,
+                    "to_ids_only": to_ids_only,
+                    "checked": attributes.len(),
+                    "hits": hit_count,
+                    "coverage_pct": coverage_pct,
+                    "by_warninglist": by_warninglist,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 84: export_detection_values
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "export_detection_values",
+        "Rule-ready indicator lists from an event's attributes flagged for detection (to_ids), object attributes included: deduplicated arrays of ips, domains, urls, emails and filenames, hashes per algorithm, and other values per MISP type. Composite values such as filename|sha256 are split. Arguments: event_id, optional to_ids_only (default true)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let to_ids_only = input.get_optional_argument::<bool>("to_ids_only")?.unwrap_or(true);
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("export_detection_values failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("export_detection_values", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+
+                let mut groups: BTreeMap<&str, std::collections::BTreeSet<&str>> = BTreeMap::new();
+                let mut exported = 0;
+                for attribute in event
+                    .attribute
+                    .iter()
+                    .chain(event.object.iter().flat_map(|object| object.attributes.iter().flatten()))
+                    .filter(|a| !a.deleted && (!to_ids_only || a.to_ids))
+                {
+                    exported += 1;
+                    for (group, value) in detection_values(attribute) {
+                        groups.entry(group).or_default().insert(value);
+                    }
+                }
+
+                let mut result = serde_json::json!({
+                    "event_id": event_id,
+                    "info": event.info,
+                    "to_ids_only": to_ids_only,
+                    "attributes": exported,
+                });
+                for group in ["ips", "domains", "urls", "emails", "filenames"] {
+                    result[group] = serde_json::json!(groups.remove(group).unwrap_or_default());
+                }
+                let hashes: BTreeMap<&str, _> = DETECTION_HASH_TYPES
+                    .iter()
+                    .filter_map(|hash_type| groups.remove(hash_type).map(|values| (*hash_type, values)))
+                    .collect();
+                result["hashes"] = serde_json::json!(hashes);
+                result["other"] = serde_json::json!(groups);
+
+                let json = serde_json::to_string_pretty(&result)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 41: event_timeline
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "event_timeline",
+        "Build a chronological timeline for an event: attribute first/last seen, sightings, event reports and publication, sorted by time",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let event = match client.get_event_by_id(&event_id).await {
+                    Ok(response) => response.event,
+                    Err(e) => {
+                        error!("event_timeline failed for event_id {}: {}", event_id, e);
+                        return Ok(misp_tool_error("event_timeline", format!("Failed to get event {}: {}", event_id, e), &e));
+                    }
+                };
+                input.report_progress(25.0, Some(100.0));
+                // A timeline without sightings is still useful, so don't fail on them
+                let sightings = match client.get_sightings_by_event_id(&event_id).await {
+                    Ok(response) => response.sightings.unwrap_or_default(),
+                    Err(e) => {
+                        warn!("event_timeline: could not fetch sightings for event {}: {}", event_id, e);
+                        Vec::new()
+                    }
+                };
+                input.report_progress(50.0, Some(100.0));
+                let entries = timeline::build_timeline(&event, &sightings);
+                input.report_progress(100.0, Some(100.0));
+                let json = serde_json::to_string_pretty(&entries)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool: search_events
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "search_events",
+        "Search for events using POST /events/index with flexible filters. Arguments: request_json (stringified EventIndexRequest), optional strict (default false: reject unknown fields instead of ignoring them)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                // Accepts "request_json" (stringified EventIndexRequest) and an optional "strict"
+                let request_json: String = input.get_argument("request_json")?;
+                if input.get_optional_argument::<bool>("strict")?.unwrap_or(false) {
+                    let value: serde_json::Value = serde_json::from_str(&request_json)
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                    check_strict_fields("EventIndexRequest", "request_json", &value)?;
+                }
+                let request: EventIndexRequest = serde_json::from_str(&request_json)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                match client.search_events(&request).await {
+                    Ok(events) => {
+                        let json = serde_json::to_string_pretty(&events)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("search_events failed: {}", e);
+                        Ok(misp_tool_error("search_events", format!("Failed to search events: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    // Tool: events_rest_search
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "events_rest_search",
+        "Search events using the /events/restSearch endpoint. Arguments: EventsRestSearchRequest fields, either flat or under request_json (an object or stringified JSON); optional summarize (default false: true returns the total and breakdowns by org, tag and attribute type plus the date range instead of the events)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let summarize = input.get_optional_argument::<bool>("summarize")?.unwrap_or(false);
+                input.arguments.remove("summarize");
+                let params = input.get_request_either::<EventsRestSearchRequest>("request_json")?;
+                match client.events_rest_search(&params).await {
+                    Ok(resp) => {
+                        let json = if summarize {
+                            serde_json::to_string_pretty(&summarize_events(&resp.response))?
+                        } else {
+                            serde_json::to_string_pretty(&resp)?
+                        };
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => Ok(misp_tool_error("events_rest_search", format!("Failed to search events: {}", e), &e)),
+                }
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    // Tool 45: search_published_events
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "search_published_events",
+        "Search published events only (for sharing with partners). Same arguments as events_rest_search, but `published` is always forced to true",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let mut params = input.get_request_either::<EventsRestSearchRequest>("request_json")?;
+                if params.published == Some(false) {
+                    warn!("search_published_events: ignoring published=false in request");
+                }
+                params.published = Some(true);
+                match client.events_rest_search(&params).await {
+                    Ok(mut resp) => {
+                        // Don't rely on the filter alone: drafts must never leave through this tool
+                        resp.response.retain(|wrapper| wrapper.event.published == Some(true));
+                        let json = serde_json::to_string_pretty(&resp)?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => Ok(misp_tool_error("search_published_events", format!("Failed to search published events: {}", e), &e)),
+                }
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    // Tool 65: extract_iocs
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "extract_iocs",
+        "Extract the indicator values of matching events as a flat, deduplicated [{type, value}] list (object attributes included). Same arguments as events_rest_search, plus optional to_ids_only (default false)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let to_ids_only = input.get_optional_argument::<bool>("to_ids_only")?.unwrap_or(false);
+                input.arguments.remove("to_ids_only");
+                let params = input.get_request_either::<EventsRestSearchRequest>("request_json")?;
+                let response = match client.events_rest_search(&params).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        error!("extract_iocs failed: {}", e);
+                        return Ok(misp_tool_error("extract_iocs", format!("Failed to search events: {}", e), &e));
+                    }
+                };
+                let mut seen = std::collections::HashSet::new();
+                let mut iocs = Vec::new();
+                for event in response.response.iter().map(|wrapper| &wrapper.event) {
+                    let attributes = event
+                        .attribute
+                        .iter()
+                        .chain(event.object.iter().flat_map(|object| object.attributes.iter().flatten()));
+                    for attribute in attributes.filter(|a| !a.deleted && (a.to_ids || !to_ids_only)) {
+                        if seen.insert((attribute.attribute_type.as_str(), attribute.value.as_str())) {
+                            iocs.push(serde_json::json!({ "type": attribute.attribute_type, "value": attribute.value }));
+                        }
+                    }
+                }
+                let json = serde_json::to_string_pretty(&iocs)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    // Tool 50: recent_changes
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "recent_changes",
+        "List events modified in the last N days, most recent first. Arguments: days (default 1), optional limit. Returns id, uuid, modification time and published flag only",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let days = input.get_optional_argument::<u32>("days")?.unwrap_or(1);
+                if days == 0 {
+                    return Err(mcp_core::McpError::invalid_argument("days", "days must be at least 1"));
+                }
+                let since = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+                let params = EventsRestSearchRequest {
+                    timestamp: Some(since.timestamp().to_string()),
+                    limit: input.get_optional_argument("limit")?,
+                    ..Default::default()
+                };
+                let mut events: Vec<misp_types::MinimalEvent> = match client.events_rest_search_minimal(&params).await {
+                    Ok(response) => response.response.into_iter().map(|wrapper| wrapper.event).collect(),
+                    Err(e) => {
+                        error!("recent_changes failed for {} days: {}", days, e);
+                        return Ok(misp_tool_error("recent_changes", format!("Failed to search recently modified events: {}", e), &e));
+                    }
+                };
+                let modified_at = |event: &misp_types::MinimalEvent| {
+                    event.timestamp.as_deref().and_then(|ts| ts.parse::<i64>().ok()).unwrap_or(0)
+                };
+                events.sort_by_key(|event| std::cmp::Reverse(modified_at(event)));
+
+                let entries: Vec<serde_json::Value> = events
+                    .iter()
+                    .map(|event| serde_json::json!({
+                        "id": event.id,
+                        "uuid": event.uuid,
+                        "modified": chrono::DateTime::from_timestamp(modified_at(event), 0)
+                            .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+                        "published": event.published,
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "days": days,
+                    "since": since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                    "count": entries.len(),
+                    "events": entries,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    // Tool 59: count_events
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "count_events",
+        "Count events, optionally for one organisation, without returning them. Arguments: optional org (name or ID), published, from and to (YYYY-MM-DD)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let org: Option<String> = input.get_optional_argument("org")?;
+                let params = EventsRestSearchRequest {
+                    org: org.clone(),
+                    published: input.get_optional_argument("published")?,
+                    from: input.get_optional_argument("from")?,
+                    to: input.get_optional_argument("to")?,
+                    ..Default::default()
+                };
+                match client.count_events(&params).await {
+                    Ok(count) => {
+                        let json = serde_json::to_string_pretty(&serde_json::json!({
+                            "org": org,
+                            "count": count,
+                        }))
+                        .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("count_events failed for org {:?}: {}", org, e);
+                        Ok(misp_tool_error("count_events", format!("Failed to count events: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    // Tool 52: normalize_indicators
+    add_permitted_tool(server, tools, Tool::new(
+        "normalize_indicators",
+        "Refang defanged indicators (hxxp://, 1[.]2[.]3[.]4, [at], (dot), ...) and return each original value with its normalized form. Argument: values (array of strings)",
+        move |input: ToolInput| {
+            Box::pin(async move {
+                let values: Vec<String> = input.get_argument("values")?;
+                let results: Vec<serde_json::Value> = values
+                    .iter()
+                    .map(|original| {
+                        let normalized = refang_indicator(original);
+                        serde_json::json!({
+                            "original": original,
+                            "normalized": normalized,
+                            "changed": &normalized != original,
+                        })
+                    })
+                    .collect();
+                let json = serde_json::to_string_pretty(&results)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 46: search_indicators
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "search_indicators",
+        "Search attributes for a list of indicator values in one restSearch call and return the matches grouped per input value. Arguments: values (array of strings), optional type, category, to_ids, limit, refang (default true: defanged values such as hxxp://evil[.]com are searched refanged) and with_context (default true: each match carries its event UUID and the event's tags)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let values: Vec<String> = input.get_argument("values")?;
+                let refang = input.get_optional_argument::<bool>("refang")?.unwrap_or(true);
+                let with_context = input.get_optional_argument::<bool>("with_context")?.unwrap_or(true);
+                // Keep the analyst's order but drop blanks and repeats
+                let mut seen = std::collections::HashSet::new();
+                let mut originals = std::collections::HashMap::new();
+                let values: Vec<String> = values
+                    .into_iter()
+                    .map(|v| {
+                        let value = if refang { refang_indicator(&v) } else { v.trim().to_string() };
+                        if value != v.trim() {
+                            originals.entry(value.clone()).or_insert_with(|| v.trim().to_string());
+                        }
+                        value
+                    })
+                    .filter(|v| !v.is_empty() && seen.insert(v.to_lowercase()))
+                    .collect();
+                if values.is_empty() {
+                    return Err(mcp_core::McpError::invalid_argument("values", "values must contain at least one non-empty indicator"));
+                }
+
+                let filter = AttributeRestSearchRequest {
+                    value: Some(serde_json::json!(values)),
+                    attribute_type: input.get_optional_argument("type")?,
+                    category: input.get_optional_argument("category")?,
+                    to_ids: input.get_optional_argument("to_ids")?,
+                    limit: input.get_optional_argument("limit")?,
+                    include_event_uuid: with_context.then_some(true),
+                    include_event_tags: with_context.then_some(true),
+                    ..Default::default()
+                };
+                let attributes = match client.attributes_rest_search(&filter).await {
+                    Ok(response) => response.response.attribute,
+                    Err(e) => {
+                        error!("search_indicators failed for {} values: {}", values.len(), e);
+                        return Ok(misp_tool_error("search_indicators", format!("Failed to search indicators: {}", e), &e));
+                    }
+                };
+
+                // MISP matches case-insensitively and on either half of composite
+                // values (e.g. ip-dst|port), so group the same way
+                let mut groups: Vec<Vec<&misp_types::Attribute>> = vec![Vec::new(); values.len()];
+                for attribute in &attributes {
+                    for (index, value) in values.iter().enumerate() {
+                        let hit = attribute.value.eq_ignore_ascii_case(value)
+                            || attribute.value.split('|').any(|part| part.eq_ignore_ascii_case(value));
+                        if hit {
+                            groups[index].push(attribute);
+                        }
+                    }
+                }
+
+                let matched = groups.iter().filter(|g| !g.is_empty()).count();
+                let results: Vec<serde_json::Value> = values
+                    .iter()
+                    .zip(&groups)
+                    .map(|(value, found)| {
+                        let mut result = serde_json::json!({
+                            "value": value,
+                            "count": found.len(),
+                            "attributes": found,
+                        });
+                        if let Some(original) = originals.get(value) {
+                            result["original"] = serde_json::json!(original);
+                        }
+                        result
+                    })
+                    .collect();
+                let mut summary = format!("{} of {} values matched", matched, values.len());
+                summary.push_str(if matched > 0 { ":" } else { "." });
+                for (value, found) in values.iter().zip(&groups).filter(|(_, found)| !found.is_empty()) {
+                    let mut events: Vec<&str> = found.iter().map(|a| a.event_id.as_str()).collect();
+                    events.sort_unstable();
+                    events.dedup();
+                    summary.push_str(&format!(
+                        "\n- {}: {} attribute(s) in event(s) {}",
+                        value,
+                        found.len(),
+                        events.join(", ")
+                    ));
+                }
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "searched": values.len(),
+                    "matched": matched,
+                    "results": results,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                // JSON stays the first block for clients that parse content[0]
+                Ok(ToolResult::text(json).with_blocks(vec![ToolContent::text(summary)]))
+            })
+        }
+    ));
+
+    // Tool 83: search_by_value
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "search_by_value",
+        "Search for one pasted indicator without knowing its MISP type: the type (ip-dst, domain, md5/sha1/sha256/sha512, email, url) is detected from the value and used to narrow the search. Returns the detected type, the matching attributes and their events. Arguments: value (defanged values are accepted), optional type (overrides detection), to_ids, limit",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let original: String = input.get_argument("value")?;
+                let value = refang_indicator(&original);
+                if value.is_empty() {
+                    return Err(mcp_core::McpError::invalid_argument("value", "value must be a non-empty indicator"));
+                }
+                let detected_type = detect_indicator_type(&value);
+                let searched_type: Option<String> = input
+                    .get_optional_argument("type")?
+                    .or_else(|| detected_type.map(str::to_string));
+
+                let filter = AttributeRestSearchRequest {
+                    value: Some(serde_json::json!(value)),
+                    attribute_type: searched_type.clone(),
+                    to_ids: input.get_optional_argument("to_ids")?,
+                    limit: input.get_optional_argument("limit")?,
+                    include_event_uuid: Some(true),
+                    include_event_tags: Some(true),
+                    ..Default::default()
+                };
+                let attributes = match client.attributes_rest_search(&filter).await {
+                    Ok(response) => response.response.attribute,
+                    Err(e) => {
+                        error!("search_by_value failed for {}: {}", value, e);
+                        return Ok(misp_tool_error("search_by_value", format!("Failed to search for {}: {}", value, e), &e));
+                    }
+                };
+                info!(
+                    "search_by_value: {} (detected {}) matched {} attribute(s)",
+                    value,
+                    detected_type.unwrap_or("no type"),
+                    attributes.len()
+                );
+
+                let mut events: Vec<&str> = attributes.iter().map(|a| a.event_id.as_str()).collect();
+                events.sort_unstable();
+                events.dedup();
+                let mut result = serde_json::json!({
+                    "value": value,
+                    "detected_type": detected_type,
+                    "searched_type": searched_type,
+                    "count": attributes.len(),
+                    "event_ids": events,
+                    "attributes": attributes,
+                });
+                if value != original.trim() {
+                    result["original"] = serde_json::json!(original.trim());
+                }
+                let json = serde_json::to_string_pretty(&result)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 72: pivot_from_attribute
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "pivot_from_attribute",
+        "Pivot from an indicator value: find the events containing it and return the other attributes of those events as a graph of event and attribute nodes linked by edges. Arguments: value, optional type (of the pivot value), types (array: only return related attributes of these types, e.g. [\"ip-dst\", \"sha256\"]), max_events (default 5, at most 50)",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let value: String = input.get_argument("value")?;
+                let value = refang_indicator(&value);
+                if value.is_empty() {
+                    return Err(mcp_core::McpError::invalid_argument("value", "value must not be empty"));
+                }
+                let pivot_type: Option<String> = input.get_optional_argument("type")?;
+                let types: Option<Vec<String>> = input.get_optional_argument("types")?;
+                let max_events = input.get_optional_argument::<usize>("max_events")?.unwrap_or(5);
+                if !(1..=50).contains(&max_events) {
+                    return Err(mcp_core::McpError::invalid_argument("max_events", "max_events must be between 1 and 50"));
+                }
+
+                let filter = AttributeRestSearchRequest {
+                    value: Some(serde_json::json!(value)),
+                    attribute_type: pivot_type.clone(),
+                    ..Default::default()
+                };
+                let matches = match client.attributes_rest_search(&filter).await {
+                    Ok(response) => response.response.attribute,
+                    Err(e) => {
+                        error!("pivot_from_attribute failed for value {}: {}", value, e);
+                        return Ok(misp_tool_error("pivot_from_attribute", format!("Failed to search attributes: {}", e), &e));
+                    }
+                };
+                let mut event_ids: Vec<&str> = Vec::new();
+                for attribute in &matches {
+                    if !event_ids.contains(&attribute.event_id.as_str()) {
+                        event_ids.push(&attribute.event_id);
+                    }
+                }
+                let events_found = event_ids.len();
+                event_ids.truncate(max_events);
+
+                let is_pivot = |attribute: &misp_types::Attribute| {
+                    (attribute.value.eq_ignore_ascii_case(&value)
+                        || attribute.value.split('|').any(|part| part.eq_ignore_ascii_case(&value)))
+                        && pivot_type.as_ref().is_none_or(|t| *t == attribute.attribute_type)
+                };
+                let pivot_id = format!("value:{}", value);
+                let mut nodes = vec![serde_json::json!({
+                    "id": pivot_id,
+                    "kind": "pivot",
+                    "type": pivot_type,
+                    "value": value,
+                })];
+                let mut edges = Vec::new();
+                let mut attribute_nodes = std::collections::HashSet::new();
+                for event_id in &event_ids {
+                    let event = match client.get_event_by_id(event_id).await {
+                        Ok(response) => response.event,
+                        Err(e) => {
+                            error!("pivot_from_attribute failed for event_id {}: {}", event_id, e);
+                            return Ok(misp_tool_error("pivot_from_attribute", format!("Failed to get event {}: {}", event_id, e), &e));
+                        }
+                    };
+                    let event_node = format!("event:{}", event.id);
+                    nodes.push(serde_json::json!({
+                        "id": event_node,
+                        "kind": "event",
+                        "info": event.info,
+                        "date": event.date,
+                    }));
+                    edges.push(serde_json::json!({ "source": pivot_id, "target": event_node, "relation": "found_in" }));
+
+                    // Values are nodes of their own so a value seen in several
+                    // events links them; each edge keeps the attribute ID
+                    let in_objects = event
+                        .object
+                        .iter()
+                        .flat_map(|object| object.attributes.iter().flatten().map(move |a| (a, Some(object.name.as_str()))));
+                    for (attribute, object) in event.attribute.iter().map(|a| (a, None)).chain(in_objects) {
+                        if attribute.deleted || is_pivot(attribute) {
+                            continue;
+                        }
+                        if types.as_ref().is_some_and(|types| !types.contains(&attribute.attribute_type)) {
+                            continue;
+                        }
+                        let node = format!("attribute:{}:{}", attribute.attribute_type, attribute.value);
+                        if attribute_nodes.insert(node.clone()) {
+                            nodes.push(serde_json::json!({
+                                "id": node,
+                                "kind": "attribute",
+                                "type": attribute.attribute_type,
+                                "value": attribute.value,
+                            }));
+                        }
+                        let mut edge = serde_json::json!({
+                            "source": event_node,
+                            "target": node,
+                            "relation": "contains",
+                            "attribute_id": attribute.id,
+                            "to_ids": attribute.to_ids,
+                        });
+                        if let Some(object) = object {
+                            edge["object"] = serde_json::json!(object);
+                        }
+                        edges.push(edge);
+                    }
+                }
+
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "value": value,
+                    "events_found": events_found,
+                    "events_expanded": event_ids.len(),
+                    "truncated": events_found > event_ids.len(),
+                    "nodes": nodes,
+                    "edges": edges,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ).with_concurrency_limit(EXPENSIVE_SEARCH_CONCURRENCY));
+
+    // Tool 57: search_attributes_with_sightings
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "search_attributes_with_sightings",
+        "Search attributes and return each match with its sightings inline, plus per-attribute counts of sightings, false positives and expirations. Arguments: value (string or array of strings), optional type, category, eventid, to_ids, limit",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let value: serde_json::Value = input.get_argument("value")?;
+                let filter = AttributeRestSearchRequest {
+                    value: Some(value),
+                    attribute_type: input.get_optional_argument("type")?,
+                    category: input.get_optional_argument("category")?,
+                    eventid: input.get_optional_id("eventid")?,
+                    to_ids: input.get_optional_argument("to_ids")?,
+                    limit: input.get_optional_argument("limit")?,
+                    include_sightings: Some(true),
+                    ..Default::default()
+                };
+                let attributes = match client.attributes_rest_search(&filter).await {
+                    Ok(response) => response.response.attribute,
+                    Err(e) => {
+                        error!("search_attributes_with_sightings failed: {}", e);
+                        return Ok(misp_tool_error("search_attributes_with_sightings", format!("Failed to search attributes: {}", e), &e));
+                    }
+                };
+
+                // MISP omits the Sighting key for unsighted attributes; always
+                // return an array so callers need not special-case it
+                let results: Vec<serde_json::Value> = attributes
+                    .into_iter()
+                    .map(|mut attribute| {
+                        let sightings = attribute.sighting.take().unwrap_or_default();
+                        let count_of = |kind: &str| {
+                            sightings
+                                .iter()
+                                .filter(|s| s.type_.as_deref().unwrap_or("0") == kind)
+                                .count()
+                        };
+                        let summary = serde_json::json!({
+                            "sightings": count_of("0"),
+                            "false_positives": count_of("1"),
+                            "expirations": count_of("2"),
+                            "last_seen": sightings.iter().filter_map(|s| s.date_sighting.as_deref()).max(),
+                        });
+                        attribute.sighting = Some(sightings);
+                        serde_json::json!({
+                            "attribute": attribute,
+                            "sighting_summary": summary,
+                        })
+                    })
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "count": results.len(),
+                    "results": results,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 54: describe_tools
+    let catalog = server.tool_catalog();
+    add_permitted_tool(server, tools, Tool::new(
+        "describe_tools",
+        "Describe every tool this server offers: name, description and input schema (the tools/list catalog as a callable tool)",
+        move |_input: ToolInput| {
+            let catalog = catalog.clone();
+            Box::pin(async move {
+                let tools = catalog.list();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "count": tools.len(),
+                    "tools": tools,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 40: get_request_schema
+    add_permitted_tool(server, tools, Tool::new(
+        "get_request_schema",
+        "Return the JSON Schema of a request type used by the search/add tools (e.g. EventsRestSearchRequest, AttributeRestSearchRequest)",
+        move |input: ToolInput| {
+            Box::pin(async move {
+                let request_type: String = input.get_argument("request_type")?;
+                let schema = misp_types::schema::request_schema(&request_type).ok_or_else(|| {
+                    mcp_core::McpError::invalid_argument(
+                        "request_type",
+                        format!(
+                            "Unknown request type '{}'; expected one of: {}",
+                            request_type,
+                            misp_types::schema::REQUEST_TYPES.join(", ")
+                        ),
+                    )
+                })?;
+                let json = serde_json::to_string_pretty(&schema)
+                    .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+    // Tool 39: get_object_templates
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_object_templates",
+        "List MISP object templates (id, uuid, name, version, meta-category, requirements). Optional `name` filters to templates whose name contains it",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let name: Option<String> = input.get_optional_argument("name")?;
+                match client.get_object_templates().await {
+                    Ok(entries) => {
+                        let templates: Vec<_> = entries
+                            .into_iter()
+                            .map(|entry| entry.object_template)
+                            .filter(|t| name.as_deref().is_none_or(|n| t.name.to_lowercase().contains(&n.to_lowercase())))
+                            .collect();
+                        let json = serde_json::to_string_pretty(&templates)
+                            .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                        Ok(ToolResult::text(json))
+                    }
+                    Err(e) => {
+                        error!("get_object_templates failed: {}", e);
+                        Ok(misp_tool_error("get_object_templates", format!("Failed to get object templates: {}", e), &e))
+                    }
+                }
+            })
+        }
+    ));
+
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_object",
+        "Retrieve a specific object by ID or UUID from MISP",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let object_id: String = input.get_id("object_id")?;
+                get_by_id_result("get_object", "object", &object_id, client.get_object_by_id(&object_id).await)
+            })
+        }
+    ));
+
+    // Tool 61: get_event_objects
+    let clients_clone = clients.clone();
+    add_permitted_tool(server, tools, Tool::new(
+        "get_event_objects",
+        "List the objects of one event with their template name and attributes, without fetching the whole event. Arguments: event_id",
+        move |mut input: ToolInput| {
+            let clients = clients_clone.clone();
+            Box::pin(async move {
+                let client = clients.select(&mut input)?;
+                let event_id: String = input.get_id("event_id")?;
+                let params = ObjectsRestSearchRequest {
+                    eventid: Some(event_id.clone()),
+                    ..Default::default()
+                };
+                let objects = match client.objects_rest_search(&params).await {
+                    Ok(objects) => objects,
+                    Err(e) => {
+                        error!("get_event_objects failed for event_id {}: {}", event_id, e);
+                        return Ok(tool_error_result(
+                            "get_event_objects",
+                            format!("Failed to get objects of event {}: {}", event_id, e),
+                            e.downcast_ref::<MispError>().and_then(MispError::status_code),
+                        ));
+                    }
+                };
+                let objects: Vec<serde_json::Value> = objects
+                    .iter()
+                    .map(|object| serde_json::json!({
+                        "id": object.id,
+                        "uuid": object.uuid,
+                        "template": object.name,
+                        "meta_category": object.meta_category,
+                        "comment": object.comment.as_deref().filter(|c| !c.is_empty()),
+                        "attributes": object.attributes.iter().flatten().map(|attribute| serde_json::json!({
+                            "id": attribute.id,
+                            "object_relation": attribute.object_relation,
+                            "type": attribute.attribute_type,
+                            "category": attribute.category,
+                            "value": attribute.value,
+                            "to_ids": attribute.to_ids,
+                        })).collect::<Vec<_>>(),
+                    }))
+                    .collect();
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "event_id": event_id,
+                    "count": objects.len(),
+                    "objects": objects,
+                }))
+                .map_err(|e| mcp_core::McpError::serialization_error(e.to_string()))?;
+                Ok(ToolResult::text(json))
+            })
+        }
+    ));
+
+add_permitted_tool(server, tools, Tool::new(
+    "objects_rest_search",
+    "Get a filtered and paginated list of objects from MISP. Arguments: ObjectsRestSearchRequest fields, optional strict (default false: reject unknown fields instead of ignoring them)",
+    move |mut input: ToolInput| {
+        let clients = clients.clone();
         Box::pin(async move {
-            // Parse input as ObjectsRestSearchRequest
-            let map: serde_json::Map<String, serde_json::Value> = input.arguments.into_iter().collect();
-            let params: ObjectsRestSearchRequest = serde_json::from_value(serde_json::Value::Object(map))?;
+            let client = clients.select(&mut input)?;
+            // Parse input as ObjectsRestSearchRequest; `strict` is ours, not a filter
+            let strict = input.get_optional_argument::<bool>("strict")?.unwrap_or(false);
+            let map: serde_json::Map<String, serde_json::Value> = input.arguments.into_iter().filter(|(key, _)| key != "strict").collect();
+            let request = serde_json::Value::Object(map);
+            if strict {
+                check_strict_fields("ObjectsRestSearchRequest", "arguments", &request)?;
+            }
+            let params: ObjectsRestSearchRequest = serde_json::from_value(request)?;
             match client.objects_rest_search(&params).await {
                 Ok(objects) => {
                     let json = serde_json::to_string_pretty(&objects)
@@ -1214,13 +5164,17 @@ server.add_tool(Tool::new(
                 }
                 Err(e) => {
                     error!("objects_rest_search failed: {}", e);
-                    Ok(ToolResult::error(format!("Failed to search objects: {}", e)))
+                    Ok(tool_error_result(
+                        "objects_rest_search",
+                        format!("Failed to search objects: {}", e),
+                        e.downcast_ref::<MispError>().and_then(MispError::status_code),
+                    ))
                 }
             }
         })
     }
 ));
 
-    info!("Successfully registered get_users, get_user, get_galaxies, get_galaxy, search_galaxies, get_galaxy_clusters, get_galaxy_cluster_by_id, search_galaxy_clusters, get_organisations, get_tags, get_tag_by_id, search_tags, get_organisation_by_id, get_taxonomies, get_taxonomy_by_id, get_taxonomy_extended_with_tags tools, get_sightings_by_event_id, get_warninglists, get_noticelists, get_warninglist_by_id, get_noticelist_by_id, search_warninglists, get_eventreports, get_event_report_by_id, get_collection_by_id, search_collections, list_analyst_data, get_analyst_data_by_id tools, list_attributes, get_attribute_by_id tools, get_attribute_statistics tools, describe_attribute_types tools, attributes_rest_search tools, get_events, and get_event_by_id, search_events, get_object, objects_rest_search tools");
+    info!("Successfully registered get_users, get_user, get_org_users, get_roles, whoami, check_permissions, list_instances, get_user_settings, get_galaxies, list_galaxies_by_type, get_galaxy, search_galaxies, get_galaxy_clusters, galaxy_cluster_counts, get_galaxy_cluster_by_id, search_galaxy_clusters, attack_technique, events_by_cluster, cluster_relationships, get_organisations, get_server_settings, get_tags, get_tag_by_id, search_tags, get_tag_numerical_value, export_tags_with_taxonomy, get_tag_statistics, get_organisation_by_id, resolve_org, get_taxonomies, list_taxonomies, list_enabled_taxonomies, get_taxonomy_by_id, get_taxonomy_extended_with_tags, get_taxonomy_tags, auto_tag_attribute, resolve_confidence_tag tools, get_sightings_by_event_id, get_warninglists, get_noticelists, get_warninglist_by_id, get_warninglist_entries, get_noticelist_by_id, search_warninglists, check_value_warninglist, get_eventreports, get_event_report_by_id, get_collection_by_id, search_collections, create_collection, add_to_collection, list_analyst_data, get_analyst_data_by_id tools, list_attributes, get_attribute_by_id tools, get_attribute_statistics tools, get_decaying_models, describe_attribute_types, list_categories, list_types tools, add_attribute, upsert_attribute, restore_attribute, get_attributes_by_type, normalize_indicators, search_indicators, search_by_value, pivot_from_attribute, search_attributes_with_sightings, attributes_rest_search, export_attributes_csv tools, get_events, and get_event_by_id, get_events_by_ids, get_event_history, event_summary, resolve_event_sharing, related_events, event_attribute_breakdown, find_duplicate_attributes, prepublish_check, event_warninglist_coverage, export_detection_values, event_timeline, search_events, search_published_events, extract_iocs, recent_changes, count_events, describe_tools, get_request_schema, get_object_templates, get_object, get_event_objects, add_object_reference, objects_rest_search tools");
     Ok(())
 }
@@ -6,9 +6,12 @@
 use misp_types::*;
 //use crate::types::{AttributeRestSearchRequest, AttributeListResponse};
 use reqwest::{Client, Response, StatusCode};
-use serde_json;
-use std::time::Duration;
-use tracing::{debug, info, trace, warn, error};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn, error, Level};
 
 /// Errors that can occur during MISP API operations.
 #[derive(Debug, thiserror::Error)]
@@ -16,20 +19,703 @@ pub enum MispError {
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
     
+    #[error("MISP timed out after {timeout_secs}s")]
+    Timeout { timeout_secs: u64 },
+    
+    #[error("Could not connect to MISP: {0}")]
+    Connect(reqwest::Error),
+    
     #[error("JSON serialization/deserialization failed: {0}")]
     Json(#[from] serde_json::Error),
     
-    #[error("MISP API error: {status} - {message}")]
-    Api { status: u16, message: String },
+    #[error("MISP API error: {status} - {message}{}", debug_suffix(.details))]
+    Api { status: u16, message: String, details: Option<Box<ResponseDetails>> },
     
-    #[error("Authentication failed: invalid API key")]
-    Authentication,
+    #[error("Authentication failed: invalid API key{}", debug_suffix(.details))]
+    Authentication { details: Option<Box<ResponseDetails>> },
     
-    #[error("Resource not found: {resource}")]
-    NotFound { resource: String },
+    #[error("Resource not found: {resource}{}", debug_suffix(.details))]
+    NotFound { resource: String, details: Option<Box<ResponseDetails>> },
     
     #[error("Invalid configuration: {message}")]
     Config { message: String },
+    
+    #[error("MISP unavailable: circuit breaker open after repeated failures, retry in {retry_in_secs}s")]
+    CircuitOpen { retry_in_secs: u64 },
+    
+    #[error("MISP is in maintenance, {}", retry_hint(*.retry_after))]
+    Maintenance { retry_after: Option<u64> },
+    
+    #[error("MISP response exceeds the {limit}-byte limit; narrow the request or raise --max-response-bytes")]
+    ResponseTooLarge { limit: usize },
+}
+
+/// Response headers kept in debug error details.
+const DEBUG_HEADERS: &[&str] = &["x-request-id", "request-id", "content-type"];
+
+/// Longest response body excerpt kept in debug error details, in characters.
+const DEBUG_BODY_CHARS: usize = 500;
+
+/// What MISP answered to a failed request, kept when the client runs in
+/// debug mode (see [`MispClient::with_debug_errors`]).
+#[derive(Debug, Clone)]
+pub struct ResponseDetails {
+    pub status: u16,
+    /// Request-id and content-type headers, when MISP sent them
+    pub headers: Vec<(String, String)>,
+    /// Start of the response body, truncated to `DEBUG_BODY_CHARS`
+    pub body: String,
+}
+
+impl ResponseDetails {
+    /// Status and debug headers of a response; the body is added once read.
+    fn from_head(response: &Response) -> Self {
+        let headers = DEBUG_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = response.headers().get(*name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        Self {
+            status: response.status().as_u16(),
+            headers,
+            body: String::new(),
+        }
+    }
+
+    fn with_body(mut self, body: &str) -> Self {
+        self.body = match body.char_indices().nth(DEBUG_BODY_CHARS) {
+            Some((end, _)) => format!("{}...", &body[..end]),
+            None => body.to_string(),
+        };
+        self
+    }
+}
+
+impl std::fmt::Display for ResponseDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HTTP {}", self.status)?;
+        for (name, value) in &self.headers {
+            write!(f, "; {}: {}", name, value)?;
+        }
+        write!(f, "; body: {}", self.body)
+    }
+}
+
+/// " (debug: ...)" when response details were kept, otherwise nothing.
+fn debug_suffix(details: &Option<Box<ResponseDetails>>) -> String {
+    details
+        .as_ref()
+        .map(|details| format!(" (debug: {})", details))
+        .unwrap_or_default()
+}
+
+/// Read the collection out of a `{"Collection": {...}}` response.
+///
+/// Depending on the MISP version the organisations and elements come inside
+/// the collection or next to it; the latter are moved in.
+fn collection_from_response(mut response: serde_json::Value) -> Result<Collection, MispError> {
+    let mut collection = response["Collection"].take();
+    if let (Some(collection), Some(siblings)) = (collection.as_object_mut(), response.as_object_mut()) {
+        for key in ["Org", "Orgc", "CollectionElement"] {
+            if !collection.contains_key(key) {
+                if let Some(value) = siblings.remove(key) {
+                    collection.insert(key.to_string(), value);
+                }
+            }
+        }
+    }
+    serde_json::from_value(collection).map_err(MispError::Json)
+}
+
+/// "retry in N seconds", or "retry later" without a Retry-After value.
+fn retry_hint(retry_after: Option<u64>) -> String {
+    match retry_after {
+        Some(seconds) => format!("retry in {} seconds", seconds),
+        None => "retry later".to_string(),
+    }
+}
+
+impl MispError {
+    /// HTTP status code behind this error, if it came from a MISP response.
+    ///
+    /// Authentication failures cover both 401 and 403 and are reported as 401.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            MispError::Http(e) => e.status().map(|status| status.as_u16()),
+            MispError::Api { status, .. } => Some(*status),
+            MispError::Authentication { .. } => Some(StatusCode::UNAUTHORIZED.as_u16()),
+            MispError::NotFound { .. } => Some(StatusCode::NOT_FOUND.as_u16()),
+            MispError::Maintenance { .. } => Some(StatusCode::SERVICE_UNAVAILABLE.as_u16()),
+            MispError::Json(_)
+            | MispError::Config { .. }
+            | MispError::CircuitOpen { .. }
+            | MispError::Timeout { .. }
+            | MispError::ResponseTooLarge { .. }
+            | MispError::Connect(_) => None,
+        }
+    }
+}
+
+/// Keys whose values are masked when request/response bodies are logged.
+const SENSITIVE_KEYS: &[&str] = &["authkey", "password", "api_key", "apikey", "authorization"];
+
+/// Produce a loggable copy of a JSON body.
+///
+/// Sensitive fields are masked and attachment `data` strings (base64, often
+/// megabytes) are replaced by their size. Non-JSON bodies are only sized.
+fn redact_body(body: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            value.to_string()
+        }
+        Err(_) => format!("<non-JSON body, {} bytes>", body.len()),
+    }
+}
+
+fn redact_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.to_ascii_lowercase().as_str()) {
+                    if !field.is_null() {
+                        *field = serde_json::Value::String("<redacted>".to_string());
+                    }
+                } else if key == "data" && field.as_str().is_some_and(|s| !s.is_empty()) {
+                    let len = field.as_str().map_or(0, str::len);
+                    *field = serde_json::Value::String(format!("<redacted {} bytes>", len));
+                } else {
+                    redact_value(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+/// Setting name fragments whose string values are masked in server settings.
+const SECRET_SETTING_WORDS: &[&str] = &["password", "salt", "secret", "key", "token"];
+
+/// Mask secret-looking values in a /servers/serverSettings tree.
+///
+/// Settings are `{"setting": "Security.salt", "value": ...}` entries, so the
+/// secret is named by a sibling field rather than by its own key.
+fn redact_settings(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let secret = map
+                .get("setting")
+                .and_then(|setting| setting.as_str())
+                .map(|setting| setting.rsplit('.').next().unwrap_or(setting).to_ascii_lowercase())
+                .is_some_and(|name| SECRET_SETTING_WORDS.iter().any(|word| name.contains(word)));
+            if secret {
+                if let Some(field) = map.get_mut("value").filter(|field| field.as_str().is_some_and(|s| !s.is_empty())) {
+                    *field = serde_json::Value::String("<redacted>".to_string());
+                }
+            }
+            map.values_mut().for_each(redact_settings);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_settings),
+        _ => {}
+    }
+}
+
+/// Detect a write MISP rejected while still answering 2xx.
+///
+/// Returns the flattened `errors` (e.g. `value: Invalid IP`) when the body
+/// carries `"saved": false`.
+fn rejected_write_errors(body: &serde_json::Value) -> Option<String> {
+    let saved = body.get("saved")?;
+    if !(saved == &serde_json::Value::Bool(false) || saved.as_str() == Some("false")) {
+        return None;
+    }
+    let message = match body.get("errors") {
+        Some(serde_json::Value::Object(fields)) => fields
+            .iter()
+            .map(|(field, errors)| match errors {
+                serde_json::Value::Array(items) => {
+                    let items: Vec<String> = items
+                        .iter()
+                        .map(|item| item.as_str().map_or_else(|| item.to_string(), str::to_string))
+                        .collect();
+                    format!("{}: {}", field, items.join(", "))
+                }
+                serde_json::Value::String(text) => format!("{}: {}", field, text),
+                other => format!("{}: {}", field, other),
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(other) if !other.is_null() => other.to_string(),
+        _ => body
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("MISP did not save the change")
+            .to_string(),
+    };
+    Some(message)
+}
+
+/// Filters merged into every event, attribute and object search.
+///
+/// Caller-supplied values win: `org` is only set when the request has none,
+/// and an excluded tag is skipped when the request already names it (with or
+/// without `!`). MISP has no distribution range filter, so the floor is
+/// applied to the returned events/attributes instead.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultSearchFilters {
+    /// Organisation (name, ID or UUID) searches are scoped to
+    pub org: Option<String>,
+    /// Tags excluded from results (sent as `!tag`)
+    pub exclude_tags: Vec<String>,
+    /// Lowest distribution level (0-3) kept; sharing group and inherit are not filtered
+    pub min_distribution: Option<Distribution>,
+}
+
+impl DefaultSearchFilters {
+    /// Whether no default filter is configured.
+    pub fn is_empty(&self) -> bool {
+        self.org.is_none() && self.exclude_tags.is_empty() && self.min_distribution.is_none()
+    }
+
+    fn merge_tags(&self, tags: &mut Option<Vec<String>>) {
+        if self.exclude_tags.is_empty() {
+            return;
+        }
+        let tags = tags.get_or_insert_with(Vec::new);
+        for excluded in &self.exclude_tags {
+            let named = tags.iter().any(|tag| tag.trim_start_matches('!').eq_ignore_ascii_case(excluded));
+            if !named {
+                tags.push(format!("!{}", excluded));
+            }
+        }
+    }
+
+    fn apply_to_events(&self, params: &EventsRestSearchRequest) -> EventsRestSearchRequest {
+        let mut params = params.clone();
+        if params.org.is_none() {
+            params.org = self.org.clone();
+        }
+        self.merge_tags(&mut params.tags);
+        params
+    }
+
+    fn apply_to_attributes(&self, params: &AttributeRestSearchRequest) -> AttributeRestSearchRequest {
+        let mut params = params.clone();
+        if params.org.is_none() {
+            params.org = self.org.clone();
+        }
+        self.merge_tags(&mut params.tags);
+        params
+    }
+
+    fn apply_to_event_index(&self, request: &EventIndexRequest) -> EventIndexRequest {
+        let mut request = request.clone();
+        if request.org.is_none() {
+            request.org = self.org.clone();
+        }
+        self.merge_tags(&mut request.tags);
+        request
+    }
+
+    fn apply_to_objects(&self, params: &ObjectsRestSearchRequest) -> ObjectsRestSearchRequest {
+        let mut params = params.clone();
+        if params.org.is_none() {
+            params.org = self.org.clone();
+        }
+        self.merge_tags(&mut params.tags);
+        params
+    }
+
+    /// Whether a result with this distribution passes the floor.
+    fn allows_distribution(&self, distribution: Option<&str>) -> bool {
+        let Some(floor) = self.min_distribution.as_ref().and_then(|d| d.as_str().parse::<u8>().ok()) else {
+            return true;
+        };
+        match distribution.and_then(|d| d.trim().parse::<u8>().ok()) {
+            Some(level @ 0..=3) => level >= floor,
+            // Sharing group, inherit or missing: not on the 0-3 scale
+            _ => true,
+        }
+    }
+}
+
+/// Distribution given to created attributes that do not set one.
+///
+/// Lets operators enforce a sharing policy: a request's own distribution
+/// wins, and the sharing group is only filled in for distribution 4.
+#[derive(Debug, Clone, Default)]
+pub struct WriteDefaults {
+    /// Distribution used when a request has none
+    pub distribution: Option<Distribution>,
+    /// Sharing group used with distribution 4 when a request has none
+    pub sharing_group_id: Option<String>,
+}
+
+impl WriteDefaults {
+    /// Whether no write default is configured.
+    pub fn is_empty(&self) -> bool {
+        self.distribution.is_none() && self.sharing_group_id.is_none()
+    }
+
+    fn apply_to_attribute(&self, request: &AddAttributeRequest) -> AddAttributeRequest {
+        let mut request = request.clone();
+        if request.distribution.is_none() {
+            request.distribution = self.distribution;
+        }
+        if request.distribution == Some(Distribution::SharingGroup) && request.sharing_group_id.is_none() {
+            request.sharing_group_id = self.sharing_group_id.clone();
+        }
+        request
+    }
+}
+
+/// Settings for the circuit breaker guarding MISP calls.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the breaker; 0 disables it
+    pub failure_threshold: u32,
+    /// How long the breaker stays open before letting a probe through
+    pub cooldown: Duration,
+    /// Upper bound for the cooldown, which doubles each time a probe fails
+    pub max_cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+            max_cooldown: Duration::from_secs(300),
+        }
+    }
+}
+
+const BREAKER_CLOSED: u8 = 0;
+const BREAKER_OPEN: u8 = 1;
+const BREAKER_HALF_OPEN: u8 = 2;
+
+/// Stops calling MISP while it is down.
+///
+/// Closed: calls go through and failures are counted. After
+/// `failure_threshold` consecutive failures the breaker opens and calls fail
+/// fast with `MispError::CircuitOpen`. Once the cooldown has passed, one
+/// call is let through as a probe (half-open): success closes the breaker,
+/// failure reopens it with a doubled cooldown.
+///
+/// Only failures that suggest MISP itself is unavailable count: transport
+/// errors, timeouts and 5xx responses. 4xx answers mean MISP is up, and the
+/// maintenance page already says when to come back, so callers keep getting
+/// `MispError::Maintenance` rather than `CircuitOpen`.
+#[derive(Debug)]
+struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    /// Reference point for the millisecond timestamps below
+    epoch: Instant,
+    state: AtomicU8,
+    consecutive_failures: AtomicU32,
+    /// Times the breaker reopened since it last closed; drives the backoff
+    reopenings: AtomicU32,
+    /// When an open breaker lets the next probe through, in ms since `epoch`
+    retry_at_ms: AtomicU64,
+}
+
+impl CircuitBreaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            epoch: Instant::now(),
+            state: AtomicU8::new(BREAKER_CLOSED),
+            consecutive_failures: AtomicU32::new(0),
+            reopenings: AtomicU32::new(0),
+            retry_at_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.epoch.elapsed().as_millis() as u64
+    }
+
+    /// Decide whether a call may go to MISP.
+    ///
+    /// The returned [`BreakerCall`] records the call's outcome.
+    fn before_call(&self) -> Result<BreakerCall<'_>, MispError> {
+        let call = |probe| BreakerCall { breaker: self, probe, recorded: false };
+        if self.config.failure_threshold == 0 {
+            return Ok(call(false));
+        }
+        match self.state.load(Ordering::Acquire) {
+            BREAKER_CLOSED => Ok(call(false)),
+            BREAKER_OPEN => {
+                let now = self.now_ms();
+                let retry_at = self.retry_at_ms.load(Ordering::Acquire);
+                // Exactly one caller wins the switch to half-open and probes
+                if now >= retry_at
+                    && self
+                        .state
+                        .compare_exchange(BREAKER_OPEN, BREAKER_HALF_OPEN, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                {
+                    info!("Circuit breaker half-open, probing MISP");
+                    return Ok(call(true));
+                }
+                Err(MispError::CircuitOpen {
+                    retry_in_secs: retry_at.saturating_sub(now).div_ceil(1000),
+                })
+            }
+            // A probe is already in flight
+            _ => Err(MispError::CircuitOpen { retry_in_secs: 0 }),
+        }
+    }
+
+    fn record_success(&self) {
+        if self.config.failure_threshold == 0 {
+            return;
+        }
+        self.consecutive_failures.store(0, Ordering::Release);
+        if self.state.swap(BREAKER_CLOSED, Ordering::AcqRel) != BREAKER_CLOSED {
+            self.reopenings.store(0, Ordering::Release);
+            info!("Circuit breaker closed, MISP is responding again");
+        }
+    }
+
+    fn record_failure(&self) {
+        if self.config.failure_threshold == 0 {
+            return;
+        }
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::AcqRel) + 1;
+        let probe_failed = self.state.load(Ordering::Acquire) == BREAKER_HALF_OPEN;
+        if !probe_failed && failures < self.config.failure_threshold {
+            return;
+        }
+        let reopenings = if probe_failed {
+            self.reopenings.fetch_add(1, Ordering::AcqRel) + 1
+        } else {
+            self.reopenings.load(Ordering::Acquire)
+        };
+        let cooldown = self.cooldown(reopenings);
+        self.retry_at_ms
+            .store(self.now_ms() + cooldown.as_millis() as u64, Ordering::Release);
+        if self.state.swap(BREAKER_OPEN, Ordering::AcqRel) != BREAKER_OPEN {
+            warn!(
+                "Circuit breaker open after {} consecutive failures; pausing MISP calls for {}s",
+                failures,
+                cooldown.as_secs()
+            );
+        }
+    }
+
+    /// Reopen the breaker after a probe ended without an outcome, so the
+    /// next probe is let through after a fresh cooldown.
+    fn abandon_probe(&self) {
+        let cooldown = self.cooldown(self.reopenings.load(Ordering::Acquire));
+        self.retry_at_ms
+            .store(self.now_ms() + cooldown.as_millis() as u64, Ordering::Release);
+        if self
+            .state
+            .compare_exchange(BREAKER_HALF_OPEN, BREAKER_OPEN, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            warn!("Circuit breaker probe ended without an answer; probing again in {}s", cooldown.as_secs());
+        }
+    }
+
+    /// Cooldown after `reopenings` failed probes: doubled each time, capped.
+    fn cooldown(&self, reopenings: u32) -> Duration {
+        self.config
+            .cooldown
+            .saturating_mul(2u32.saturating_pow(reopenings))
+            .min(self.config.max_cooldown)
+    }
+}
+
+/// One call let through by the circuit breaker.
+///
+/// The outcome is recorded with `success` or `failure`. A half-open probe
+/// dropped without an outcome (the caller gave up on it, e.g. a cancelled
+/// request) reopens the breaker instead of leaving it half-open for good.
+struct BreakerCall<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl BreakerCall<'_> {
+    fn success(mut self) {
+        self.recorded = true;
+        self.breaker.record_success();
+    }
+
+    fn failure(mut self) {
+        self.recorded = true;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for BreakerCall<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.recorded {
+            self.breaker.abandon_probe();
+        }
+    }
+}
+
+/// Events fetched per page when `count_events` has to count them itself.
+const COUNT_PAGE_SIZE: u32 = 500;
+
+/// Largest response body read from MISP unless configured otherwise (256 MiB).
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 256 * 1024 * 1024;
+
+/// Connection settings shared by the API-key and cookie-session HTTP clients.
+///
+/// Each client keeps a pool of HTTP keep-alive connections that requests
+/// (and clones of the `MispClient`) reuse; idle connections are closed after
+/// reqwest's 90 seconds.
+#[derive(Debug)]
+struct HttpSettings {
+    verify_tls: bool,
+    timeout: Duration,
+    root_certificates: Vec<reqwest::Certificate>,
+    /// TCP keepalive probe interval on idle pooled connections (`None` disables)
+    tcp_keepalive: Option<Duration>,
+}
+
+impl HttpSettings {
+    fn build_client(&self, cookie_store: bool) -> Result<Client, reqwest::Error> {
+        let mut client_builder = Client::builder()
+            .timeout(self.timeout)
+            .danger_accept_invalid_certs(!self.verify_tls)
+            .cookie_store(cookie_store)
+            .tcp_keepalive(self.tcp_keepalive)
+            .user_agent("misp-mcp-server/0.1.0");
+        if !self.verify_tls {
+            client_builder = client_builder.danger_accept_invalid_hostnames(true);
+        }
+        for certificate in &self.root_certificates {
+            client_builder = client_builder.add_root_certificate(certificate.clone());
+        }
+        client_builder.build()
+    }
+}
+
+/// Extract the hidden `<input>` fields (CSRF tokens, `_method`) of an HTML form.
+fn hidden_form_fields(html: &str) -> Vec<(String, String)> {
+    fn attribute(tag: &str, name: &str) -> Option<String> {
+        let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+        let end = start + tag[start..].find('"')?;
+        Some(
+            tag[start..end]
+                .replace("&quot;", "\"")
+                .replace("&#039;", "'")
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&amp;", "&"),
+        )
+    }
+
+    html.split("<input")
+        .skip(1)
+        .filter_map(|rest| {
+            let tag = &rest[..rest.find('>').unwrap_or(rest.len())];
+            if attribute(tag, "type").as_deref() != Some("hidden") {
+                return None;
+            }
+            Some((attribute(tag, " name")?, attribute(tag, "value").unwrap_or_default()))
+        })
+        .collect()
+}
+
+/// Username/password session for deployments that only accept cookie auth
+/// on some routes.
+struct SessionAuth {
+    /// Cookie-jar client; requests sent with it carry no API key
+    client: Client,
+    username: String,
+    password: String,
+    /// Path prefixes (relative to the base URL) whose refusals are replayed
+    routes: Vec<String>,
+    /// Path of the base URL, for MISP served below the host root
+    base_path: String,
+    /// Whether `client` holds a logged-in session; also serializes logins
+    logged_in: tokio::sync::Mutex<bool>,
+}
+
+impl std::fmt::Debug for SessionAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionAuth").field("username", &self.username).finish_non_exhaustive()
+    }
+}
+
+impl SessionAuth {
+    /// Whether a request to `url` may be replayed over the session.
+    fn covers(&self, url: &reqwest::Url) -> bool {
+        let path = url.path().strip_prefix(self.base_path.as_str()).unwrap_or(url.path());
+        self.routes.iter().any(|route| path.starts_with(route.as_str()))
+    }
+
+    /// Log in through the MISP web form, keeping the session cookie.
+    async fn login(&self, base_url: &str, username: &str, password: &str) -> Result<(), MispError> {
+        let url = format!("{}/users/login", base_url);
+        // The form carries CSRF tokens that must be posted back
+        let page = self.client.get(&url).send().await?.error_for_status()?.text().await?;
+        let mut form = hidden_form_fields(&page);
+        form.push(("data[User][email]".to_string(), username.to_string()));
+        form.push(("data[User][password]".to_string(), password.to_string()));
+
+        let response = self.client.post(&url).form(&form).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(MispError::Api {
+                status: status.as_u16(),
+                message: "session login failed".to_string(),
+                details: None,
+            });
+        }
+        // A failed login renders the form again instead of redirecting away
+        if is_login_page(&response) {
+            return Err(MispError::Authentication { details: None });
+        }
+        info!("Logged in to MISP as {} (cookie session)", username);
+        Ok(())
+    }
+}
+
+/// Whether a session request ended up on the login form (no or expired session).
+fn is_login_page(response: &Response) -> bool {
+    response.url().path().trim_end_matches('/').ends_with("/users/login")
+}
+
+/// Whether MISP refused the credentials a request was sent with.
+fn is_auth_rejection(response: &Response) -> bool {
+    matches!(response.status(), StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+}
+
+/// Whether a response is MISP's maintenance page: a 503 that announces when
+/// to come back or serves HTML instead of the API's JSON.
+fn is_maintenance(response: &Response) -> bool {
+    if response.status() != StatusCode::SERVICE_UNAVAILABLE {
+        return false;
+    }
+    let html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("text/html"));
+    html || response.headers().contains_key(reqwest::header::RETRY_AFTER)
+}
+
+/// Seconds to wait according to a Retry-After header (delay or HTTP date).
+fn retry_after_secs(response: &Response) -> Option<u64> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(seconds);
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.timestamp() - chrono::Utc::now().timestamp();
+    Some(wait.max(0) as u64)
 }
 
 /// HTTP client for MISP API operations.
@@ -41,6 +727,26 @@ pub struct MispClient {
     client: Client,
     base_url: String,
     api_key: String,
+    /// Cached /attributes/describeTypes result, shared across clones
+    describe_types_cache: Arc<OnceCell<DescribeTypesResult>>,
+    /// ID of the MITRE ATT&CK attack-pattern galaxy, shared across clones
+    attack_galaxy_id: Arc<OnceCell<String>>,
+    /// Organisation ID -> name lookups, shared across clones
+    org_name_cache: Arc<RwLock<HashMap<String, String>>>,
+    /// Filters merged into every event/attribute search
+    search_defaults: Arc<DefaultSearchFilters>,
+    /// Distribution filled into created attributes
+    write_defaults: Arc<WriteDefaults>,
+    /// Fails calls fast while MISP is down, shared across clones
+    breaker: Arc<CircuitBreaker>,
+    /// Settings the HTTP clients are built from
+    http: Arc<HttpSettings>,
+    /// Cookie session used when a route rejects the API key, if configured
+    session: Option<Arc<SessionAuth>>,
+    /// Largest response body read from MISP, in bytes (0: no limit)
+    max_response_bytes: usize,
+    /// Keep status, headers and body excerpt in errors from failed responses
+    debug_errors: bool,
 }
 
 impl MispClient {
@@ -50,12 +756,17 @@ impl MispClient {
     /// - `base_url`: MISP server base URL (e.g., "https://misp.local")
     /// - `api_key`: MISP API authentication key
     /// - `verify_tls`: Whether to verify TLS certificates
+    /// - `ca_cert`: Optional path to a PEM CA bundle to trust; forces verification on
     /// - `timeout_seconds`: Request timeout in seconds
+    /// - `tcp_keepalive`: TCP keepalive for pooled connections, so firewalls and
+    ///   load balancers don't drop them while idle; `None` disables it
     pub async fn new(
         base_url: String,
         api_key: String,
         verify_tls: bool,
+        ca_cert: Option<&str>,
         timeout_seconds: u64,
+        tcp_keepalive: Option<Duration>,
     ) -> Result<Self, MispError> {
         // Validate configuration
         if base_url.is_empty() {
@@ -70,28 +781,239 @@ impl MispClient {
             });
         }
         
-        // Build HTTP client with appropriate settings
-        let mut client_builder = Client::builder()
-            .timeout(Duration::from_secs(timeout_seconds))
-            .danger_accept_invalid_certs(!verify_tls)
-            .user_agent("misp-mcp-server/0.1.0");
+        // A pinned CA only makes sense with verification enabled
+        let verify_tls = verify_tls || ca_cert.is_some();
         
         if !verify_tls {
             warn!("TLS certificate verification is disabled");
-            client_builder = client_builder.danger_accept_invalid_hostnames(true);
         }
         
-        let client = client_builder.build()?;
+        let mut root_certificates = Vec::new();
+        if let Some(path) = ca_cert {
+            let pem = std::fs::read(path).map_err(|e| MispError::Config {
+                message: format!("Cannot read CA certificate '{}': {}", path, e),
+            })?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| MispError::Config {
+                message: format!("Invalid PEM in CA certificate '{}': {}", path, e),
+            })?;
+            if certificates.is_empty() {
+                return Err(MispError::Config {
+                    message: format!("No certificates found in '{}'", path),
+                });
+            }
+            info!("Trusting {} CA certificate(s) from {}", certificates.len(), path);
+            root_certificates = certificates;
+        }
+        
+        let http = HttpSettings {
+            verify_tls,
+            timeout: Duration::from_secs(timeout_seconds),
+            root_certificates,
+            tcp_keepalive,
+        };
+        let client = http.build_client(false)?;
         
         info!("Created MISP client for {}", base_url);
-        debug!("Client configuration: verify_tls={}, timeout={}s", verify_tls, timeout_seconds);
+        debug!("Client configuration: verify_tls={}, timeout={}s, tcp_keepalive={:?}", verify_tls, timeout_seconds, tcp_keepalive);
         
         Ok(Self {
             client,
             base_url,
             api_key,
+            describe_types_cache: Arc::new(OnceCell::new()),
+            attack_galaxy_id: Arc::new(OnceCell::new()),
+            org_name_cache: Arc::new(RwLock::new(HashMap::new())),
+            search_defaults: Arc::new(DefaultSearchFilters::default()),
+            write_defaults: Arc::new(WriteDefaults::default()),
+            breaker: Arc::new(CircuitBreaker::new(CircuitBreakerConfig::default())),
+            http: Arc::new(http),
+            session: None,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            debug_errors: false,
         })
     }
+
+    /// Apply `filters` to every event, attribute and object search made by this client.
+    pub fn with_default_search_filters(mut self, filters: DefaultSearchFilters) -> Self {
+        self.search_defaults = Arc::new(filters);
+        self
+    }
+
+    /// Apply `defaults` to every attribute created by this client.
+    pub fn with_write_defaults(mut self, defaults: WriteDefaults) -> Self {
+        self.write_defaults = Arc::new(defaults);
+        self
+    }
+
+    /// Fail requests whose response body is larger than `limit` bytes (0: no limit).
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Put the HTTP status, request-id and content-type headers and the start
+    /// of the body into errors from failed MISP responses.
+    ///
+    /// Meant for one call at a time: tools enable it on a clone when called
+    /// with `debug: true`.
+    pub fn with_debug_errors(mut self, enabled: bool) -> Self {
+        self.debug_errors = enabled;
+        self
+    }
+
+    /// Replace the default circuit breaker settings.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.breaker = Arc::new(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Fall back to a cookie session for routes that reject the API key.
+    ///
+    /// Only requests whose path (relative to the base URL) starts with one
+    /// of `routes` fall back: the session logs in with `username`/`password`
+    /// the first time such a request is refused (401/403) and the request is
+    /// replayed without the API key; an expired session is renewed the same
+    /// way. Refusals on other routes are genuine permission denials for the
+    /// API key and are returned as they are.
+    pub fn with_session_fallback(mut self, username: String, password: String, routes: Vec<String>) -> Result<Self, MispError> {
+        if username.is_empty() || password.is_empty() {
+            return Err(MispError::Config {
+                message: "Session fallback needs both a username and a password".to_string(),
+            });
+        }
+        let routes: Vec<String> = routes
+            .iter()
+            .map(|route| route.trim())
+            .filter(|route| !route.is_empty())
+            .map(|route| format!("/{}", route.trim_start_matches('/')))
+            .collect();
+        if routes.is_empty() {
+            return Err(MispError::Config {
+                message: "Session fallback needs at least one route prefix".to_string(),
+            });
+        }
+        self.session = Some(Arc::new(SessionAuth {
+            client: self.http.build_client(true)?,
+            username,
+            password,
+            routes,
+            base_path: reqwest::Url::parse(&self.base_url)
+                .map(|url| url.path().trim_end_matches('/').to_string())
+                .unwrap_or_default(),
+            logged_in: tokio::sync::Mutex::new(false),
+        }));
+        Ok(self)
+    }
+
+    /// Base URL of the MISP server this client calls.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Establish a cookie session with the given credentials.
+    ///
+    /// Corresponds to: GET + POST /users/login
+    ///
+    /// Requires `with_session_fallback`; the session is then used for
+    /// requests that the API key alone cannot make.
+    pub async fn login(&self, username: &str, password: &str) -> Result<(), MispError> {
+        let session = self.session.as_ref().ok_or_else(|| MispError::Config {
+            message: "Cookie session mode is not enabled".to_string(),
+        })?;
+        let mut logged_in = session.logged_in.lock().await;
+        *logged_in = false;
+        session.login(&self.base_url, username, password).await?;
+        *logged_in = true;
+        Ok(())
+    }
+
+    /// Replay a request refused for the API key over the cookie session.
+    async fn send_with_session(&self, session: &SessionAuth, mut request: reqwest::Request) -> Result<Response, MispError> {
+        request.headers_mut().remove("Authorization");
+        let mut renewed = false;
+        {
+            let mut logged_in = session.logged_in.lock().await;
+            if !*logged_in {
+                session.login(&self.base_url, &session.username, &session.password).await?;
+                *logged_in = true;
+                renewed = true;
+            }
+        }
+        let retry = request.try_clone();
+        let response = session.client.execute(request).await.map_err(|e| self.transport_error(e))?;
+        if renewed || !(is_auth_rejection(&response) || is_login_page(&response)) {
+            return Ok(response);
+        }
+        // The session expired: log in again and replay once more
+        let Some(retry) = retry else {
+            return Ok(response);
+        };
+        {
+            let mut logged_in = session.logged_in.lock().await;
+            *logged_in = false;
+            session.login(&self.base_url, &session.username, &session.password).await?;
+            *logged_in = true;
+        }
+        session.client.execute(retry).await.map_err(|e| self.transport_error(e))
+    }
+
+    /// Classify a failed request: timeouts and connection failures get their
+    /// own variants so callers can tell them from other HTTP errors.
+    fn transport_error(&self, e: reqwest::Error) -> MispError {
+        if e.is_timeout() {
+            MispError::Timeout {
+                timeout_secs: self.http.timeout.as_secs(),
+            }
+        } else if e.is_connect() {
+            MispError::Connect(e)
+        } else {
+            MispError::Http(e)
+        }
+    }
+
+    /// Send a request through the circuit breaker.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Response, MispError> {
+        // Build first: a request that cannot be built never reaches MISP
+        let request = request.build()?;
+        let call = self.breaker.before_call()?;
+        // Bodies here are in-memory, so a copy for the session fallback is cheap
+        let fallback = self
+            .session
+            .as_ref()
+            .filter(|session| session.covers(request.url()))
+            .and_then(|session| Some((session, request.try_clone()?)));
+        let response = match self.client.execute(request).await {
+            Ok(response) => {
+                if response.status().is_server_error() && !is_maintenance(&response) {
+                    call.failure();
+                } else {
+                    call.success();
+                }
+                response
+            }
+            Err(e) => {
+                call.failure();
+                return Err(self.transport_error(e));
+            }
+        };
+        if is_maintenance(&response) {
+            let retry_after = retry_after_secs(&response);
+            warn!("MISP is in maintenance ({} answered 503), retry after {:?}s", response.url(), retry_after);
+            return Err(MispError::Maintenance { retry_after });
+        }
+        match fallback {
+            Some((session, request)) if is_auth_rejection(&response) => {
+                warn!(
+                    "{} rejected the API key ({}), replaying it over the cookie session of {}",
+                    response.url(),
+                    response.status(),
+                    session.username
+                );
+                self.send_with_session(session, request).await
+            }
+            _ => Ok(response),
+        }
+    }
     
     /// Execute a GET request to a MISP endpoint.
     async fn misp_get<T>(&self, endpoint: &str) -> Result<T, MispError>
@@ -101,14 +1023,13 @@ impl MispClient {
         let url = format!("{}{}", self.base_url, endpoint);
         debug!("GET {}", url);
         
-        let response = self
+        let request = self
             .client
             .get(&url)
             .header("Authorization", &self.api_key)
             .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
+            .header("Content-Type", "application/json");
+        let response = self.send(request).await?;
         
         self.handle_response(response).await
     }
@@ -118,24 +1039,111 @@ impl MispClient {
     where
         T: for<'de> serde::Deserialize<'de>,
         B: serde::Serialize,
+    {
+        let response = self.misp_post_response(endpoint, body).await?;
+        self.handle_response(response).await
+    }
+
+    /// Send a JSON POST to a MISP endpoint and return the raw response,
+    /// for callers that need its headers.
+    async fn misp_post_response<B>(&self, endpoint: &str, body: &B) -> Result<Response, MispError>
+    where
+        B: serde::Serialize,
     {
         let url = format!("{}{}", self.base_url, endpoint);
         debug!("POST {}", url);
         
         let json_body = serde_json::to_string(body)?;
-        trace!("Request body: {}", json_body);
+        if tracing::enabled!(Level::DEBUG) {
+            debug!("Request body: {}", redact_body(&json_body));
+        }
         
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("Authorization", &self.api_key)
             .header("Accept", "application/json")
             .header("Content-Type", "application/json")
-            .body(json_body)
-            .send()
-            .await?;
-        
-        self.handle_response(response).await
+            .body(json_body);
+        self.send(request).await
+    }
+    
+    /// Execute a GET request, keeping the raw JSON if it does not parse as `T`.
+    ///
+    /// For endpoints whose shape changes between MISP versions and whose
+    /// callers can work with raw JSON.
+    async fn misp_get_lenient<T>(&self, endpoint: &str) -> Result<Lenient<T>, MispError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let response: Lenient<T> = self.misp_get(endpoint).await?;
+        if let Some(error) = response.error() {
+            warn!("{} did not match the expected shape ({}), returning raw JSON", endpoint, error);
+        }
+        Ok(response)
+    }
+
+    /// Execute a POST request and return the body as text.
+    ///
+    /// The request body is JSON; the answer is returned unparsed, in the
+    /// format chosen by `accept` (e.g. `text/csv`).
+    async fn misp_post_text<B>(&self, endpoint: &str, body: &B, accept: &str) -> Result<String, MispError>
+    where
+        B: serde::Serialize,
+    {
+        let url = format!("{}{}", self.base_url, endpoint);
+        debug!("POST {} ({})", url, accept);
+
+        let json_body = serde_json::to_string(body)?;
+        if tracing::enabled!(Level::DEBUG) {
+            debug!("Request body: {}", redact_body(&json_body));
+        }
+
+        let request = self
+            .client
+            .post(&url)
+            .header("Authorization", &self.api_key)
+            .header("Accept", accept)
+            .header("Content-Type", "application/json")
+            .body(json_body);
+        let response = self.send(request).await?;
+
+        self.response_text(response).await
+    }
+
+    /// Execute a write (add/edit/attach) POST to a MISP endpoint.
+    ///
+    /// Like `misp_post`, but a 2xx body with `"saved": false` is turned into
+    /// `MispError::Api` carrying MISP's validation errors instead of being
+    /// parsed as a success. Some endpoints answer a successful write with an
+    /// empty 200 body; that is `Ok(None)`, as there is nothing to parse.
+    async fn misp_write<T, B>(&self, endpoint: &str, body: &B) -> Result<Option<T>, MispError>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+        B: serde::Serialize,
+    {
+        let text = self.misp_post_text(endpoint, body, "application/json").await?;
+        if text.trim().is_empty() {
+            debug!("MISP saved the write to {} without a response body", endpoint);
+            return Ok(None);
+        }
+        let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
+            error!("Failed to parse JSON response from {}: {}", endpoint, e);
+            MispError::Json(e)
+        })?;
+        if let Some(errors) = rejected_write_errors(&value) {
+            warn!("MISP rejected write to {}: {}", endpoint, errors);
+            // MISP answered 200; the status reflects that rather than inventing one
+            return Err(MispError::Api {
+                status: StatusCode::OK.as_u16(),
+                message: format!("write not saved: {}", errors),
+                details: None,
+            });
+        }
+        serde_json::from_value(value).map(Some).map_err(|e| {
+            error!("Failed to parse JSON response from {}: {}", endpoint, e);
+            MispError::Json(e)
+        })
     }
     
     /// Handle HTTP response and deserialize JSON.
@@ -143,41 +1151,116 @@ impl MispClient {
     where
         T: for<'de> serde::Deserialize<'de>,
     {
+        let url = response.url().to_string();
+        let response_text = self.response_text(response).await?;
+        
+        // Try to deserialize the response
+        match serde_json::from_str::<T>(&response_text) {
+            Ok(data) => {
+                debug!("Successfully parsed response");
+                Ok(data)
+            }
+            Err(e) => {
+                // Paging past the end, MISP may answer `null` (or nothing) where a
+                // list is expected; treat that as an empty page for list targets
+                if matches!(response_text.trim(), "" | "null") {
+                    if let Ok(empty) = serde_json::from_str::<T>("[]") {
+                        debug!("Empty/null body from {}, treating as an empty list", url);
+                        return Ok(empty);
+                    }
+                    // An empty 200 answers `()` and `Option` targets too
+                    if let Ok(nothing) = serde_json::from_str::<T>("null") {
+                        debug!("Empty body from {}, treating as no value", url);
+                        return Ok(nothing);
+                    }
+                }
+                error!("Failed to parse JSON response: {}", e);
+                error!("Response was: {}", redact_body(&response_text));
+                Err(MispError::Json(e))
+            }
+        }
+    }
+    
+    /// Turn an error status into a `MispError`, otherwise read the body as text.
+    async fn response_text(&self, response: Response) -> Result<String, MispError> {
         let status = response.status();
         let url = response.url().to_string();
         
         debug!("Response: {} {}", status, url);
         
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
+            // Headers go with the response, so take what debug details need first
+            let head = self.debug_errors.then(|| ResponseDetails::from_head(&response));
+            let error_text = self.read_body(response).await.unwrap_or_default();
             error!("HTTP error {}: {}", status, error_text);
+            let details = head.map(|head| Box::new(head.with_body(&error_text)));
             
             return Err(match status {
-                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => MispError::Authentication,
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => MispError::Authentication { details },
                 StatusCode::NOT_FOUND => MispError::NotFound {
                     resource: url,
+                    details,
                 },
                 _ => MispError::Api {
                     status: status.as_u16(),
                     message: error_text,
+                    details,
                 },
             });
         }
         
-        let response_text = response.text().await?;
-        trace!("Response body: {}", response_text);
+        let response_text = self.read_body(response).await?;
+        if tracing::enabled!(Level::DEBUG) {
+            debug!("Response body: {}", redact_body(&response_text));
+        }
+        Ok(response_text)
+    }
+    
+    /// Read a response body as text, stopping once it passes `max_response_bytes`.
+    async fn read_body(&self, mut response: Response) -> Result<String, MispError> {
+        let limit = self.max_response_bytes;
+        if limit > 0 && response.content_length().is_some_and(|length| length > limit as u64) {
+            error!("Response from {} announces {:?} bytes, over the {}-byte limit", response.url(), response.content_length(), limit);
+            return Err(MispError::ResponseTooLarge { limit });
+        }
         
-        // Try to deserialize the response
-        match serde_json::from_str::<T>(&response_text) {
-            Ok(data) => {
-                debug!("Successfully parsed response");
-                Ok(data)
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| self.transport_error(e))? {
+            if limit > 0 && body.len() + chunk.len() > limit {
+                error!("Response from {} exceeded the {}-byte limit", response.url(), limit);
+                return Err(MispError::ResponseTooLarge { limit });
             }
-            Err(e) => {
-                error!("Failed to parse JSON response: {}", e);
-                error!("Response was: {}", response_text);
-                Err(MispError::Json(e))
+            body.extend_from_slice(&chunk);
+        }
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+    
+    /// Send a GET or POST to any MISP endpoint and return the JSON answer.
+    /// 
+    /// `path` is relative to the MISP base URL and must start with `/`.
+    /// A POST without a body sends `{}`.
+    pub async fn raw_request(
+        &self,
+        method: &reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value, MispError> {
+        // Anything else could steer the request away from the MISP host
+        if !path.starts_with('/') {
+            return Err(MispError::Config {
+                message: format!("raw request path must start with '/': {}", path),
+            });
+        }
+        info!("Raw MISP request: {} {}", method, path);
+        match *method {
+            reqwest::Method::GET => self.misp_get(path).await,
+            reqwest::Method::POST => {
+                let empty = serde_json::Value::Object(serde_json::Map::new());
+                self.misp_post(path, body.unwrap_or(&empty)).await
             }
+            _ => Err(MispError::Config {
+                message: format!("raw requests support GET and POST, not {}", method),
+            }),
         }
     }
     
@@ -189,6 +1272,17 @@ impl MispClient {
         self.misp_get("/admin/users").await
     }
     
+    /// Get the users belonging to one organisation.
+    /// 
+    /// GET /admin/users has no organisation filter in its JSON API, so the
+    /// full list is fetched and filtered on `org_id`.
+    pub async fn get_users_by_org(&self, org_id: &str) -> Result<GetUsersResponse, MispError> {
+        info!("Fetching users of organisation {}", org_id);
+        let mut users = self.get_users().await?;
+        users.retain(|entry| entry.user.org_id == org_id);
+        Ok(users)
+    }
+    
     /// Get a specific user by ID from MISP.
     /// 
     /// Corresponds to: GET /admin/users/view/{user_id}
@@ -198,6 +1292,36 @@ impl MispClient {
         self.misp_get(&endpoint).await
     }
     
+    /// Get all roles with their permission flags.
+    /// 
+    /// Corresponds to: GET /roles
+    pub async fn get_roles(&self) -> Result<Vec<RoleEntry>, MispError> {
+        info!("Fetching all roles");
+        self.misp_get("/roles.json").await
+    }
+    
+    /// Get the user the API key belongs to.
+    /// 
+    /// Corresponds to: GET /users/view/me.json (no admin rights needed)
+    pub async fn get_current_user(&self) -> Result<GetUserByIdResponse, MispError> {
+        info!("Fetching the current user");
+        self.misp_get("/users/view/me.json").await
+    }
+    
+    /// Get the server settings and diagnostics (site admin only).
+    /// 
+    /// Corresponds to: GET /servers/serverSettings
+    /// 
+    /// Secret-looking values (salts, passwords, keys, tokens) are masked
+    /// before the tree is returned.
+    pub async fn get_server_settings(&self) -> Result<serde_json::Value, MispError> {
+        info!("Fetching server settings");
+        let mut settings: serde_json::Value = self.misp_get("/servers/serverSettings").await?;
+        redact_value(&mut settings);
+        redact_settings(&mut settings);
+        Ok(settings)
+    }
+    
     /// Get all galaxies from MISP.
     /// 
     /// Corresponds to: GET /galaxies
@@ -259,6 +1383,14 @@ impl MispClient {
         self.misp_get(&endpoint).await
     }
 
+    /// Like `get_galaxy_cluster_by_id`, but a cluster that does not match
+    /// the typed shape comes back as raw JSON instead of an error.
+    pub async fn get_galaxy_cluster_by_id_lenient(&self, galaxy_cluster_id: &str) -> Result<Lenient<GetGalaxyClusterByIdResponse>, MispError> {
+        info!("Fetching galaxy cluster by ID: {}", galaxy_cluster_id);
+        let endpoint = format!("/galaxy_clusters/view/{}.json", galaxy_cluster_id);
+        self.misp_get_lenient(&endpoint).await
+    }
+
     /// Search galaxy clusters within a specific galaxy using search criteria.
     /// 
     /// Corresponds to: POST /galaxy_clusters/index/{galaxy_id}
@@ -284,6 +1416,28 @@ impl MispClient {
         self.misp_post(&endpoint, &request_payload).await
     }
 
+    /// ID of the MITRE ATT&CK technique galaxy (type `mitre-attack-pattern`).
+    ///
+    /// Resolved through GET /galaxies on first use and kept for the lifetime
+    /// of the client; galaxy IDs only change when galaxies are reimported.
+    pub async fn attack_pattern_galaxy_id(&self) -> Result<&str, MispError> {
+        let id = self
+            .attack_galaxy_id
+            .get_or_try_init(|| async {
+                let galaxies = self.get_galaxies().await?;
+                galaxies
+                    .into_iter()
+                    .find(|entry| entry.galaxy.galaxy_type == "mitre-attack-pattern")
+                    .map(|entry| entry.galaxy.id)
+                    .ok_or_else(|| MispError::NotFound {
+                        resource: "MITRE ATT&CK attack-pattern galaxy".to_string(),
+                        details: None,
+                    })
+            })
+            .await?;
+        Ok(id.as_str())
+    }
+
     /// Get all organisations.
     /// 
     /// Corresponds to: GET /organisations
@@ -328,6 +1482,17 @@ impl MispClient {
         let endpoint = format!("/tags/search/{}", search_term);
         self.misp_get(&endpoint).await
     }
+
+    /// Get tag and taxonomy usage counts.
+    /// 
+    /// Corresponds to: GET /tags/tagStatistics/{percentage}/{keysort}
+    /// 
+    /// # Arguments
+    /// - `percentage`: Return percentages instead of absolute counts
+    pub async fn tag_statistics(&self, percentage: bool) -> Result<TagStatisticsResponse, MispError> {
+        let endpoint = format!("/tags/tagStatistics/{}/0", u8::from(percentage));
+        self.misp_get(&endpoint).await
+    }
     
     /// Get a specific organisation by ID from MISP.
     /// 
@@ -341,6 +1506,45 @@ impl MispClient {
         self.misp_get(&endpoint).await
     }
 
+    /// Resolve an organisation ID to its name, caching the result.
+    ///
+    /// Organisation names rarely change, so each ID is fetched from MISP at
+    /// most once per client. Returns the name and whether it came from cache.
+    pub async fn resolve_org_name(&self, organisation_id: &str) -> Result<(String, bool), MispError> {
+        if let Some(name) = self.org_name_cache.read().unwrap().get(organisation_id) {
+            debug!("Organisation {} resolved from cache", organisation_id);
+            return Ok((name.clone(), true));
+        }
+
+        let entry = self.get_organisation_by_id(organisation_id).await?;
+        let name = entry.organisation.name.ok_or_else(|| MispError::NotFound {
+            resource: format!("name of organisation {}", organisation_id),
+            details: None,
+        })?;
+
+        self.org_name_cache
+            .write()
+            .unwrap()
+            .insert(organisation_id.to_string(), name.clone());
+        Ok((name, false))
+    }
+
+    /// Add `org_name`/`orgc_name` fields to a serialized event.
+    ///
+    /// Looks up the event's `org_id` and `orgc_id` through the organisation
+    /// name cache. Lookups that fail are skipped rather than failing the event.
+    pub async fn enrich_event_org_names(&self, event: &mut serde_json::Value) {
+        for (id_field, name_field) in [("org_id", "org_name"), ("orgc_id", "orgc_name")] {
+            let Some(org_id) = event.get(id_field).and_then(|v| v.as_str()).map(str::to_string) else {
+                continue;
+            };
+            match self.resolve_org_name(&org_id).await {
+                Ok((name, _)) => event[name_field] = serde_json::Value::String(name),
+                Err(e) => warn!("Could not resolve {} {}: {}", id_field, org_id, e),
+            }
+        }
+    }
+
     /// Get all taxonomies from the MISP instance.
     ///
     /// Corresponds to: GET /taxonomies
@@ -370,6 +1574,31 @@ impl MispClient {
         self.misp_get(&endpoint).await
     }
 
+    /// Like `get_taxonomy_extended_with_tags`, but a response that does not
+    /// match the typed shape (`existing_tag` is an object on some MISP
+    /// versions) comes back as raw JSON instead of an error.
+    pub async fn get_taxonomy_extended_with_tags_lenient(&self, taxonomy_id: &str) -> Result<Lenient<GetTaxonomyExtendedWithTagsResponse>, MispError> {
+        info!("Fetching taxonomy extended with tags for ID: {}", taxonomy_id);
+        let endpoint = format!("/taxonomies/taxonomy_tags/{}", taxonomy_id);
+        self.misp_get_lenient(&endpoint).await
+    }
+
+    /// Get the taxonomy with the given namespace (e.g. `tlp`) and its tags.
+    ///
+    /// Resolves the namespace through GET /taxonomies, then fetches
+    /// GET /taxonomies/taxonomy_tags/{taxonomy_id}.
+    pub async fn get_taxonomy_tags_by_namespace(&self, namespace: &str) -> Result<GetTaxonomyExtendedWithTagsResponse, MispError> {
+        let taxonomies = self.get_taxonomies().await?;
+        let taxonomy = taxonomies
+            .into_iter()
+            .find(|entry| entry.taxonomy.namespace.eq_ignore_ascii_case(namespace))
+            .ok_or_else(|| MispError::NotFound {
+                resource: format!("taxonomy with namespace '{}'", namespace),
+                details: None,
+            })?;
+        self.get_taxonomy_extended_with_tags(&taxonomy.taxonomy.id).await
+    }
+
     /// Get sightings for a specific event by ID or UUID from MISP.
     ///
     /// Corresponds to: GET /sightings/index/{eventId}
@@ -382,6 +1611,22 @@ impl MispClient {
         self.misp_get(&endpoint).await
     }
 
+    /// Get the audit log entries of an event and its attributes/objects.
+    /// 
+    /// Corresponds to: GET /logs/event_index/{eventId}
+    /// 
+    /// Requires audit logging on the MISP instance and a user allowed to see
+    /// the event's logs; returns entries oldest first.
+    pub async fn get_event_logs(&self, event_id: &str) -> Result<Vec<LogEntry>, MispError> {
+        info!("Fetching logs for event ID: {}", event_id);
+        let endpoint = format!("/logs/event_index/{}", event_id);
+        let response: EventLogsResponse = self.misp_get(&endpoint).await?;
+        let mut entries: Vec<LogEntry> = response.into_iter().map(|wrapper| wrapper.log).collect();
+        // "YYYY-MM-DD HH:MM:SS" sorts chronologically as text
+        entries.sort_by(|a, b| a.created.cmp(&b.created));
+        Ok(entries)
+    }
+
     /// Get all warninglists from MISP.
     ///
     /// Corresponds to: GET /warninglists
@@ -398,11 +1643,30 @@ impl MispClient {
         let endpoint = format!("/warninglists/view/{}", warninglist_id);
         // The API returns {"Warninglist": {...}}, so we need to extract the inner object.
         let response: serde_json::Value = self.misp_get(&endpoint).await?;
-        let warninglist = serde_json::from_value(response["Warninglist"].clone())
+        let mut warninglist: Warninglist = serde_json::from_value(response["Warninglist"].clone())
             .map_err(MispError::Json)?;
+        // Some MISP versions put entries and types next to "Warninglist" instead of inside it
+        if warninglist.warninglist_entry.is_none() && response.get("WarninglistEntry").is_some() {
+            warninglist.warninglist_entry = serde_json::from_value(response["WarninglistEntry"].clone())
+                .map_err(MispError::Json)?;
+        }
+        if warninglist.warninglist_type.is_none() && response.get("WarninglistType").is_some() {
+            warninglist.warninglist_type = serde_json::from_value(response["WarninglistType"].clone())
+                .map_err(MispError::Json)?;
+        }
         Ok(warninglist)
     }
 
+    /// Get the entries (values) of a warninglist.
+    ///
+    /// MISP has no paginated entries endpoint, so this reads them from
+    /// GET /warninglists/view/{warninglist_id}; callers page through the result.
+    pub async fn get_warninglist_entries(&self, warninglist_id: &str) -> Result<(Warninglist, Vec<WarninglistEntry>), MispError> {
+        let mut warninglist = self.get_warninglist_by_id(warninglist_id).await?;
+        let entries = warninglist.warninglist_entry.take().unwrap_or_default();
+        Ok((warninglist, entries))
+    }
+
     /// Search warninglists by value (POST /warninglists)
     pub async fn search_warninglists(&self, value: &str) -> Result<WarninglistsResponse, MispError> {
         info!("Searching warninglists with value: {}", value);
@@ -414,6 +1678,20 @@ impl MispClient {
         self.misp_post("/warninglists", &request_payload).await
     }
 
+    /// Check values against the enabled warninglists.
+    ///
+    /// Corresponds to: POST /warninglists/checkValue
+    /// Only values with at least one hit appear in the result.
+    pub async fn check_warninglist_values(&self, values: &[String]) -> Result<CheckWarninglistValuesResponse, MispError> {
+        info!("Checking {} values against warninglists", values.len());
+        let response: serde_json::Value = self.misp_post("/warninglists/checkValue", &values).await?;
+        // No hits at all comes back as an empty PHP array, i.e. `[]`
+        if response.as_array().is_some_and(|hits| hits.is_empty()) {
+            return Ok(CheckWarninglistValuesResponse::new());
+        }
+        Ok(serde_json::from_value(response)?)
+    }
+
     /// Get all noticelists from MISP.
     ///
     /// Corresponds to: GET /noticelists
@@ -461,10 +1739,32 @@ impl MispClient {
     pub async fn get_collection_by_id(&self, collection_id: &str) -> Result<Collection, MispError> {
         let endpoint = format!("/collections/view/{}", collection_id);
         let response: serde_json::Value = self.misp_get(&endpoint).await?;
-        // Assuming the API returns { "Collection": { ... } }
-        let collection = serde_json::from_value::<Collection>(response["Collection"].clone())
-            .map_err(MispError::Json)?;
-        Ok(collection)
+        collection_from_response(response)
+    }
+
+    /// Get a sharing group with its member organisations and servers
+    /// (GET /sharing_groups/view/{sharingGroupId})
+    pub async fn get_sharing_group_by_id(&self, sharing_group_id: &str) -> Result<GetSharingGroupResponse, MispError> {
+        let endpoint = format!("/sharing_groups/view/{}", sharing_group_id);
+        self.misp_get(&endpoint).await
+    }
+
+    /// Create a collection (POST /collections/add)
+    pub async fn create_collection(&self, collection: &CreateCollectionRequest) -> Result<Option<Collection>, MispError> {
+        info!("Creating {} collection '{}'", collection.type_, collection.name);
+        let response: Option<serde_json::Value> = self.misp_write("/collections/add", collection).await?;
+        response.map(collection_from_response).transpose()
+    }
+
+    /// Add an event or galaxy cluster to a collection (POST /collectionElements/add/{collectionId})
+    pub async fn add_collection_element(
+        &self,
+        collection_id: &str,
+        element: &AddCollectionElementRequest,
+    ) -> Result<Option<CollectionElement>, MispError> {
+        let endpoint = format!("/collectionElements/add/{}", collection_id);
+        let wrapper: Option<CollectionElementWrapper> = self.misp_write(&endpoint, element).await?;
+        Ok(wrapper.map(|wrapper| wrapper.collection_element))
     }
 
     /// Get a list of collections with filtering.
@@ -491,6 +1791,10 @@ impl MispClient {
 
     /// Get all attributes (GET /attributes)
     pub async fn list_attributes(&self) -> Result<Vec<Attribute>, MispError> {
+        if !self.search_defaults.is_empty() {
+            // GET /attributes takes no filters; an unfiltered restSearch does
+            return Ok(self.attributes_rest_search(&AttributeRestSearchRequest::default()).await?.response.attribute);
+        }
         self.misp_get("/attributes").await
     }
 
@@ -510,16 +1814,174 @@ impl MispClient {
         self.misp_get(&endpoint).await
     }
 
+    /// Get all decaying models with their parameters (GET /decayingModel/index)
+    pub async fn get_decaying_models(&self) -> Result<Vec<FullDecayingModel>, MispError> {
+        info!("Fetching decaying models");
+        let response: Vec<serde_json::Value> = self.misp_get("/decayingModel/index").await?;
+        // Each entry is wrapped as {"DecayingModel": {...}}; accept bare models too
+        response
+            .into_iter()
+            .map(|mut entry| {
+                let model = match entry.get_mut("DecayingModel") {
+                    Some(model) => model.take(),
+                    None => entry,
+                };
+                serde_json::from_value(model).map_err(MispError::Json)
+            })
+            .collect()
+    }
+
     /// Get list of available attribute types, categories, and sane defaults (GET /attributes/describeTypes)
     pub async fn describe_attribute_types(&self) -> Result<DescribeTypesResult, MispError> {
         let wrapper: DescribeTypesWrapper = self.misp_get("/attributes/describeTypes").await?;
         Ok(wrapper.result)
     }
 
+    /// Get the describeTypes result, fetching it from MISP only on first use.
+    ///
+    /// The type/category lists only change on MISP upgrades, so the first
+    /// successful response is kept for the lifetime of the client.
+    pub async fn describe_attribute_types_cached(&self) -> Result<&DescribeTypesResult, MispError> {
+        self.describe_types_cache
+            .get_or_try_init(|| self.describe_attribute_types())
+            .await
+    }
+
     /// Search for attributes with filters and pagination.
     /// Mirrors the /attributes/restSearch endpoint.
+    /// Default search filters are merged in first.
     pub async fn attributes_rest_search(&self, params: &AttributeRestSearchRequest) -> Result<AttributeListResponse, MispError> {
-        self.misp_post("/attributes/restSearch", params).await
+        let params = self.search_defaults.apply_to_attributes(params);
+        let mut response: AttributeListResponse = self.misp_post("/attributes/restSearch", &params).await?;
+        response
+            .response
+            .attribute
+            .retain(|attribute| self.search_defaults.allows_distribution(Some(&attribute.distribution)));
+        Ok(response)
+    }
+
+    /// Export matching attributes as MISP's CSV.
+    ///
+    /// Corresponds to: POST /attributes/restSearch with `returnFormat: csv`
+    /// and `Accept: text/csv`. The CSV has no distribution column, so the
+    /// export is refused while a minimum distribution filter is configured.
+    pub async fn attributes_rest_search_csv(&self, params: &AttributeRestSearchRequest) -> Result<String, MispError> {
+        if self.search_defaults.min_distribution.is_some() {
+            return Err(MispError::Config {
+                message: "CSV export cannot apply the minimum distribution filter".to_string(),
+            });
+        }
+        let params = AttributeRestSearchRequest {
+            return_format: Some("csv".to_string()),
+            ..self.search_defaults.apply_to_attributes(params)
+        };
+        self.misp_post_text("/attributes/restSearch", &params, "text/csv").await
+    }
+
+    /// Add an attribute to an event (POST /attributes/add/{eventId})
+    /// Write defaults fill in a missing distribution first.
+    pub async fn add_attribute(&self, event_id: &str, request: &AddAttributeRequest) -> Result<Option<Attribute>, MispError> {
+        let request = self.write_defaults.apply_to_attribute(request);
+        let endpoint = format!("/attributes/add/{}", event_id);
+        let wrapper: Option<AttributeWrapper> = self.misp_write(&endpoint, &request).await?;
+        Ok(wrapper.map(|wrapper| wrapper.attribute))
+    }
+
+    /// Edit fields of an existing attribute (POST /attributes/edit/{attributeId})
+    pub async fn edit_attribute(&self, attribute_id: &str, changes: &serde_json::Value) -> Result<Option<Attribute>, MispError> {
+        let endpoint = format!("/attributes/edit/{}", attribute_id);
+        let wrapper: Option<AttributeWrapper> = self.misp_write(&endpoint, changes).await?;
+        Ok(wrapper.map(|wrapper| wrapper.attribute))
+    }
+
+    /// Undo the soft delete of an attribute (POST /attributes/restore/{attributeId})
+    ///
+    /// Hard-deleted attributes no longer exist and cannot be restored; MISP
+    /// reports them as not found.
+    pub async fn restore_attribute(&self, attribute_id: &str) -> Result<Option<Attribute>, MispError> {
+        info!("Restoring attribute {}", attribute_id);
+        let endpoint = format!("/attributes/restore/{}", attribute_id);
+        let wrapper: Option<AttributeWrapper> = self.misp_write(&endpoint, &serde_json::json!({})).await?;
+        Ok(wrapper.map(|wrapper| wrapper.attribute))
+    }
+
+    /// Attach a tag to an attribute by UUID (POST /tags/attachTagToObject)
+    pub async fn tag_attribute(&self, attribute_uuid: &str, tag: &str) -> Result<(), MispError> {
+        let body = serde_json::json!({ "uuid": attribute_uuid, "tag": tag });
+        self.misp_write::<serde_json::Value, _>("/tags/attachTagToObject", &body).await?;
+        Ok(())
+    }
+
+    /// Link an object to another object or attribute (POST /objectReferences/add/{objectId})
+    pub async fn add_object_reference(&self, object_id: &str, reference: &AddObjectReferenceRequest) -> Result<Option<ObjectReference>, MispError> {
+        let endpoint = format!("/objectReferences/add/{}", object_id);
+        let wrapper: Option<ObjectReferenceWrapper> = self.misp_write(&endpoint, reference).await?;
+        Ok(wrapper.map(|wrapper| wrapper.object_reference))
+    }
+
+    /// Add an attribute unless the event already holds the same type/value.
+    ///
+    /// Looks for an existing match with /attributes/restSearch first. When one
+    /// is found its comment is updated if a different one was given; otherwise
+    /// the attribute is created. Tags are attached in both cases. Returns the
+    /// attribute and whether it was newly created.
+    pub async fn upsert_attribute(
+        &self,
+        event_id: &str,
+        request: &AddAttributeRequest,
+        tags: &[String],
+    ) -> Result<(Attribute, bool), MispError> {
+        let (mut attribute, created) = match self.find_event_attribute(event_id, request).await? {
+            Some(attribute) => {
+                debug!("Attribute {} already present in event {}", attribute.id, event_id);
+                (attribute, false)
+            }
+            None => match self.add_attribute(event_id, request).await? {
+                Some(attribute) => (attribute, true),
+                // Saved without a body: look it up to get the UUID to tag
+                None => {
+                    let attribute = self.find_event_attribute(event_id, request).await?.ok_or_else(|| MispError::NotFound {
+                        resource: format!("attribute {} just added to event {}", request.value, event_id),
+                        details: None,
+                    })?;
+                    (attribute, true)
+                }
+            },
+        };
+
+        if !created {
+            if let Some(comment) = request.comment.as_ref().filter(|c| attribute.comment.as_ref() != Some(*c)) {
+                match self.edit_attribute(&attribute.id, &serde_json::json!({ "comment": comment })).await? {
+                    Some(edited) => attribute = edited,
+                    None => attribute.comment = Some(comment.clone()),
+                }
+            }
+        }
+        for tag in tags {
+            self.tag_attribute(&attribute.uuid, tag).await?;
+        }
+
+        Ok((attribute, created))
+    }
+
+    /// The attribute of an event with the request's type and value, if any.
+    async fn find_event_attribute(&self, event_id: &str, request: &AddAttributeRequest) -> Result<Option<Attribute>, MispError> {
+        let search = AttributeRestSearchRequest {
+            eventid: Some(event_id.to_string()),
+            value: Some(serde_json::Value::String(request.value.clone())),
+            attribute_type: Some(request.attribute_type.clone()),
+            ..Default::default()
+        };
+        // Bypass the default search filters: the match must be found whatever
+        // its org, tags or distribution. restSearch also matches composite
+        // value halves, so confirm the hit
+        Ok(self
+            .misp_post::<AttributeListResponse, _>("/attributes/restSearch", &search)
+            .await?
+            .response
+            .attribute
+            .into_iter()
+            .find(|a| a.value == request.value && a.attribute_type == request.attribute_type))
     }
 
     /// Fetch all events from the MISP instance (GET /events).
@@ -527,23 +1989,13 @@ impl MispClient {
     ///
     /// # Errors
     /// Returns an error if the HTTP request fails or the response cannot be deserialized.
-    pub async fn get_events(&self) -> Result<Vec<Event>, anyhow::Error> {
-        // Construct the endpoint URL
-        let url = format!("{}/events", self.base_url);
-
-        // Send the GET request with required headers
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .header("Accept", "application/json")
-            .send()
-            .await?
-            .error_for_status()?; // Return error if not 2xx
-        // Deserialize the response body as a Vec<Event>
-        let events = response.json::<Vec<Event>>().await?;
-
-        Ok(events)
+    pub async fn get_events(&self) -> Result<Vec<Event>, MispError> {
+        info!("Fetching all events");
+        if !self.search_defaults.is_empty() {
+            // GET /events takes no filters; the index search does
+            return self.search_events(&EventIndexRequest::default()).await;
+        }
+        self.misp_get("/events").await
     }
 
     /// Get a single event by its ID from MISP.
@@ -561,67 +2013,177 @@ impl MispClient {
 
     /// Search for events using POST /events/index.
     /// Accepts an EventIndexRequest and returns a vector of Event objects.
+    /// Default search filters are merged in first.
     pub async fn search_events(&self, request: &EventIndexRequest) -> Result<Vec<Event>, MispError> {
         info!("Searching events with POST /events/index");
-        self.misp_post("/events/index", request).await
+        let request = self.search_defaults.apply_to_event_index(request);
+        let mut events: Vec<Event> = self.misp_post("/events/index", &request).await?;
+        events.retain(|event| self.search_defaults.allows_distribution(event.distribution.as_deref()));
+        Ok(events)
     }
 
     /// Mirrors the /events/restSearch endpoint.
     /// Accepts an EventsRestSearchRequest and returns EventsRestSearchResponse.
+    /// Default search filters are merged in first.
     pub async fn events_rest_search(&self, params: &EventsRestSearchRequest) -> Result<EventsRestSearchResponse, MispError> {
-        self.misp_post("/events/restSearch", params).await
+        let params = self.search_defaults.apply_to_events(params);
+        let mut response: EventsRestSearchResponse = self.misp_post("/events/restSearch", &params).await?;
+        response
+            .response
+            .retain(|wrapper| self.search_defaults.allows_distribution(wrapper.event.distribution.as_deref()));
+        Ok(response)
+    }
+
+    /// Count the events matching `params` without downloading them.
+    ///
+    /// Asks /events/restSearch for one minimal event and reads the total
+    /// from MISP's `X-Result-Count` header; MISP versions that do not send
+    /// it get the whole minimal list counted instead. With a distribution
+    /// floor configured the header would count events the floor hides, so
+    /// the events are then fetched as metadata, `COUNT_PAGE_SIZE` at a time,
+    /// and counted after filtering, as `events_rest_search` returns them.
+    pub async fn count_events(&self, params: &EventsRestSearchRequest) -> Result<usize, MispError> {
+        if self.search_defaults.min_distribution.is_some() {
+            let mut count = 0;
+            for page in 1.. {
+                let params = EventsRestSearchRequest {
+                    metadata: Some(true),
+                    limit: Some(COUNT_PAGE_SIZE),
+                    page: Some(page),
+                    ..self.search_defaults.apply_to_events(params)
+                };
+                let response: EventsRestSearchResponse = self.misp_post("/events/restSearch", &params).await?;
+                count += response
+                    .response
+                    .iter()
+                    .filter(|wrapper| self.search_defaults.allows_distribution(wrapper.event.distribution.as_deref()))
+                    .count();
+                if response.response.len() < COUNT_PAGE_SIZE as usize {
+                    break;
+                }
+            }
+            return Ok(count);
+        }
+        let probe = EventsRestSearchRequest {
+            minimal: Some(true),
+            limit: Some(1),
+            page: Some(1),
+            ..self.search_defaults.apply_to_events(params)
+        };
+        let response = self.misp_post_response("/events/restSearch", &probe).await?;
+        let count = response
+            .headers()
+            .get("X-Result-Count")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<usize>().ok());
+        // Still read the body, so MISP errors surface as usual
+        let _: serde_json::Value = self.handle_response(response).await?;
+        match count {
+            Some(count) => Ok(count),
+            None => {
+                debug!("No X-Result-Count header from MISP, counting the minimal event list");
+                Ok(self.events_rest_search_minimal(params).await?.response.len())
+            }
+        }
+    }
+
+    /// Search events in minimal mode (/events/restSearch with `minimal: true`).
+    /// Returns only ids, uuids, timestamps and the published flag per event.
+    /// Minimal events carry no distribution, so with a distribution floor
+    /// configured the events are fetched as metadata, filtered, and cut
+    /// down to the minimal fields instead.
+    pub async fn events_rest_search_minimal(&self, params: &EventsRestSearchRequest) -> Result<MinimalEventsRestSearchResponse, MispError> {
+        if self.search_defaults.min_distribution.is_some() {
+            let params = EventsRestSearchRequest {
+                metadata: Some(true),
+                ..params.clone()
+            };
+            let response = self.events_rest_search(&params).await?.response;
+            return Ok(MinimalEventsRestSearchResponse {
+                response: response
+                    .into_iter()
+                    .map(|wrapper| MinimalEventWrapper {
+                        event: MinimalEvent {
+                            id: wrapper.event.id,
+                            uuid: wrapper.event.uuid,
+                            timestamp: wrapper.event.timestamp,
+                            sighting_timestamp: wrapper.event.sighting_timestamp,
+                            published: wrapper.event.published,
+                            orgc_uuid: wrapper.event.orgc.and_then(|orgc| orgc.uuid),
+                        },
+                    })
+                    .collect(),
+            });
+        }
+        let params = EventsRestSearchRequest {
+            minimal: Some(true),
+            ..self.search_defaults.apply_to_events(params)
+        };
+        self.misp_post("/events/restSearch", &params).await
+    }
+
+    /// Get all object templates (GET /objectTemplates)
+    pub async fn get_object_templates(&self) -> Result<Vec<ObjectTemplateEntry>, MispError> {
+        self.misp_get("/objectTemplates").await
     }
 
     /// Fetch a MISP Object by its numeric ID or UUID.
     /// Returns the full Object as defined in types.rs.
-    pub async fn get_object_by_id(&self, object_id: &str) -> Result<Object, anyhow::Error> {
-        // Build the endpoint URL
-        let url = format!("{}/objects/view/{}", self.base_url, object_id);
-        // Send GET request with authentication headers
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .header("Accept", "application/json")
-            .send()
-            .await?
-            .error_for_status()?;
-
-    // Parse the JSON response
-    let json: serde_json::Value = response.json().await?;
-    // Log the Object field for debugging
-    // println!("DEBUG: json[\"Object\"] = {}", json["Object"]);
-    // Extract the "Object" field and deserialize
-    let object: Object = serde_json::from_value(json["Object"].clone())?;
-    Ok(object)
+    pub async fn get_object_by_id(&self, object_id: &str) -> Result<Object, MispError> {
+        info!("Fetching object with ID: {}", object_id);
+        let endpoint = format!("/objects/view/{}", object_id);
+        // The API returns {"Object": {...}}, so we need to extract the inner object.
+        let response: serde_json::Value = self.misp_get(&endpoint).await?;
+        Ok(serde_json::from_value(response["Object"].clone())?)
     }
 
     /// Fetch a filtered and paginated list of objects using /objects/restsearch.
     /// Returns a vector of Object structs as per the official schema.
+    /// Default search filters are merged in first.
     pub async fn objects_rest_search(&self, params: &ObjectsRestSearchRequest) -> Result<Vec<Object>, anyhow::Error> {
+        let params = &self.search_defaults.apply_to_objects(params);
         let url = format!("{}/objects/restsearch", self.base_url);
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("Authorization", &self.api_key)
             .header("Accept", "application/json")
             .header("Content-Type", "application/json")
-            .json(params)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(params);
+        let response = self.send(request).await?.error_for_status()?;
 
-        // The response is expected to be: { "response": [ { "Object": { ... } }, ... ] }
-        let json: serde_json::Value = response.json().await?;
-        let objects = json["response"]
-            .as_array()
-            .ok_or_else(|| anyhow::anyhow!("Missing 'response' array in /objects/restsearch response"))?
-            .iter()
-            .filter_map(|entry| entry.get("Object"))
-            .map(|obj| serde_json::from_value(obj.clone()))
+        // Usually { "response": [ { "Object": { ... } }, ... ] }, but MISP versions
+        // also answer { "response": { "Object": [ ... ] } }, a bare array, or
+        // `null` when nothing matches
+        let json: serde_json::Value = serde_json::from_str(&self.read_body(response).await?)?;
+        let body = match json {
+            serde_json::Value::Object(mut envelope) if envelope.contains_key("response") => {
+                envelope.remove("response").unwrap_or_default()
+            }
+            other => other,
+        };
+        let entries = match body {
+            serde_json::Value::Null => Vec::new(),
+            serde_json::Value::Array(entries) => entries
+                .into_iter()
+                .filter_map(|mut entry| entry.get_mut("Object").map(serde_json::Value::take))
+                .collect(),
+            serde_json::Value::Object(mut inner) => match inner.remove("Object") {
+                Some(serde_json::Value::Array(objects)) => objects,
+                Some(serde_json::Value::Null) | None => Vec::new(),
+                Some(object) => vec![object],
+            },
+            other => anyhow::bail!("Unexpected /objects/restsearch response: {}", other),
+        };
+        let objects = entries
+            .into_iter()
+            .map(serde_json::from_value)
             .collect::<Result<Vec<Object>, _>>()?;
 
-        Ok(objects)
+        Ok(objects
+            .into_iter()
+            .filter(|object| self.search_defaults.allows_distribution(object.distribution.as_deref()))
+            .collect())
     }
 
 }
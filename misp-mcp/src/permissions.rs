@@ -0,0 +1,68 @@
+//! API key permission checks.
+//!
+//! Compares the role behind the API key (from `/users/view/me`) with the
+//! MISP permissions the registered write tools need, so a read-only key
+//! behind write tools shows up at startup rather than as 403s mid-session.
+
+use crate::misp_client::{MispClient, MispError};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// MISP permissions each write tool needs.
+const WRITE_TOOL_PERMISSIONS: &[(&str, &[&str])] = &[
+    ("add_attribute", &["perm_add"]),
+    ("upsert_attribute", &["perm_add", "perm_modify", "perm_tagger"]),
+    ("restore_attribute", &["perm_modify"]),
+    ("add_object_reference", &["perm_add"]),
+    ("create_collection", &["perm_add"]),
+    ("add_to_collection", &["perm_add"]),
+    ("auto_tag_attribute", &["perm_tagger"]),
+];
+
+/// What the API key may do, and which registered tools it falls short for.
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionReport {
+    /// Email of the user the key belongs to
+    pub user: String,
+    /// Organisation of that user
+    pub org: Option<String>,
+    /// Role name
+    pub role: String,
+    /// `perm_*` flags the role grants
+    pub granted: Vec<String>,
+    /// Registered write tools mapped to the permissions the role lacks for them
+    pub missing: BTreeMap<String, Vec<&'static str>>,
+}
+
+impl PermissionReport {
+    /// Whether every registered write tool is usable with this key.
+    pub fn is_sufficient(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Look up the key's role and check it against the `registered` tools.
+pub async fn probe(client: &MispClient, registered: &HashSet<String>) -> Result<PermissionReport, MispError> {
+    let me = client.get_current_user().await?;
+    let granted = me.role.granted_permissions();
+    let has = |permission: &str| {
+        granted.iter().any(|name| name == permission)
+            // Modifying the organisation's data covers modifying one's own
+            || (permission == "perm_modify" && granted.iter().any(|name| name == "perm_modify_org"))
+    };
+    let missing = WRITE_TOOL_PERMISSIONS
+        .iter()
+        .filter(|(tool, _)| registered.contains(*tool))
+        .filter_map(|(tool, needed)| {
+            let lacking: Vec<&'static str> = needed.iter().copied().filter(|permission| !has(permission)).collect();
+            (!lacking.is_empty()).then(|| (tool.to_string(), lacking))
+        })
+        .collect();
+    Ok(PermissionReport {
+        user: me.user.email,
+        org: me.organisation.name,
+        role: me.role.name,
+        granted,
+        missing,
+    })
+}
@@ -0,0 +1,63 @@
+//! Curated prompts for common MISP workflows.
+//!
+//! Each prompt spells out which MISP tools to call and in what order, so a
+//! client can start an investigation from `prompts/get` instead of having
+//! the user describe the workflow.
+
+use mcp_core::{McpResult, PromptInput, PromptMessage, PromptTemplate, Server};
+
+/// Add the MISP workflow prompts to the server.
+pub fn register_misp_prompts(server: &mut Server) {
+    server.add_prompt(
+        PromptTemplate::new(
+            "triage_event",
+            "Triage a MISP event: what it is about, how actionable its indicators are and what it relates to",
+            triage_event,
+        )
+        .with_argument("event_id", "ID or UUID of the event to triage", true),
+    );
+    server.add_prompt(
+        PromptTemplate::new(
+            "hunt_indicator",
+            "Hunt for an indicator across MISP: where it was seen, with what context, and what it leads to",
+            hunt_indicator,
+        )
+        .with_argument("value", "Indicator value; defanged values are accepted", true)
+        .with_argument("type", "MISP attribute type of the value, e.g. ip-dst or sha256", false),
+    );
+}
+
+fn triage_event(input: &PromptInput) -> McpResult<Vec<PromptMessage>> {
+    let event_id = input.get_argument("event_id")?;
+    Ok(vec![PromptMessage::user(format!(
+        "Triage MISP event {event_id} using the MISP tools, in this order:\n\
+         \n\
+         1. Call event_summary with event_id \"{event_id}\" for the event's subject, threat level, organisations, tags and galaxies.\n\
+         2. Call event_warninglist_coverage with event_id \"{event_id}\" to see how many detection indicators are known benign or noisy.\n\
+         3. Call related_events with event_id \"{event_id}\" to find the events sharing indicators with it.\n\
+         4. Call event_timeline with event_id \"{event_id}\" to see when the indicators were active and sighted.\n\
+         \n\
+         Then write a short triage note: what the event describes, how much of it is actionable \
+         (indicators flagged for detection and not on warninglists), the most relevant related events, \
+         and a recommendation (act on, monitor or discard). Quote attribute values exactly as MISP returned them."
+    ))])
+}
+
+fn hunt_indicator(input: &PromptInput) -> McpResult<Vec<PromptMessage>> {
+    let value = input.get_argument("value")?;
+    let type_hint = input
+        .get_optional_argument("type")
+        .map(|attribute_type| format!(" and type \"{}\"", attribute_type))
+        .unwrap_or_default();
+    Ok(vec![PromptMessage::user(format!(
+        "Hunt for the indicator \"{value}\" in MISP using the MISP tools, in this order:\n\
+         \n\
+         1. Call normalize_indicators with values [\"{value}\"] and use the normalized value from here on.\n\
+         2. Call search_indicators with that value{type_hint} to find the events and attributes containing it, with their tags.\n\
+         3. Call search_attributes_with_sightings with that value{type_hint} to see how often it was sighted and whether it was reported as a false positive.\n\
+         4. If it was found, call pivot_from_attribute with that value{type_hint} to collect the indicators that appear alongside it.\n\
+         \n\
+         Then report whether the indicator is known to MISP, the events and threat context it appears in, \
+         its sighting history, and the related indicators worth hunting for next. If MISP has no match, say so plainly."
+    ))])
+}
@@ -0,0 +1,142 @@
+//! Markdown event briefings.
+//!
+//! Renders the headline facts of a MISP event (info, date, threat level,
+//! organisations, attribute counts per category, galaxies and tags) as a
+//! short markdown document meant to be read by people.
+
+use misp_types::{Attribute, Distribution, Event};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Threat level name for a `threat_level_id`, as displayed by MISP.
+fn threat_level_name(id: &str) -> Option<&'static str> {
+    match id.trim() {
+        "1" => Some("High"),
+        "2" => Some("Medium"),
+        "3" => Some("Low"),
+        "4" => Some("Undefined"),
+        _ => None,
+    }
+}
+
+/// Analysis stage name for an event `analysis` value.
+fn analysis_name(analysis: &str) -> Option<&'static str> {
+    match analysis.trim() {
+        "0" => Some("Initial"),
+        "1" => Some("Ongoing"),
+        "2" => Some("Completed"),
+        _ => None,
+    }
+}
+
+/// Markdown-safe inline text: table pipes and newlines would break the layout.
+fn inline(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn count_attributes<'a>(counts: &mut BTreeMap<&'a str, usize>, attributes: impl Iterator<Item = &'a Attribute>) {
+    for attribute in attributes.filter(|a| !a.deleted) {
+        *counts.entry(attribute.category.as_str()).or_default() += 1;
+    }
+}
+
+/// Render an event as a markdown brief.
+pub fn event_markdown(event: &Event) -> String {
+    let mut out = String::new();
+    // Writing to a String cannot fail
+    let _ = writeln!(out, "# Event {}: {}", event.id, inline(&event.info));
+    let _ = writeln!(out);
+
+    let threat_level = event
+        .threat_level
+        .as_ref()
+        .and_then(|level| level.name.clone())
+        .or_else(|| {
+            event
+                .threat_level_id
+                .as_deref()
+                .map(|id| threat_level_name(id).map(str::to_string).unwrap_or_else(|| id.to_string()))
+        });
+    let org_name = |org: &Option<misp_types::Organisation>, id: &Option<String>| {
+        org.as_ref()
+            .and_then(|o| o.name.clone())
+            .or_else(|| id.as_ref().map(|id| format!("organisation {}", id)))
+    };
+
+    let mut facts: Vec<(&str, String)> = Vec::new();
+    if let Some(date) = event.date.as_deref() {
+        facts.push(("Date", date.to_string()));
+    }
+    if let Some(level) = threat_level {
+        facts.push(("Threat level", level));
+    }
+    if let Some(analysis) = event.analysis.as_deref() {
+        facts.push(("Analysis", analysis_name(analysis).unwrap_or(analysis).to_string()));
+    }
+    if let Some(creator) = org_name(&event.orgc, &event.orgc_id) {
+        facts.push(("Creator org", creator));
+    }
+    if let Some(owner) = org_name(&event.org, &event.org_id) {
+        facts.push(("Owner org", owner));
+    }
+    if let Some(distribution) = event.distribution.as_deref() {
+        let label = distribution
+            .parse::<Distribution>()
+            .map(|d| d.name().to_string())
+            .unwrap_or_else(|_| distribution.to_string());
+        facts.push(("Distribution", label));
+    }
+    facts.push(("Published", if event.published == Some(true) { "yes" } else { "no" }.to_string()));
+    if let Some(uuid) = event.uuid.as_deref() {
+        facts.push(("UUID", format!("`{}`", uuid)));
+    }
+    for (label, value) in facts {
+        let _ = writeln!(out, "- **{}:** {}", label, inline(&value));
+    }
+
+    let mut counts = BTreeMap::new();
+    count_attributes(&mut counts, event.attribute.iter());
+    for object in &event.object {
+        count_attributes(&mut counts, object.attributes.iter().flatten());
+    }
+    let total: usize = counts.values().sum();
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Attributes ({})", total);
+    let _ = writeln!(out);
+    if counts.is_empty() {
+        let _ = writeln!(out, "No attributes.");
+    } else {
+        let _ = writeln!(out, "| Category | Count |");
+        let _ = writeln!(out, "|---|---:|");
+        // Largest categories first; BTreeMap order breaks ties alphabetically
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        for (category, count) in counts {
+            let _ = writeln!(out, "| {} | {} |", inline(category), count);
+        }
+    }
+    if !event.object.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Objects: {}", event.object.len());
+    }
+
+    if !event.galaxy.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Galaxies");
+        let _ = writeln!(out);
+        for galaxy in &event.galaxy {
+            let _ = writeln!(out, "- {} (`{}`)", inline(&galaxy.name), inline(&galaxy.galaxy_type));
+        }
+    }
+
+    let tags: Vec<&str> = event.tag.iter().filter_map(|tag| tag.name.as_deref()).collect();
+    if !tags.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Tags");
+        let _ = writeln!(out);
+        let tags: Vec<String> = tags.iter().map(|tag| format!("`{}`", tag)).collect();
+        let _ = writeln!(out, "{}", tags.join(", "));
+    }
+
+    out
+}
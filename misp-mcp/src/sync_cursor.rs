@@ -0,0 +1,67 @@
+//! Persistent sync cursors.
+//!
+//! A small JSON file maps each sync key to the modification timestamp of the
+//! newest event a sync has returned, so the next sync with that key only asks
+//! MISP for events modified after it. Keys synced from an additional
+//! instance are prefixed with its name:
+//!
+//! ```json
+//! { "default": 1718000000, "soc-feed": 1717990000, "staging:default": 1717900000 }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+
+/// Sync cursors stored in a JSON file.
+#[derive(Debug)]
+pub struct CursorStore {
+    path: PathBuf,
+    /// Serializes read-modify-write cycles on the file
+    lock: Mutex<()>,
+}
+
+impl CursorStore {
+    /// Use the cursor file at `path`; it is created on the first advance.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    async fn load(&self) -> anyhow::Result<BTreeMap<String, i64>> {
+        match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("Invalid sync cursor file {}: {}", self.path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(anyhow::anyhow!("Failed to read sync cursor file {}: {}", self.path.display(), e)),
+        }
+    }
+
+    /// Timestamp stored for `key`, if a sync with it has run before.
+    pub async fn get(&self, key: &str) -> anyhow::Result<Option<i64>> {
+        let _guard = self.lock.lock().await;
+        Ok(self.load().await?.get(key).copied())
+    }
+
+    /// Move the cursor of `key` forward to `timestamp`; older values are ignored.
+    ///
+    /// The file is replaced through a temporary file so a crash never leaves
+    /// it half written.
+    pub async fn advance(&self, key: &str, timestamp: i64) -> anyhow::Result<()> {
+        let _guard = self.lock.lock().await;
+        let mut cursors = self.load().await?;
+        let cursor = cursors.entry(key.to_string()).or_insert(timestamp);
+        *cursor = (*cursor).max(timestamp);
+
+        let contents = serde_json::to_string_pretty(&cursors)?;
+        let temporary = self.path.with_extension("tmp");
+        tokio::fs::write(&temporary, contents)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to write sync cursor file {}: {}", temporary.display(), e))?;
+        tokio::fs::rename(&temporary, &self.path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to replace sync cursor file {}: {}", self.path.display(), e))
+    }
+}
@@ -0,0 +1,126 @@
+//! Event timeline construction.
+//!
+//! Merges the time-bearing parts of a MISP event (attribute first/last seen,
+//! sightings, event reports, publication) into one chronologically sorted list.
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, TimeZone, Utc};
+use misp_types::{Attribute, Event, Sighting};
+use serde::Serialize;
+
+/// A single point on an event timeline.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    /// RFC 3339 UTC timestamp
+    pub timestamp: String,
+    /// Entry kind, e.g. "attribute_first_seen", "sighting", "event_report"
+    pub kind: &'static str,
+    /// Short human-readable description
+    pub summary: String,
+    #[serde(skip)]
+    sort_key: DateTime<Utc>,
+}
+
+impl TimelineEntry {
+    fn new(at: DateTime<Utc>, kind: &'static str, summary: String) -> Self {
+        Self {
+            timestamp: at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            kind,
+            summary,
+            sort_key: at,
+        }
+    }
+}
+
+/// Parse the timestamp formats MISP uses: unix seconds, unix microseconds
+/// (first_seen/last_seen on some versions), RFC 3339 and plain dates.
+fn parse_misp_time(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if raw.bytes().all(|b| b.is_ascii_digit()) {
+        let n: i64 = raw.parse().ok()?;
+        // Anything past year 5138 in seconds is really microseconds
+        return if n > 99_999_999_999 {
+            Utc.timestamp_micros(n).single()
+        } else {
+            Utc.timestamp_opt(n, 0).single()
+        };
+    }
+    if let Ok(at) = DateTime::parse_from_rfc3339(raw) {
+        return Some(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+}
+
+fn push_attribute(entries: &mut Vec<TimelineEntry>, attribute: &Attribute) {
+    let label = format!("{} {} (attribute {})", attribute.attribute_type, attribute.value, attribute.id);
+    let first_seen = attribute.first_seen.as_deref().and_then(parse_misp_time);
+    let last_seen = attribute.last_seen.as_deref().and_then(parse_misp_time);
+
+    if let Some(at) = first_seen {
+        entries.push(TimelineEntry::new(at, "attribute_first_seen", format!("First seen: {}", label)));
+    }
+    if let Some(at) = last_seen {
+        entries.push(TimelineEntry::new(at, "attribute_last_seen", format!("Last seen: {}", label)));
+    }
+    // Without first/last seen, fall back to when the attribute was recorded
+    if first_seen.is_none() && last_seen.is_none() {
+        if let Some(at) = attribute.timestamp.as_deref().and_then(parse_misp_time) {
+            entries.push(TimelineEntry::new(at, "attribute", format!("Recorded: {}", label)));
+        }
+    }
+}
+
+/// Build a sorted timeline from an event and its sightings.
+pub fn build_timeline(event: &Event, sightings: &[Sighting]) -> Vec<TimelineEntry> {
+    let mut entries = Vec::new();
+
+    if let Some(at) = event.date.as_deref().and_then(parse_misp_time) {
+        entries.push(TimelineEntry::new(at, "event_date", format!("Event {}: {}", event.id, event.info)));
+    }
+    if event.published == Some(true) {
+        if let Some(at) = event.publish_timestamp.as_deref().and_then(parse_misp_time) {
+            entries.push(TimelineEntry::new(at, "event_published", format!("Event {} published", event.id)));
+        }
+    }
+
+    for attribute in &event.attribute {
+        push_attribute(&mut entries, attribute);
+    }
+    for object in &event.object {
+        for attribute in object.attributes.iter().flatten() {
+            push_attribute(&mut entries, attribute);
+        }
+    }
+
+    for report in &event.event_report {
+        if let Some(at) = report.timestamp.as_deref().and_then(parse_misp_time) {
+            let name = report.name.as_deref().unwrap_or("(unnamed)");
+            entries.push(TimelineEntry::new(at, "event_report", format!("Report {}: {}", report.id, name)));
+        }
+    }
+
+    for sighting in sightings {
+        let Some(at) = sighting.date_sighting.as_deref().and_then(parse_misp_time) else {
+            continue;
+        };
+        let (kind, what) = match sighting.type_.as_deref() {
+            Some("1") => ("false_positive", "reported as false positive"),
+            Some("2") => ("expiration", "marked expired"),
+            _ => ("sighting", "sighted"),
+        };
+        let attribute_id = sighting.attribute_id.as_deref().unwrap_or("?");
+        let summary = match sighting.source.as_deref().filter(|s| !s.is_empty()) {
+            Some(source) => format!("Attribute {} {} ({})", attribute_id, what, source),
+            None => format!("Attribute {} {}", attribute_id, what),
+        };
+        entries.push(TimelineEntry::new(at, kind, summary));
+    }
+
+    entries.sort_by_key(|entry| entry.sort_key);
+    entries
+}
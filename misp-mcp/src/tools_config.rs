@@ -0,0 +1,53 @@
+//! Which tools to register.
+//!
+//! Operators can restrict the exposed tools with a JSON file passed as
+//! `--tools-config`:
+//!
+//! ```json
+//! { "allow": ["get_event", "search_events"], "deny": ["delete_event"] }
+//! ```
+//!
+//! With `allow` present only the listed tools are registered; tools named in
+//! `deny` are never registered. Without a file every tool is registered.
+
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// Allowlist and denylist of tool names.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolsConfig {
+    /// Tools to register; `None` means all of them
+    #[serde(default)]
+    pub allow: Option<HashSet<String>>,
+    /// Tools never to register, even when allowed
+    #[serde(default)]
+    pub deny: HashSet<String>,
+}
+
+impl ToolsConfig {
+    /// Read a tools config from a JSON file.
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read tools config {}: {}", path, e))?;
+        serde_json::from_str(&contents).map_err(|e| anyhow::anyhow!("Invalid tools config {}: {}", path, e))
+    }
+
+    /// Whether a tool may be registered.
+    pub fn permits(&self, name: &str) -> bool {
+        !self.deny.contains(name) && self.allow.as_ref().is_none_or(|allow| allow.contains(name))
+    }
+
+    /// Allowed names that are not among the `registered` tools, most likely typos.
+    pub fn unknown_allowed<'a>(&'a self, registered: &HashSet<String>) -> Vec<&'a str> {
+        let mut unknown: Vec<&str> = self
+            .allow
+            .iter()
+            .flatten()
+            .filter(|name| !registered.contains(*name) && !self.deny.contains(*name))
+            .map(String::as_str)
+            .collect();
+        unknown.sort_unstable();
+        unknown
+    }
+}
@@ -0,0 +1,65 @@
+//! stdout of the server binary must carry JSON-RPC and nothing else.
+//!
+//! Runs the real binary against a mock MISP with debug logging on, so any
+//! log line or stray print that reached stdout would break the assertions.
+
+use serde_json::{json, Value};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn tool_call_writes_only_json_rpc_lines_to_stdout() {
+    let misp = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/admin/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&misp)
+        .await;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_misp-mcp"))
+        .args(["--misp-url", &misp.uri(), "--api-key", "test-key"])
+        .env("RUST_LOG", "debug")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .expect("server binary starts");
+
+    let requests = [
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+            "protocolVersion": "2024-11-05", "capabilities": {}, "clientInfo": {"name": "test", "version": "0"}
+        }}),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "get_users", "arguments": {}}}),
+    ];
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    for request in &requests {
+        stdin.write_all(format!("{}\n", request).as_bytes()).await.expect("request written");
+    }
+    // EOF on stdin shuts the server down
+    drop(stdin);
+
+    let output = tokio::time::timeout(Duration::from_secs(30), child.wait_with_output())
+        .await
+        .expect("server exits after stdin closes")
+        .expect("server output");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is UTF-8");
+    assert!(!output.stderr.is_empty(), "debug logs are written to stderr");
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "one line per response, nothing else on stdout:\n{}", stdout);
+    for line in &lines {
+        let message: Value = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("stdout line is not JSON ({}): {}", e, line));
+        assert_eq!(message["jsonrpc"], "2.0", "not a JSON-RPC message: {}", line);
+    }
+    let call: Value = serde_json::from_str(lines[1]).expect("tools/call response");
+    assert_eq!(call["id"], 2);
+    assert!(call["error"].is_null(), "tools/call failed: {}", lines[1]);
+    assert_eq!(call["result"]["content"][0]["text"], "[]");
+}
@@ -0,0 +1,153 @@
+//! Helpers for indicator values as analysts paste them.
+//!
+//! Shared IOCs are often defanged so they can't be clicked or resolved by
+//! accident (`hxxp://evil[.]com`, `1[.]2[.]3[.]4`). MISP stores and matches the
+//! real value, so these need refanging before a search or an add. Analysts
+//! also rarely know which MISP type a pasted value has, so `detect_type`
+//! guesses it from the value's shape.
+
+/// Defang markers and what they stand for, longest first so that e.g.
+/// `[://]` wins over `[:]`. Matched case-insensitively.
+const DEFANG_MARKERS: &[(&str, &str)] = &[
+    (" [dot] ", "."),
+    (" (dot) ", "."),
+    ("[://]", "://"),
+    ("[dot]", "."),
+    ("(dot)", "."),
+    ("{dot}", "."),
+    ("[at]", "@"),
+    ("(at)", "@"),
+    ("{at}", "@"),
+    ("[.]", "."),
+    ("(.)", "."),
+    ("{.}", "."),
+    ("[:]", ":"),
+    ("[/]", "/"),
+    ("[@]", "@"),
+    ("\\.", "."),
+];
+
+/// Defanged URL schemes and their real spelling. Matched case-insensitively
+/// at the start of the value only.
+const DEFANGED_SCHEMES: &[(&str, &str)] = &[
+    ("hxxps", "https"),
+    ("hxxp", "http"),
+    ("hxtps", "https"),
+    ("hxtp", "http"),
+    ("fxp", "ftp"),
+];
+
+/// Undo common defanging of an indicator value.
+///
+/// Values that are not defanged come back unchanged apart from surrounding
+/// whitespace being trimmed.
+///
+/// ```rust
+/// use misp_types::indicators::refang;
+///
+/// assert_eq!(refang("1[.]2[.]3[.]4"), "1.2.3.4");
+/// assert_eq!(refang("hxxps://evil[.]example/x"), "https://evil.example/x");
+/// assert_eq!(refang("admin[at]example(dot)org"), "admin@example.org");
+/// ```
+pub fn refang(value: &str) -> String {
+    let mut value = value.trim().to_string();
+
+    for (scheme, real) in DEFANGED_SCHEMES {
+        let rest = value.get(scheme.len()..).unwrap_or("");
+        let is_scheme = rest.starts_with(':') || rest.starts_with("[:");
+        if is_scheme && value.get(..scheme.len()).is_some_and(|head| head.eq_ignore_ascii_case(scheme)) {
+            value = format!("{}{}", real, rest);
+            break;
+        }
+    }
+
+    for (marker, real) in DEFANG_MARKERS {
+        value = replace_ignore_ascii_case(&value, marker, real);
+    }
+    value
+}
+
+/// Replace every ASCII-case-insensitive occurrence of `from` with `to`.
+fn replace_ignore_ascii_case(haystack: &str, from: &str, to: &str) -> String {
+    let lower = haystack.to_ascii_lowercase();
+    let from = from.to_ascii_lowercase();
+    let mut result = String::with_capacity(haystack.len());
+    let mut last = 0;
+    // ASCII lowercasing keeps byte offsets, so indices into `lower` are valid in `haystack`
+    for (index, _) in lower.match_indices(&from) {
+        result.push_str(&haystack[last..index]);
+        result.push_str(to);
+        last = index + from.len();
+    }
+    result.push_str(&haystack[last..]);
+    result
+}
+
+/// Hex digest lengths and the MISP hash type they identify.
+const HASH_LENGTHS: &[(usize, &str)] = &[(32, "md5"), (40, "sha1"), (64, "sha256"), (128, "sha512")];
+
+/// Guess the MISP attribute type of a (refanged) indicator value.
+///
+/// Recognises IP addresses and CIDR ranges (`ip-dst`), MD5/SHA-1/SHA-256/
+/// SHA-512 digests by length, email addresses, URLs and domain names.
+/// Anything else is `None`.
+///
+/// ```rust
+/// use misp_types::indicators::detect_type;
+///
+/// assert_eq!(detect_type("198.51.100.7"), Some("ip-dst"));
+/// assert_eq!(detect_type("2001:db8::/32"), Some("ip-dst"));
+/// assert_eq!(detect_type(&"ab".repeat(32)), Some("sha256"));
+/// assert_eq!(detect_type("admin@evil.example"), Some("email"));
+/// assert_eq!(detect_type("https://evil.example/payload"), Some("url"));
+/// assert_eq!(detect_type("evil.example"), Some("domain"));
+/// assert_eq!(detect_type("not an indicator"), None);
+/// ```
+pub fn detect_type(value: &str) -> Option<&'static str> {
+    let value = value.trim();
+    if value.is_empty() || value.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    let (address, prefix) = value.split_once('/').unwrap_or((value, ""));
+    if address.parse::<std::net::IpAddr>().is_ok() && (prefix.is_empty() || prefix.parse::<u8>().is_ok()) {
+        return Some("ip-dst");
+    }
+
+    if value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return HASH_LENGTHS
+            .iter()
+            .find(|(length, _)| *length == value.len())
+            .map(|(_, hash_type)| *hash_type);
+    }
+
+    if let Some((scheme, rest)) = value.split_once("://") {
+        let is_scheme = !scheme.is_empty()
+            && scheme.bytes().all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.'));
+        return (is_scheme && !rest.is_empty()).then_some("url");
+    }
+
+    if let Some((local, domain)) = value.split_once('@') {
+        return (!local.is_empty() && is_domain(domain)).then_some("email");
+    }
+
+    is_domain(value).then_some("domain")
+}
+
+/// Whether `value` is a dotted host name ending in an alphabetic TLD.
+fn is_domain(value: &str) -> bool {
+    let value = value.strip_suffix('.').unwrap_or(value);
+    let labels: Vec<&str> = value.split('.').collect();
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.bytes().all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_'))
+    };
+    let tld = labels.last().copied().unwrap_or("");
+    labels.len() >= 2
+        && labels.iter().all(valid_label)
+        && tld.len() >= 2
+        && (tld.bytes().all(|byte| byte.is_ascii_alphabetic()) || tld.starts_with("xn--"))
+}
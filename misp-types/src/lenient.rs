@@ -0,0 +1,99 @@
+//! Tolerant parsing for responses whose shape depends on the MISP version.
+//!
+//! Fields get added, renamed or change type between MISP releases, and a
+//! strict struct then fails on the whole response. `Lenient<T>` keeps the
+//! raw JSON instead, so callers that only pass the data on can still do so.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A `T` if the JSON matched it, otherwise the JSON itself.
+///
+/// Serializes as whichever it holds, so forwarding a `Lenient<T>` produces
+/// the same output as forwarding `T` whenever parsing succeeded.
+///
+/// ```rust
+/// use misp_types::Lenient;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Entry { tag: String, existing_tag: bool }
+///
+/// let typed: Lenient<Entry> = serde_json::from_str(r#"{"tag": "tlp:red", "existing_tag": false}"#)?;
+/// assert_eq!(typed.as_typed().map(|e| e.tag.as_str()), Some("tlp:red"));
+///
+/// // Newer MISP returns the existing tag as an object
+/// let raw: Lenient<Entry> = serde_json::from_str(r#"{"tag": "tlp:red", "existing_tag": {"id": "1"}}"#)?;
+/// assert!(raw.as_typed().is_none());
+/// assert_eq!(raw.as_raw().map(|v| v["existing_tag"]["id"].clone()), Some("1".into()));
+/// assert!(raw.error().is_some());
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub enum Lenient<T> {
+    /// The JSON matched `T`
+    Typed(T),
+    /// The JSON did not match `T`
+    Raw {
+        /// The JSON as received
+        value: serde_json::Value,
+        /// Why it did not parse as `T`
+        error: String,
+    },
+}
+
+impl<T> Lenient<T> {
+    /// The parsed value, if the JSON matched `T`.
+    pub fn as_typed(&self) -> Option<&T> {
+        match self {
+            Lenient::Typed(typed) => Some(typed),
+            Lenient::Raw { .. } => None,
+        }
+    }
+
+    /// The JSON, if it did not match `T`.
+    pub fn as_raw(&self) -> Option<&serde_json::Value> {
+        match self {
+            Lenient::Typed(_) => None,
+            Lenient::Raw { value, .. } => Some(value),
+        }
+    }
+
+    /// Why the JSON did not match `T`.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Lenient::Typed(_) => None,
+            Lenient::Raw { error, .. } => Some(error),
+        }
+    }
+
+    /// The parsed value, or the parse error as a message.
+    pub fn into_typed(self) -> Result<T, String> {
+        match self {
+            Lenient::Typed(typed) => Ok(typed),
+            Lenient::Raw { error, .. } => Err(error),
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Lenient<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Anything that is valid JSON at all is accepted
+        let value = serde_json::Value::deserialize(deserializer)?;
+        Ok(match T::deserialize(&value) {
+            Ok(typed) => Lenient::Typed(typed),
+            Err(error) => Lenient::Raw {
+                value,
+                error: error.to_string(),
+            },
+        })
+    }
+}
+
+impl<T: Serialize> Serialize for Lenient<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Lenient::Typed(typed) => typed.serialize(serializer),
+            Lenient::Raw { value, .. } => value.serialize(serializer),
+        }
+    }
+}
@@ -12,18 +12,48 @@
 //! - Events and threat intelligence: `Event`, `Attribute`, `Object`, etc.
 //! - Request/response wrappers for specific endpoints
 //!
+//! The `indicators` module refangs defanged indicator values and guesses
+//! their MISP type, and `Lenient` keeps the raw JSON of responses whose shape
+//! differs between MISP versions.
+//!
+//! Some fields are left out by certain MISP endpoints or versions (e.g.
+//! `object_id` on attributes returned by `/attributes/restSearch` with
+//! `requested_attributes`). Those fields deserialize to an empty default
+//! instead of failing the whole response:
+//! - `Attribute`: `event_id`, `object_id`, `category`, `distribution` (empty
+//!   string), `to_ids`, `deleted`, `disable_correlation` (false)
+//! - `Event`: `info`
+//! - `Galaxy`: `description`, `version`, `namespace`
+//! - `GalaxyCluster`: `collection_uuid`, `description`, `source`, `authors`
+//!   (also when `null`), `version`, `distribution`, `org_id`, `orgc_id`,
+//!   `extends_version`, `published`, `deleted`
+//!
+//! ```rust
+//! use misp_types::Attribute;
+//!
+//! let attribute: Attribute = serde_json::from_str(
+//!     r#"{"id": "7", "type": "ip-dst", "value": "203.0.113.7", "uuid": "5f1c0a2e-1b3c-4d5e-8f90-123456789abc"}"#,
+//! )?;
+//! assert_eq!(attribute.object_id, "");
+//! assert!(!attribute.to_ids);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+//!
+//! With the `schema` feature, the `schema` module exposes JSON Schemas for the
+//! request types.
+//!
 //! ## Example
 //!
 //! ```rust
-//! use misp_types::{Event, SearchEventsRequest};
+//! use misp_types::{Event, EventsRestSearchRequest};
 //! use serde_json;
 //!
 //! // Parse a MISP event from JSON
-//! let event_json = r#"{"id": "1", "info": "Test event", ...}"#;
+//! let event_json = r#"{"id": "1", "info": "Test event"}"#;
 //! let event: Event = serde_json::from_str(event_json)?;
 //!
 //! // Create a search request
-//! let mut search_req = SearchEventsRequest::default();
+//! let mut search_req = EventsRestSearchRequest::default();
 //! search_req.limit = Some(10);
 //! search_req.published = Some(true);
 //! # Ok::<(), Box<dyn std::error::Error>>(())
@@ -31,5 +61,11 @@
 
 // Re-export all types from the types module
 pub use types::*;
+pub use lenient::Lenient;
 
 pub mod types;
+pub mod indicators;
+pub mod lenient;
+
+#[cfg(feature = "schema")]
+pub mod schema;
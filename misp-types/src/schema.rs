@@ -0,0 +1,71 @@
+//! JSON Schema generation for MISP request types.
+//!
+//! Enabled by the `schema` feature. Tools that take a request struct as
+//! (stringified) JSON can hand these schemas to the caller so it knows which
+//! fields exist and how they are spelled on the wire.
+
+use crate::types::*;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::{schema_for, JsonSchema};
+use serde_json::Value;
+
+/// Names accepted by [`request_schema`].
+pub const REQUEST_TYPES: &[&str] = &[
+    "AddAttributeRequest",
+    "AddCollectionElementRequest",
+    "AddObjectReferenceRequest",
+    "AttributeRestSearchRequest",
+    "CollectionFilterBody",
+    "CreateCollectionRequest",
+    "EventIndexRequest",
+    "EventsRestSearchRequest",
+    "ObjectsRestSearchRequest",
+];
+
+/// Return the JSON Schema for a request type by its Rust name.
+///
+/// Returns `None` for names not listed in [`REQUEST_TYPES`].
+pub fn request_schema(request_type: &str) -> Option<Value> {
+    let schema = match request_type {
+        "AddAttributeRequest" => schema_for!(AddAttributeRequest),
+        "AddCollectionElementRequest" => schema_for!(AddCollectionElementRequest),
+        "AddObjectReferenceRequest" => schema_for!(AddObjectReferenceRequest),
+        "AttributeRestSearchRequest" => schema_for!(AttributeRestSearchRequest),
+        "CollectionFilterBody" => schema_for!(CollectionFilterBody),
+        "CreateCollectionRequest" => schema_for!(CreateCollectionRequest),
+        "EventIndexRequest" => schema_for!(EventIndexRequest),
+        "EventsRestSearchRequest" => schema_for!(EventsRestSearchRequest),
+        "ObjectsRestSearchRequest" => schema_for!(ObjectsRestSearchRequest),
+        _ => return None,
+    };
+    serde_json::to_value(schema).ok()
+}
+
+/// Top-level fields of `request` that the request type does not define.
+///
+/// Serde silently ignores unknown fields, so a misspelled filter (`limitt`)
+/// would otherwise just be dropped. Returns `None` for unknown request
+/// types or when `request` is not a JSON object.
+pub fn unknown_fields(request_type: &str, request: &Value) -> Option<Vec<String>> {
+    let schema = request_schema(request_type)?;
+    let known = schema.get("properties")?.as_object()?;
+    let fields = request.as_object()?;
+    Some(fields.keys().filter(|field| !known.contains_key(*field)).cloned().collect())
+}
+
+/// Distribution has hand-written serde impls, so its schema is spelled out
+/// to match what `Deserialize` accepts: a level number or its name.
+impl JsonSchema for Distribution {
+    fn schema_name() -> String {
+        "Distribution".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        serde_json::from_value(serde_json::json!({
+            "description": "Distribution level: 0-5 (number or numeric string) or its name (\"your organisation only\", \"this community only\", \"connected communities\", \"all communities\", \"sharing group\", \"inherit event\")",
+            "type": ["string", "integer"],
+        }))
+        .expect("static schema is valid")
+    }
+}
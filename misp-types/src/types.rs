@@ -65,6 +65,40 @@ where
     deserializer.deserialize_any(BoolOrStringVisitor)
 }
 
+/// Custom deserializer for list fields that MISP sends as `null` when a page is empty
+fn deserialize_null_as_empty_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Custom deserializer for a galaxy's `kill_chain_order` (kill chain name to ordered phases).
+///
+/// MISP sends an object, `null`, `[]` (an empty PHP array) or, on some
+/// versions, the object JSON-encoded in a string. Empty values become `None`.
+fn deserialize_kill_chain_order<'de, D>(deserializer: D) -> Result<Option<HashMap<String, Vec<String>>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::{self, Unexpected};
+
+    let value = match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(encoded)) if encoded.trim().is_empty() => return Ok(None),
+        Some(Value::String(encoded)) => serde_json::from_str(&encoded).map_err(de::Error::custom)?,
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    match value {
+        Value::Null => Ok(None),
+        Value::Array(items) if items.is_empty() => Ok(None),
+        Value::Object(chains) if chains.is_empty() => Ok(None),
+        Value::Object(chains) => serde_json::from_value(Value::Object(chains)).map(Some).map_err(de::Error::custom),
+        other => Err(de::Error::invalid_type(Unexpected::Other(&other.to_string()), &"a kill chain order object")),
+    }
+}
+
 // User object based on official schema with clarifications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -262,8 +296,8 @@ pub struct Role {
     /// Enforce rate limit - boolean (missing from response)
     #[serde(rename = "enforce_rate_limit")]
     pub enforce_rate_limit: Option<bool>,
-    /// Rate limit count - string ^\d+$ (missing from response)
-    #[serde(rename = "rate_limit_count")]
+    /// Rate limit count - string ^\d+$ or number (missing from response)
+    #[serde(rename = "rate_limit_count", default, deserialize_with = "deserialize_optional_string_or_number")]
     pub rate_limit_count: Option<String>,
     /// Permission - string ^\d+$ (missing from response)
     pub permission: Option<String>,
@@ -297,6 +331,30 @@ pub struct Role {
     pub perm_sync_authoritative: Option<bool>,
 }
 
+impl Role {
+    /// Names of the permissions this role grants (the `perm_*` flags set to true), sorted.
+    pub fn granted_permissions(&self) -> Vec<String> {
+        let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(self) else {
+            return Vec::new();
+        };
+        let mut granted: Vec<String> = fields
+            .into_iter()
+            .filter(|(name, value)| name.starts_with("perm_") && value.as_bool() == Some(true))
+            .map(|(name, _)| name)
+            .collect();
+        granted.sort();
+        granted
+    }
+}
+
+/// Role entry wrapper for GET /roles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleEntry {
+    /// Role object
+    #[serde(rename = "Role")]
+    pub role: Role,
+}
+
 // Organisation object based on official schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Organisation {
@@ -336,8 +394,8 @@ pub struct Organisation {
     pub restricted_to_domain: Option<Vec<String>>,
     /// Landing page - string (get_user_by_id only)
     pub landingpage: Option<String>,
-    /// User count - string (get_organisations endpoint)
-    #[serde(rename = "user_count")]
+    /// User count - string or number (get_organisations endpoint)
+    #[serde(rename = "user_count", default, deserialize_with = "deserialize_optional_string_or_number")]
     pub user_count: Option<String>,
     /// Created by email - string (get_organisations endpoint)
     #[serde(rename = "created_by_email")]
@@ -388,11 +446,17 @@ pub enum UserSetting {
     Object(UserSettingObject),
 }
 
+impl Default for UserSetting {
+    fn default() -> Self {
+        UserSetting::Array(Vec::new())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserSettingObject {
-    /// Publish alert filter - complex object
+    /// Publish alert filter - object, or `[]` when unset
     #[serde(rename = "publish_alert_filter")]
-    pub publish_alert_filter: Option<Vec<serde_json::Value>>,
+    pub publish_alert_filter: Option<serde_json::Value>,
     /// Dashboard access - boolean
     #[serde(rename = "dashboard_access")]
     pub dashboard_access: Option<bool>,
@@ -400,17 +464,66 @@ pub struct UserSettingObject {
     pub dashboard: Option<Vec<DashboardWidget>>,
     /// Homepage settings - object
     pub homepage: Option<Homepage>,
-    /// Default REST search parameters - object
+    /// Default REST search parameters - object, or `[]` when unset
     #[serde(rename = "default_restsearch_parameters")]
-    pub default_restsearch_parameters: Option<Vec<serde_json::Value>>,
-    /// Tag numerical value override - object
+    pub default_restsearch_parameters: Option<serde_json::Value>,
+    /// Tag numerical value override - object, or `[]` when unset
     #[serde(rename = "tag_numerical_value_override")]
-    pub tag_numerical_value_override: Option<Vec<serde_json::Value>>,
+    pub tag_numerical_value_override: Option<serde_json::Value>,
     /// Event index hide columns - array
     #[serde(rename = "event_index_hide_columns")]
     pub event_index_hide_columns: Option<Vec<String>>,
 }
 
+/// Normalized user settings with the same shape whatever MISP sent.
+///
+/// MISP serializes an empty PHP array as `[]`, both for the whole
+/// `UserSetting` block and for object-valued settings; those become
+/// defaults / empty objects here.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UserSettings {
+    pub dashboard_access: bool,
+    pub dashboard: Vec<DashboardWidget>,
+    /// Homepage path, if one is set
+    pub homepage: Option<String>,
+    pub default_restsearch_parameters: Value,
+    pub tag_numerical_value_override: Value,
+    pub publish_alert_filter: Value,
+    pub event_index_hide_columns: Vec<String>,
+}
+
+impl UserSetting {
+    /// Collapse the array/object variants into [`UserSettings`].
+    pub fn normalized(&self) -> UserSettings {
+        // `[]` and missing both mean "no settings" for object-valued fields
+        fn object_or_empty(value: &Option<Value>) -> Value {
+            match value {
+                Some(Value::Array(items)) if items.is_empty() => Value::Object(Default::default()),
+                Some(value) => value.clone(),
+                None => Value::Object(Default::default()),
+            }
+        }
+
+        match self {
+            UserSetting::Array(_) => UserSettings {
+                default_restsearch_parameters: Value::Object(Default::default()),
+                tag_numerical_value_override: Value::Object(Default::default()),
+                publish_alert_filter: Value::Object(Default::default()),
+                ..Default::default()
+            },
+            UserSetting::Object(settings) => UserSettings {
+                dashboard_access: settings.dashboard_access.unwrap_or(false),
+                dashboard: settings.dashboard.clone().unwrap_or_default(),
+                homepage: settings.homepage.as_ref().map(|h| h.path.clone()),
+                default_restsearch_parameters: object_or_empty(&settings.default_restsearch_parameters),
+                tag_numerical_value_override: object_or_empty(&settings.tag_numerical_value_override),
+                publish_alert_filter: object_or_empty(&settings.publish_alert_filter),
+                event_index_hide_columns: settings.event_index_hide_columns.clone().unwrap_or_default(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DashboardWidget {
     /// Widget type
@@ -437,7 +550,7 @@ pub struct Homepage {
     pub path: String,
 }
 
-/// Response type for GET /admin/users/view/{id} endpoint
+/// Response type for GET /admin/users/view/{id} and GET /users/view/me endpoints
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetUserByIdResponse {
     // Flat fields at root level (optional as per user requirement)
@@ -505,8 +618,8 @@ pub struct GetUserByIdResponse {
     /// Role object
     #[serde(rename = "Role")]
     pub role: Role,
-    /// User settings - can be array or object
-    #[serde(rename = "UserSetting")]
+    /// User settings - can be array or object (missing from /users/view/me)
+    #[serde(rename = "UserSetting", default)]
     pub user_setting: UserSetting,
     /// Organisation object
     #[serde(rename = "Organisation")]
@@ -533,15 +646,19 @@ pub struct Galaxy {
     #[serde(rename = "type")]
     pub galaxy_type: String,
     /// Galaxy description - string (GalaxyDescription) <= 65535 characters
+    #[serde(default)]
     pub description: String,
     /// Galaxy version - string (GalaxyVersion) <= 255 characters
+    #[serde(default)]
     pub version: String,
     /// Icon - string (Icon) <= 255 characters (optional - not present in all endpoints)
     pub icon: Option<String>,
     /// Namespace - string (GalaxyNamespace) <= 255 characters
+    #[serde(default)]
     pub namespace: String,
-    /// Kill chain order - object Nullable (dynamic structure)
-    pub kill_chain_order: Option<serde_json::Value>,
+    /// Kill chain order - kill chain name to its phases, in order (None when empty)
+    #[serde(default, deserialize_with = "deserialize_kill_chain_order")]
+    pub kill_chain_order: Option<HashMap<String, Vec<String>>>,
     
     // Extra fields found in API response (all optional)
     /// Whether galaxy is enabled
@@ -669,6 +786,12 @@ pub struct GalaxyClusterRelation {
     /// Target galaxy cluster UUID - string
     #[serde(rename = "referenced_galaxy_cluster_uuid")]
     pub referenced_galaxy_cluster_uuid: String,
+    /// Target galaxy cluster ID - string (absent when the target is not on this instance)
+    #[serde(rename = "referenced_galaxy_cluster_id", default)]
+    pub referenced_galaxy_cluster_id: Option<String>,
+    /// Relationship type - string (e.g. "uses", "similar")
+    #[serde(rename = "referenced_galaxy_cluster_type", default)]
+    pub referenced_galaxy_cluster_type: Option<String>,
 }
 
 /// Targeting cluster relation object
@@ -711,7 +834,7 @@ pub struct GalaxyCluster {
     /// Cluster UUID - string
     pub uuid: String,
     /// Collection UUID - string
-    #[serde(rename = "collection_uuid")]
+    #[serde(rename = "collection_uuid", default)]
     pub collection_uuid: String,
     /// Cluster type - string
     #[serde(rename = "type")]
@@ -722,36 +845,43 @@ pub struct GalaxyCluster {
     #[serde(rename = "tag_name")]
     pub tag_name: String,
     /// Description - string
+    #[serde(default)]
     pub description: String,
     /// Galaxy ID - string
     #[serde(rename = "galaxy_id")]
     pub galaxy_id: String,
     /// Source - string
+    #[serde(default)]
     pub source: String,
-    /// Authors - array of strings
+    /// Authors - array of strings (null on clusters without authors)
+    #[serde(default, deserialize_with = "deserialize_null_as_empty_vec")]
     pub authors: Vec<String>,
     /// Version - string
+    #[serde(default)]
     pub version: String,
     /// Distribution - string
+    #[serde(default)]
     pub distribution: String,
     /// Sharing group ID - string (nullable)
     #[serde(rename = "sharing_group_id")]
     pub sharing_group_id: Option<String>,
     /// Organisation ID - string
-    #[serde(rename = "org_id")]
+    #[serde(rename = "org_id", default)]
     pub org_id: String,
     /// Organisation creator ID - string
-    #[serde(rename = "orgc_id")]
+    #[serde(rename = "orgc_id", default)]
     pub orgc_id: String,
     /// Cluster extends UUID - string (nullable)
     #[serde(rename = "extends_uuid")]
     pub extends_uuid: Option<String>,
     /// Cluster extends version - string
-    #[serde(rename = "extends_version")]
+    #[serde(rename = "extends_version", default)]
     pub extends_version: String,
     /// Published - boolean
+    #[serde(default)]
     pub published: bool,
     /// Deleted - boolean
+    #[serde(default)]
     pub deleted: bool,
     /// Locked - boolean (found in get_galaxy_clusters response)
     pub locked: Option<bool>,
@@ -1002,6 +1132,18 @@ pub struct TaxonomyPredicate {
 /// Returns array of search tag entries with variable structure
 pub type SearchTagsResponse = Vec<SearchTagEntry>;
 
+/// Response for GET /tags/tagStatistics/{percentage}/{keysort}
+/// Counts are numbers; in percentage mode MISP sends strings such as "12.5%".
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TagStatisticsResponse {
+    /// Tag name -> usage count (or percentage)
+    #[serde(default)]
+    pub tags: HashMap<String, Value>,
+    /// Taxonomy namespace -> usage count (or percentage)
+    #[serde(default)]
+    pub taxonomies: HashMap<String, Value>,
+}
+
 
 /// Sighting object for MISP get_sightings_by_EventId endpoint
 /// All fields are optional to handle incomplete or partial API responses
@@ -1030,7 +1172,7 @@ pub struct Sighting {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
     /// Sighting type - string (free text, e.g., "0", "1", "false", "true")
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "type")]
     pub type_: Option<String>,
     /// Organisation object (nested) - optional, future-proof
     #[serde(default, skip_serializing_if = "Option::is_none", rename = "Organisation")]
@@ -1083,8 +1225,8 @@ pub struct Noticelist {
     pub version: Option<String>,
     /// Whether the noticelist is enabled
     pub enabled: bool,
-    /// Number of entries in the noticelist (digits, optional)
-    #[serde(default)]
+    /// Number of entries in the noticelist (digits or number, optional)
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
     pub warninglist_entry_count: Option<String>,
     /// Valid attribute types for this noticelist (comma-separated string, optional)
     #[serde(default)]
@@ -1203,8 +1345,8 @@ pub struct Warninglist {
     /// Category of the warninglist (optional)
     #[serde(default)]
     pub category: Option<String>,
-    /// Number of entries in the warninglist (optional, as string)
-    #[serde(default)]
+    /// Number of entries in the warninglist (optional, string or number)
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
     pub warninglist_entry_count: Option<String>,
     /// Valid attribute types for this warninglist (optional, comma-separated string)
     #[serde(default)]
@@ -1218,17 +1360,17 @@ pub struct Warninglist {
 
 }
 
-/// Placeholder for future warninglist entry objects
-/// Not present in current /warninglists response, but included for schema compatibility
+/// A single value of a warninglist (e.g. a CIDR, hostname or string)
+/// Returned by GET /warninglists/view/{id}; not present in the /warninglists index
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct WarninglistEntry {
-    /// Entry ID (optional, future use)
+    /// Entry ID
     #[serde(default)]
     pub id: Option<String>,
-    /// Entry value (optional, future use)
+    /// Entry value
     #[serde(default)]
     pub value: Option<String>,
-    /// Associated warninglist ID (optional, future use)
+    /// Associated warninglist ID
     #[serde(default)]
     pub warninglist_id: Option<String>,
     /// Comment or note associated with the warninglist entry (optional)
@@ -1256,6 +1398,23 @@ pub struct SearchWarninglistRequest {
     pub value: String,
 }
 
+/// A warninglist matched by a value, as returned by POST /warninglists/checkValue
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WarninglistHit {
+    /// Warninglist ID (string or number depending on MISP version)
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub id: Option<String>,
+    /// Name of the warninglist
+    pub name: String,
+    /// Warninglist entry the value matched (newer MISP versions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched: Option<String>,
+}
+
+/// Response for POST /warninglists/checkValue: the hits for each checked value.
+/// Values that matched no warninglist are left out.
+pub type CheckWarninglistValuesResponse = HashMap<String, Vec<WarninglistHit>>;
+
 /// EventReport entry as returned by /eventReports/index.
 /// This struct matches the live API response and is future-proofed for optional fields.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1392,6 +1551,7 @@ pub struct CollectionElement {
 
 /// Request body for POST /collections/index/{filter}
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CollectionFilterBody {
     #[serde(rename = "Collection.uuid", skip_serializing_if = "Option::is_none")]
     pub uuid: Option<String>,
@@ -1401,6 +1561,47 @@ pub struct CollectionFilterBody {
     pub name: Option<String>,
 }
 
+/// Request body for POST /collections/add
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CreateCollectionRequest {
+    /// Collection type ("campaign", "intrusion_set", "named_set" or "other")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Collection name
+    pub name: String,
+    /// Description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Distribution level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<Distribution>,
+    /// Sharing group ID (only used with distribution "4")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sharing_group_id: Option<String>,
+}
+
+/// Request body for POST /collectionElements/add/{collectionId}
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AddCollectionElementRequest {
+    /// Type of the element: "Event" or "GalaxyCluster"
+    pub element_type: String,
+    /// UUID of the event or galaxy cluster
+    pub element_uuid: String,
+    /// Description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Response for POST /collectionElements/add/{collectionId}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionElementWrapper {
+    /// The added element, under the "CollectionElement" key
+    #[serde(rename = "CollectionElement")]
+    pub collection_element: CollectionElement,
+}
+
 /// Accepts both string and integer for distribution field.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -1482,12 +1683,15 @@ pub struct Attribute {
     /// Attribute ID - string (AttributeId)
     pub id: String,
     /// Event ID - string (EventId)
+    #[serde(default)]
     pub event_id: String,
     /// Object ID - string (ObjectId)
+    #[serde(default)]
     pub object_id: String,
     /// Object relation - string (NullableObjectRelation)
     pub object_relation: Option<String>,
     /// Category - string (AttributeCategory)
+    #[serde(default)]
     pub category: String,
     /// Type - string (AttributeType)
     #[serde(rename = "type")]
@@ -1499,20 +1703,24 @@ pub struct Attribute {
     /// Value2 - string (present in data, not schema)
     pub value2: Option<String>,
     /// To IDS - boolean (ToIDS)
+    #[serde(default)]
     pub to_ids: bool,
     /// UUID - string (UUID)
     pub uuid: String,
     /// Timestamp - string (NullableTimestamp)
     pub timestamp: Option<String>,
     /// Distribution - string (DistributionLevelId)
+    #[serde(default)]
     pub distribution: String,
     /// Sharing group ID - string (SharingGroupId)
     pub sharing_group_id: Option<String>,
     /// Comment - string (AttributeComment)
     pub comment: Option<String>,
     /// Deleted - boolean (SoftDeletedFlag)
+    #[serde(default)]
     pub deleted: bool,
     /// Disable correlation - boolean (DisableCorrelationFlag)
+    #[serde(default)]
     pub disable_correlation: bool,
     /// First seen - string (nullable)
     pub first_seen: Option<String>,
@@ -1539,6 +1747,32 @@ pub struct Attribute {
     /// AttributeTag array (optional, for future compatibility)
     #[serde(rename = "AttributeTag", default)]
     pub attribute_tag: Option<Vec<AttributeTag>>,    
+    /// Sightings - only present when searched with includeSightings
+    #[serde(rename = "Sighting", default, skip_serializing_if = "Option::is_none")]
+    pub sighting: Option<Vec<Sighting>>,
+    /// Correlating attributes in other events - present in /events/view responses
+    #[serde(rename = "RelatedAttribute", default, skip_serializing_if = "Option::is_none")]
+    pub related_attribute: Option<Vec<RelatedAttribute>>,
+}
+
+/// Attribute of another event that correlates with an attribute
+/// (RelatedAttribute entries of /events/view)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedAttribute {
+    /// Attribute ID - string (AttributeId)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// ID of the event holding the correlating attribute - string (EventId)
+    pub event_id: String,
+    /// Organisation ID of that event - string (OrganisationId)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    /// Correlating value - string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Info of the event holding the correlating attribute - string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
 }
 
 /// Tag object for attributes (as seen in AttributeTag array)
@@ -1563,7 +1797,7 @@ pub struct DecayScoreEntry {
 #[serde(untagged)]
 pub enum DecayingModelEnum {
     Minimal(DecayingModel),
-    Full(FullDecayingModel),
+    Full(Box<FullDecayingModel>),
 }
 
 /// Minimal DecayingModel (id and name only)
@@ -1617,14 +1851,15 @@ pub struct SaneDefault {
 }
 
 /// Request struct for /attributes/restSearch (all fields from official schema, all Option<T>)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AttributeRestSearchRequest {
     /// Page number (>= 1)
     pub page: Option<u32>,
     /// Maximum number of results (0 means maximum allowed)
     pub limit: Option<u32>,
-    /// Attribute value filter
-    pub value: Option<String>,
+    /// Attribute value filter: a single value or a list of values
+    pub value: Option<serde_json::Value>,
     /// Attribute value1 filter
     pub value1: Option<String>,
     /// Attribute value2 filter
@@ -1732,6 +1967,7 @@ pub struct AttributeRestSearchRequest {
 
 /// ModelOverridesRestSearchFilter object for decaying model overrides
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ModelOverridesRestSearchFilter {
     /// Lifetime override
     pub lifetime: Option<f64>,
@@ -1756,12 +1992,95 @@ pub struct AttributeListResponse {
     pub response: AttributeListResponseInner,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Body of the /attributes/restSearch `response` key.
+///
+/// MISP versions differ in the envelope: either `{"Attribute": [...]}` or
+/// `[{"Attribute": {...}}, ...]`. Both deserialize into the same list;
+/// serialization always uses the object form.
+#[derive(Debug, Clone, Serialize)]
 pub struct AttributeListResponseInner {
     #[serde(rename = "Attribute")]
     pub attribute: Vec<Attribute>,
 }
 
+impl<'de> Deserialize<'de> for AttributeListResponseInner {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct ListEnvelope {
+            #[serde(rename = "Attribute", default)]
+            attribute: Vec<Attribute>,
+        }
+
+        #[derive(Deserialize)]
+        struct EntryEnvelope {
+            #[serde(rename = "Attribute")]
+            attribute: Attribute,
+        }
+
+        use serde::de::Error;
+
+        // Dispatch on the shape rather than `untagged` so field errors stay specific
+        let attribute = match Value::deserialize(deserializer)? {
+            // Past the last page some MISP versions send `null`
+            Value::Null => Vec::new(),
+            Value::Array(entries) => serde_json::from_value::<Vec<EntryEnvelope>>(Value::Array(entries))
+                .map_err(D::Error::custom)?
+                .into_iter()
+                .map(|entry| entry.attribute)
+                .collect(),
+            other => serde_json::from_value::<ListEnvelope>(other)
+                .map_err(D::Error::custom)?
+                .attribute,
+        };
+        Ok(Self { attribute })
+    }
+}
+
+// =============================================================================
+// Types for POST /attributes/add/{eventId}
+// =============================================================================
+
+/// Request body for POST /attributes/add/{eventId}
+///
+/// Only `type` and `value` are required; MISP fills the rest from the type's
+/// sane defaults. Unset fields are left out of the body entirely.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AddAttributeRequest {
+    /// Attribute type (e.g. "ip-dst", "domain", "sha256")
+    #[serde(rename = "type")]
+    pub attribute_type: String,
+    /// Attribute value
+    pub value: String,
+    /// Attribute category (defaults to the type's default category)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// To IDS flag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_ids: Option<bool>,
+    /// Distribution level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<Distribution>,
+    /// Sharing group ID (only used with distribution "4")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sharing_group_id: Option<String>,
+    /// Comment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Disable correlation flag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_correlation: Option<bool>,
+    /// First seen (ISO 8601)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    /// Last seen (ISO 8601)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<String>,
+}
+
     /// DecayScore for an attribute
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct DecayScore {
@@ -1824,6 +2143,7 @@ pub struct Event {
     /// Event ID - string (EventId) <= 10 characters ^\d+$
     pub id: String,
     /// Event info - string (EventInfo) <= 65535 characters
+    #[serde(default)]
     pub info: String,
     /// Event UUID - string <uuid> (UUID)
     pub uuid: Option<String>,
@@ -1837,12 +2157,13 @@ pub struct Event {
     pub orgc_id: Option<String>,
     /// Event date - string
     pub date: Option<String>,
-    /// Published flag - boolean (PublishedFlag)
+    /// Published flag - boolean (PublishedFlag) - some MISP versions send "0"/"1" strings
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
     pub published: Option<bool>,
     /// Analysis level - string (AnalysisLevelId) "0"-"2"
     pub analysis: Option<String>,
-    /// Attribute count - string (EventAttributeCount) ^\\d+$
-    #[serde(rename = "attribute_count")]
+    /// Attribute count - string (EventAttributeCount) ^\\d+$, or a number on some endpoints
+    #[serde(rename = "attribute_count", default, deserialize_with = "deserialize_optional_string_or_number")]
     pub attribute_count: Option<String>,
     /// Timestamp - string (NullableTimestamp) Nullable ^\\d+$|^$
     pub timestamp: Option<String>,
@@ -1852,7 +2173,8 @@ pub struct Event {
     /// Proposal email lock - boolean (EventProposalEmailLock)
     #[serde(rename = "proposal_email_lock")]
     pub proposal_email_lock: Option<bool>,
-    /// Locked flag - boolean (IsLocked)
+    /// Locked flag - boolean (IsLocked) - may be sent as a string
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
     pub locked: Option<bool>,
     /// Threat level ID - string (ThreatLevelId) "1"-"4"
     #[serde(rename = "threat_level_id")]
@@ -1863,8 +2185,8 @@ pub struct Event {
     /// Sighting timestamp - string (Timestamp) ^\\d+$, default "0"
     #[serde(rename = "sighting_timestamp")]
     pub sighting_timestamp: Option<String>,
-    /// Disable correlation flag - boolean (DisableCorrelationFlag)
-    #[serde(rename = "disable_correlation")]
+    /// Disable correlation flag - boolean (DisableCorrelationFlag) - may be sent as a string
+    #[serde(rename = "disable_correlation", deserialize_with = "deserialize_bool_or_empty_string", default)]
     pub disable_correlation: Option<bool>,
     /// Extends UUID - string (ExtendsUUID) <= 36 characters Nullable
     #[serde(rename = "extends_uuid")]
@@ -1910,7 +2232,8 @@ pub struct Event {
     #[serde(rename = "Tag", default)]
     pub tag: Vec<Tag>,
     /// Protected flag (optional, from API response)
-    /// Added to match API response field "protected"
+    /// Added to match API response field "protected" - may be sent as a string
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
     pub protected: Option<bool>,
     ///orgc_uuid found in restSearch response for Event
     pub orgc_uuid: Option<String>,
@@ -1989,9 +2312,111 @@ pub struct Object {
     /// Event Object from official schema (optional)
     #[serde(rename = "Event", default)]
     pub event: Option<Event>,
+    /// References from this object to other objects/attributes (optional)
+    #[serde(rename = "ObjectReference", default, skip_serializing_if = "Option::is_none")]
+    pub object_references: Option<Vec<ObjectReference>>,
 }
 
+// =============================================================================
+// Types for POST /objectReferences/add/{objectId}
+// =============================================================================
 
+/// Request body for POST /objectReferences/add/{objectId}
+///
+/// Links the object to another object or attribute by UUID.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AddObjectReferenceRequest {
+    /// UUID of the referenced object or attribute
+    pub referenced_uuid: String,
+    /// Relationship type (e.g. "downloaded-from", "drops", "related-to")
+    pub relationship_type: String,
+    /// Comment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Object reference as returned by MISP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectReference {
+    /// Reference ID - string
+    pub id: String,
+    /// Reference UUID - string <uuid>
+    pub uuid: Option<String>,
+    /// Timestamp - string (unix seconds)
+    pub timestamp: Option<String>,
+    /// Source object ID - string
+    pub object_id: Option<String>,
+    /// Source object UUID - string <uuid>
+    pub object_uuid: Option<String>,
+    /// Event ID - string
+    pub event_id: Option<String>,
+    /// Referenced object/attribute ID - string
+    pub referenced_id: Option<String>,
+    /// Referenced object/attribute UUID - string <uuid>
+    pub referenced_uuid: Option<String>,
+    /// Referenced type - "0" for attribute, "1" for object (number on some versions)
+    pub referenced_type: Option<serde_json::Value>,
+    /// Relationship type - string
+    pub relationship_type: Option<String>,
+    /// Comment - string
+    pub comment: Option<String>,
+    /// Deleted flag - boolean (some MISP versions send "0"/"1" strings)
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
+    pub deleted: Option<bool>,
+}
+
+/// Response for POST /objectReferences/add/{objectId}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectReferenceWrapper {
+    /// The created reference, under the "ObjectReference" key
+    #[serde(rename = "ObjectReference")]
+    pub object_reference: ObjectReference,
+}
+
+
+
+// =============================================================================
+// Object Template Types for GET /objectTemplates endpoint
+// =============================================================================
+
+/// Object template as returned by GET /objectTemplates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectTemplate {
+    /// Object template ID - string (ObjectTemplateId)
+    pub id: String,
+    /// Template UUID - string <uuid> (used as template_uuid on objects)
+    pub uuid: String,
+    /// Template name (e.g. "file", "domain-ip")
+    pub name: String,
+    /// Template version - number or numeric string depending on MISP version
+    pub version: Value,
+    /// Meta category - string (ObjectMetaCategory)
+    #[serde(rename = "meta-category", default)]
+    pub meta_category: Option<String>,
+    /// Description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Required/requiredOneOf object relations
+    #[serde(default)]
+    pub requirements: Option<Value>,
+    /// Creator organisation ID
+    #[serde(default)]
+    pub org_id: Option<String>,
+    /// Fixed (shipped with MISP) flag
+    #[serde(default)]
+    pub fixed: Option<bool>,
+    /// Active flag
+    #[serde(default)]
+    pub active: Option<bool>,
+}
+
+/// Entry in the GET /objectTemplates response array
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectTemplateEntry {
+    #[serde(rename = "ObjectTemplate")]
+    pub object_template: ObjectTemplate,
+}
 
 /// Feed object for GET /events 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2066,8 +2491,142 @@ pub struct RelatedEvent {
 }
 
 
+// =============================================================================
+// Distribution level shared by request types
+// =============================================================================
+
+/// MISP distribution level (DistributionLevelId "0"-"5").
+///
+/// Parses both the numeric string MISP expects and the human-readable names
+/// shown in the MISP UI (e.g. "Your organisation only"), and always serializes
+/// back to the numeric string. Only the full names are accepted: a shorthand
+/// such as "all" could be read as a wider level than the caller meant.
+///
+/// ```rust
+/// use misp_types::Distribution;
+///
+/// let level: Distribution = "Your organisation only".parse().unwrap();
+/// assert_eq!(level, Distribution::YourOrganisationOnly);
+/// assert_eq!(serde_json::to_string(&level).unwrap(), r#""0""#);
+/// assert_eq!(serde_json::from_str::<Distribution>(r#""0""#).unwrap(), level);
+/// assert!("all".parse::<Distribution>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Distribution {
+    /// "0" - Your organisation only
+    YourOrganisationOnly,
+    /// "1" - This community only
+    ThisCommunityOnly,
+    /// "2" - Connected communities
+    ConnectedCommunities,
+    /// "3" - All communities
+    AllCommunities,
+    /// "4" - Sharing group
+    SharingGroup,
+    /// "5" - Inherit event
+    InheritEvent,
+}
+
+impl Distribution {
+    /// Numeric string representation sent to MISP.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Distribution::YourOrganisationOnly => "0",
+            Distribution::ThisCommunityOnly => "1",
+            Distribution::ConnectedCommunities => "2",
+            Distribution::AllCommunities => "3",
+            Distribution::SharingGroup => "4",
+            Distribution::InheritEvent => "5",
+        }
+    }
+
+    /// Human-readable name as displayed by MISP.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::YourOrganisationOnly => "Your organisation only",
+            Distribution::ThisCommunityOnly => "This community only",
+            Distribution::ConnectedCommunities => "Connected communities",
+            Distribution::AllCommunities => "All communities",
+            Distribution::SharingGroup => "Sharing group",
+            Distribution::InheritEvent => "Inherit event",
+        }
+    }
+}
+
+impl std::fmt::Display for Distribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when a distribution value cannot be recognised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDistributionError(pub String);
+
+impl std::fmt::Display for ParseDistributionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid distribution '{}': expected \"0\"-\"5\" or a name such as \"Your organisation only\"",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseDistributionError {}
+
+impl std::str::FromStr for Distribution {
+    type Err = ParseDistributionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Normalise case, separators and the organisation/organization spelling
+        let normalized = s
+            .trim()
+            .to_lowercase()
+            .replace(['_', '-'], " ")
+            .replace("organization", "organisation");
+
+        match normalized.as_str() {
+            "0" | "your organisation only" => Ok(Distribution::YourOrganisationOnly),
+            "1" | "this community only" => Ok(Distribution::ThisCommunityOnly),
+            "2" | "connected communities" => Ok(Distribution::ConnectedCommunities),
+            "3" | "all communities" => Ok(Distribution::AllCommunities),
+            "4" | "sharing group" => Ok(Distribution::SharingGroup),
+            "5" | "inherit event" => Ok(Distribution::InheritEvent),
+            _ => Err(ParseDistributionError(s.to_string())),
+        }
+    }
+}
+
+impl Serialize for Distribution {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Distribution {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Accept numeric strings, human names and bare integers
+        match Value::deserialize(deserializer)? {
+            Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+            Value::Number(n) => n.to_string().parse().map_err(serde::de::Error::custom),
+            other => Err(serde::de::Error::custom(format!(
+                "invalid distribution {}: expected a string or integer",
+                other
+            ))),
+        }
+    }
+}
+
 /// Request body for POST /events/index (Event search/filter)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EventIndexRequest {
     /// Page number (>= 1)
     pub page: Option<u32>,
@@ -2099,8 +2658,8 @@ pub struct EventIndexRequest {
     pub tag: Option<String>,
     /// Filter by any of a list of tag names
     pub tags: Option<Vec<String>>,
-    /// Distribution level ("0"-"5")
-    pub distribution: Option<String>,
+    /// Distribution level ("0"-"5" or its human-readable name)
+    pub distribution: Option<Distribution>,
     /// Sharing group ID (<= 10 digits)
     #[serde(rename = "sharinggroup")]
     pub sharing_group: Option<String>,
@@ -2132,6 +2691,7 @@ pub struct EventIndexRequest {
 
 /// Request body for POST /events/restSearch (filtered and paginated event search)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EventsRestSearchRequest {
     /// Page number (>= 1)
     pub page: Option<u32>,
@@ -2219,6 +2779,8 @@ pub struct EventsRestSearchRequest {
     pub extending: Option<bool>,
     /// Only events extended by another (see docs)
     pub extended: Option<bool>,
+    /// Return minimal event objects (id, uuid, timestamps, published); see `MinimalEventsRestSearchResponse`
+    pub minimal: Option<bool>,
     /// Response format (see API docs for enum)
     #[serde(rename = "returnFormat")]
     pub return_format: Option<String>,
@@ -2228,6 +2790,8 @@ pub struct EventsRestSearchRequest {
 /// The API returns: { "response": [ { "Event": { ... } }, ... ] }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventsRestSearchResponse {
+    /// `null` past the last page on some MISP versions
+    #[serde(default, deserialize_with = "deserialize_null_as_empty_vec")]
     pub response: Vec<EventWrapper>,
 }
 
@@ -2238,9 +2802,44 @@ pub struct EventWrapper {
     pub event: Event,
 }
 
+/// Response wrapper for POST /events/restSearch with `minimal: true`.
+/// The API returns: { "response": [ { "Event": { "id", "uuid", "timestamp", ... } }, ... ] }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinimalEventsRestSearchResponse {
+    /// `null` past the last page on some MISP versions
+    #[serde(default, deserialize_with = "deserialize_null_as_empty_vec")]
+    pub response: Vec<MinimalEventWrapper>,
+}
+
+/// Helper struct for the array of minimal { "Event": { ... } }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinimalEventWrapper {
+    #[serde(rename = "Event")]
+    pub event: MinimalEvent,
+}
+
+/// Event as returned by restSearch in minimal mode (no info, attributes or tags)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinimalEvent {
+    /// Event ID - string
+    pub id: String,
+    /// Event UUID - string
+    pub uuid: Option<String>,
+    /// Last modification timestamp - string (unix seconds)
+    pub timestamp: Option<String>,
+    /// Last sighting timestamp - string (unix seconds, "0" when never sighted)
+    pub sighting_timestamp: Option<String>,
+    /// Published flag - boolean (some MISP versions send "0"/"1" strings)
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
+    pub published: Option<bool>,
+    /// Creator organisation UUID - string
+    pub orgc_uuid: Option<String>,
+}
+
 /// Request payload for POST /objects/restsearch endpoint
 /// Official schema: https://www.misp-project.org/documentation/
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ObjectsRestSearchRequest {
     /// Page number (>= 1)
     pub page: Option<u32>,
@@ -2373,3 +2972,160 @@ pub struct ObjectsRestSearchRequest {
     #[serde(rename = "returnFormat")]
     pub return_format: Option<String>,
 }
+
+// =============================================================================
+// Types for GET /logs/event_index/{eventId}
+// =============================================================================
+
+/// Accept a string or a number (MISP 2.5 sends some ids as integers) as a string
+fn deserialize_optional_string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(Value::Number(n)) => Ok(Some(n.to_string())),
+        Some(other) => Err(serde::de::Error::custom(format!(
+            "expected a string or number, got {}",
+            other
+        ))),
+    }
+}
+
+/// A single audit log entry (who changed what, and when)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Log ID - string or number
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub id: Option<String>,
+    /// Short title, e.g. "Attribute (12): ip-dst (1.2.3.4)"
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Creation time - string ("YYYY-MM-DD HH:MM:SS")
+    #[serde(default)]
+    pub created: Option<String>,
+    /// Changed model (Event, Attribute, Object, ...)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// ID of the changed model instance
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub model_id: Option<String>,
+    /// Action (add, edit, delete, publish, tag, ...)
+    #[serde(default)]
+    pub action: Option<String>,
+    /// Acting user's ID
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub user_id: Option<String>,
+    /// Acting user's email
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Acting user's organisation name
+    #[serde(default)]
+    pub org: Option<String>,
+    /// Field changes, e.g. "comment () => (seen in campaign)"
+    #[serde(default)]
+    pub change: Option<String>,
+    /// Free-text description
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Wrapper for the array of { "Log": { ... } }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntryWrapper {
+    #[serde(rename = "Log")]
+    pub log: LogEntry,
+}
+
+/// Response type for GET /logs/event_index/{eventId}
+pub type EventLogsResponse = Vec<LogEntryWrapper>;
+
+// =============================================================================
+// Types for GET /sharing_groups/view/{sharingGroupId}
+// =============================================================================
+
+/// A sharing group: the organisations and servers an event with
+/// distribution 4 is shared with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharingGroup {
+    /// Sharing group ID - string or number
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub id: Option<String>,
+    /// Sharing group UUID
+    #[serde(default)]
+    pub uuid: Option<String>,
+    /// Sharing group name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Releasability statement, e.g. "TLP:AMBER partners only"
+    #[serde(default)]
+    pub releasability: Option<String>,
+    /// Whether the group is active
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
+    pub active: Option<bool>,
+    /// Whether the group is shared without synchronising servers
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
+    pub roaming: Option<bool>,
+    /// Creator organisation ID - string or number
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub org_id: Option<String>,
+}
+
+/// Organisation membership of a sharing group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharingGroupOrg {
+    /// Member organisation ID - string or number
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub org_id: Option<String>,
+    /// Whether the organisation may edit the sharing group
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
+    pub extend: Option<bool>,
+    /// The member organisation
+    #[serde(rename = "Organisation", default)]
+    pub organisation: Option<OrgInfoMinimal>,
+}
+
+/// Server membership of a sharing group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharingGroupServer {
+    /// Server ID - string or number; "0" is this MISP instance
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub server_id: Option<String>,
+    /// Whether every organisation on the server receives the data
+    #[serde(deserialize_with = "deserialize_bool_or_empty_string", default)]
+    pub all_orgs: Option<bool>,
+    /// The member server (empty for this instance)
+    #[serde(rename = "Server", default)]
+    pub server: Option<SharingGroupServerInfo>,
+}
+
+/// Server summary within a sharing group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharingGroupServerInfo {
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Response for GET /sharing_groups/view/{sharingGroupId}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSharingGroupResponse {
+    #[serde(rename = "SharingGroup")]
+    pub sharing_group: SharingGroup,
+    /// Organisation that created the group
+    #[serde(rename = "Organisation", default)]
+    pub organisation: Option<OrgInfoMinimal>,
+    /// Member organisations
+    #[serde(rename = "SharingGroupOrg", default, deserialize_with = "deserialize_null_as_empty_vec")]
+    pub sharing_group_org: Vec<SharingGroupOrg>,
+    /// Member servers
+    #[serde(rename = "SharingGroupServer", default, deserialize_with = "deserialize_null_as_empty_vec")]
+    pub sharing_group_server: Vec<SharingGroupServer>,
+}